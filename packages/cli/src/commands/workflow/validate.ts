@@ -6,6 +6,7 @@
  *
  * Use --benchmark to see timing information for validator performance.
  * Use --wasm to force WASM validator (or --no-wasm to force TypeScript).
+ * Use --explain CODE to print the long-form documentation for a diagnostic code.
  */
 
 import path from 'path';
@@ -17,6 +18,7 @@ import {
 	getValidatorInfo,
 	benchmarkValidators,
 	isWasmAvailable,
+	explainCode,
 } from '../../lib/wasm-validator.js';
 
 interface ValidateOptions {
@@ -26,6 +28,7 @@ interface ValidateOptions {
 	iterations?: number;
 	wasm?: boolean;
 	compareValidators?: boolean;
+	explain?: string;
 }
 
 export async function workflowValidateCommand(
@@ -33,6 +36,34 @@ export async function workflowValidateCommand(
 	options: ValidateOptions = {}
 ): Promise<void> {
 	try {
+		// Explain a diagnostic code instead of validating
+		if (options.explain) {
+			const doc = await explainCode(options.explain);
+			if (!doc) {
+				Logger.error(`Unknown diagnostic code: ${options.explain} (or WASM validator unavailable)`);
+				process.exit(1);
+			}
+
+			if (options.json) {
+				console.log(JSON.stringify(doc, null, 2));
+				return;
+			}
+
+			Logger.header(`${doc.code}: ${doc.title}`);
+			Logger.listItem(`Category: ${doc.category}`);
+			Logger.listItem(`Default severity: ${doc.defaultSeverity}`);
+			Logger.blank();
+			Logger.log(doc.explanation);
+			Logger.blank();
+			Logger.section('Bad');
+			Logger.code(doc.badExample);
+			Logger.section('Good');
+			Logger.code(doc.goodExample);
+			Logger.blank();
+			Logger.log(`Docs: ${doc.docsUrl}`);
+			return;
+		}
+
 		const absolutePath = path.resolve(process.cwd(), workflowPath);
 
 		// Enable benchmark mode via flag or env var
@@ -154,6 +154,7 @@ workflowCommand
 	.option('--wasm', 'Force WASM validator')
 	.option('--no-wasm', 'Force TypeScript validator')
 	.option('--compare-validators', 'Compare validator performance')
+	.option('--explain <code>', 'Explain a diagnostic code (e.g. UNBOUNDED_SUBREQUESTS)')
 	.action(async (file: string, options: any) => {
 		try {
 			await workflowValidateCommand(file, options);
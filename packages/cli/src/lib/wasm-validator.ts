@@ -5,7 +5,8 @@
  * 1. Attempts to load the high-performance Rust/WASM validator
  * 2. Falls back to the TypeScript validator if WASM is unavailable
  *
- * The WASM validator is 10-50x faster due to pre-compiled regex patterns.
+ * The WASM validator compiles its regex patterns once and scans each file in a
+ * single pass; benchmarkValidators() measures both on a given workflow.
  *
  * Usage:
  *   const { validate, isWasmAvailable } = await loadValidator();
@@ -27,10 +28,143 @@ let wasmLoadError: Error | null = null;
  */
 interface WasmValidatorModule {
 	validate_workflow_wasm: (content: string) => ValidationResult;
+	validate_workflow_with_options: (content: string, options: WasmValidateOptions) => ValidationResult;
+	parse_config: (source: string) => ValidatorConfig;
+	WorkflowValidator: {
+		new (config?: ValidatorConfig): WasmWorkflowValidator;
+		fromToml: (source: string) => WasmWorkflowValidator;
+	};
+	explain: (code: string) => DiagnosticCodeDoc | undefined;
+	list_codes: () => DiagnosticCodeDoc[];
+	readiness: (content: string) => ReadinessReport;
+	diff_workflows: (oldContent: string, newContent: string) => WorkflowDiff;
 	get_version: () => string;
 	health_check: () => boolean;
 }
 
+/**
+ * Project configuration (`.workway-validator.toml`), as parsed by the WASM validator
+ */
+export interface ValidatorConfig {
+	rules: Record<string, boolean | 'error' | 'warning' | 'info' | 'hint'>;
+	thresholds: { max_console_statements: number };
+	modules: { blocked: string[]; allowed: string[] };
+	integrations: { known: string[] };
+	sdk: { version?: string | null };
+	custom_rules: Array<{
+		code: string;
+		severity: 'error' | 'warning' | 'info' | 'hint';
+		message: string;
+		suggestion?: string;
+		pattern?: string;
+		token?: string;
+		unless?: string;
+		scope: 'file' | 'execute' | 'loop';
+	}>;
+	overrides: Array<{
+		files: string[];
+		rules: Record<string, boolean | 'error' | 'warning' | 'info' | 'hint'>;
+		thresholds: { max_console_statements?: number | null };
+	}>;
+}
+
+/**
+ * A project configuration parsed once by the WASM validator (custom rule
+ * patterns compiled) and reused for every file under it
+ */
+interface WasmWorkflowValidator {
+	/** `path` is relative to the config file; `packageJson` is the workflow's package.json text */
+	validate: (content: string, path?: string, packageJson?: string) => ValidationResult;
+}
+
+interface WasmValidateOptions {
+	config?: ValidatorConfig;
+	/** Path relative to the config file, used to match `[[overrides]]` */
+	path?: string;
+	/** Text of the workflow's package.json, for the SDK version deprecations are checked against */
+	packageJson?: string;
+}
+
+export const VALIDATOR_CONFIG_FILE = '.workway-validator.toml';
+
+/**
+ * Long-form documentation for a diagnostic code
+ */
+export interface DiagnosticCodeDoc {
+	code: string;
+	title: string;
+	category: string;
+	defaultSeverity: 'error' | 'warning' | 'info' | 'hint';
+	explanation: string;
+	badExample: string;
+	goodExample: string;
+	docsUrl: string;
+}
+
+/**
+ * Marketplace readiness score, as computed by the WASM validator
+ */
+export interface ReadinessReport {
+	score: number;
+	listable: boolean;
+	areas: Array<{
+		area: 'completeness' | 'robustness' | 'security';
+		points: number;
+		maxPoints: number;
+	}>;
+	/** Failed checks first, in the order they should be fixed */
+	checklist: Array<{
+		id: string;
+		area: 'completeness' | 'robustness' | 'security';
+		priority: 'blocker' | 'high' | 'medium' | 'low';
+		title: string;
+		passed: boolean;
+		points: number;
+		maxPoints: number;
+		fix?: string;
+		codes?: string[];
+		line?: number;
+	}>;
+}
+
+/**
+ * Changes between two versions of a workflow, as computed by the WASM validator
+ */
+export interface WorkflowDiff {
+	hasBreakingChanges: boolean;
+	hasWarnings: boolean;
+	/** Breaking changes first, then warnings, then informational changes */
+	changes: Array<{
+		type:
+			| 'config_field_removed'
+			| 'config_field_added'
+			| 'config_field_type_changed'
+			| 'config_field_required_added'
+			| 'oauth_provider_added'
+			| 'oauth_provider_removed'
+			| 'oauth_scope_added'
+			| 'oauth_scope_removed'
+			| 'trigger_type_changed'
+			| 'trigger_config_changed'
+			| 'pricing_changed';
+		severity: 'breaking' | 'warning' | 'info';
+		field?: string;
+		message: string;
+		oldValue?: unknown;
+		newValue?: unknown;
+		migrationHint?: string;
+	}>;
+	version: {
+		oldVersion: string | null;
+		newVersion: string | null;
+		required: 'major' | 'minor' | 'patch';
+		actual: 'major' | 'minor' | 'patch' | null;
+		ok: boolean;
+		message: string;
+	};
+	summary: string;
+}
+
 /**
  * Try to load the WASM validator module
  */
@@ -97,6 +231,110 @@ export async function getWasmVersion(): Promise<string | null> {
 	return null;
 }
 
+/**
+ * Explain a diagnostic code (WASM only - the catalog lives in the Rust validator)
+ *
+ * @returns The code's documentation, or null if unknown or WASM is unavailable
+ */
+export async function explainCode(code: string): Promise<DiagnosticCodeDoc | null> {
+	const module = await loadWasmModule();
+	if (!module) {
+		return null;
+	}
+	return module.explain(code) ?? null;
+}
+
+/**
+ * Score a workflow's marketplace readiness (WASM only)
+ *
+ * @returns The score and checklist, or null if WASM is unavailable
+ */
+export async function scoreReadiness(content: string): Promise<ReadinessReport | null> {
+	const module = await loadWasmModule();
+	if (!module) {
+		return null;
+	}
+	return module.readiness(content);
+}
+
+/**
+ * Compare two versions of a workflow for breaking changes (WASM only)
+ *
+ * @returns The classified changes and version check, or null if WASM is unavailable
+ */
+export async function diffWorkflows(oldContent: string, newContent: string): Promise<WorkflowDiff | null> {
+	const module = await loadWasmModule();
+	if (!module) {
+		return null;
+	}
+	return module.diff_workflows(oldContent, newContent);
+}
+
+/** Parsed configs by path, reparsed when the file's modification time changes */
+const configValidators = new Map<string, { mtimeMs: number; validator: WasmWorkflowValidator }>();
+
+/**
+ * The WASM validator for a `.workway-validator.toml`, parsing it only when it
+ * is new or has changed since it was last parsed
+ */
+async function configValidator(module: WasmValidatorModule, configPath: string): Promise<WasmWorkflowValidator> {
+	const fs = await import('fs-extra');
+	const { mtimeMs } = await fs.default.stat(configPath);
+	const cached = configValidators.get(configPath);
+	if (cached && cached.mtimeMs === mtimeMs) {
+		return cached.validator;
+	}
+	const validator = module.WorkflowValidator.fromToml(await fs.default.readFile(configPath, 'utf-8'));
+	configValidators.set(configPath, { mtimeMs, validator });
+	return validator;
+}
+
+/**
+ * Find the nearest `.workway-validator.toml`, walking up from the workflow file
+ *
+ * @returns The config file path, or null if none exists
+ */
+export async function findValidatorConfig(workflowPath: string): Promise<string | null> {
+	const fs = await import('fs-extra');
+	let dir = path.dirname(path.resolve(workflowPath));
+
+	while (true) {
+		const candidate = path.join(dir, VALIDATOR_CONFIG_FILE);
+		if (await fs.default.pathExists(candidate)) {
+			return candidate;
+		}
+		const parent = path.dirname(dir);
+		if (parent === dir) {
+			return null;
+		}
+		dir = parent;
+	}
+}
+
+/**
+ * Find the nearest package.json that depends on the SDK, walking up from the
+ * workflow file, and return its contents
+ */
+export async function findSdkPackageJson(workflowPath: string): Promise<string | null> {
+	const fs = await import('fs-extra');
+	let dir = path.dirname(path.resolve(workflowPath));
+
+	while (true) {
+		const candidate = path.join(dir, 'package.json');
+		if (await fs.default.pathExists(candidate)) {
+			const source = await fs.default.readFile(candidate, 'utf-8');
+			if (source.includes('"@workway/sdk"') || source.includes('"@workwayco/sdk"')) {
+				return source;
+			}
+		}
+		const parent = path.dirname(dir);
+		if (parent === dir) {
+			return null;
+		}
+		dir = parent;
+	}
+}
+
 /**
  * Validate workflow content using WASM (with TS fallback)
  *
@@ -148,9 +386,16 @@ export async function validateWorkflowFileHybrid(workflowPath: string): Promise<
 			const fs = await import('fs-extra');
 			const content = await fs.default.readFile(workflowPath, 'utf-8');
 
-			// Validate with WASM
-			const result = module.validate_workflow_wasm(content);
-			return result;
+			// Validate with WASM, applying the project config if there is one
+			// and the SDK version from the nearest package.json
+			const packageJson = (await findSdkPackageJson(workflowPath)) ?? undefined;
+			const configPath = await findValidatorConfig(workflowPath);
+			if (configPath) {
+				const validator = await configValidator(module, configPath);
+				const relativePath = path.relative(path.dirname(configPath), path.resolve(workflowPath));
+				return validator.validate(content, relativePath, packageJson);
+			}
+			return module.validate_workflow_with_options(content, { packageJson });
 		} catch (error) {
 			// WASM validation failed, fall back to TypeScript
 			console.warn('WASM validation failed, falling back to TypeScript:', error);
@@ -0,0 +1,93 @@
+/**
+ * Parity tests for workflow-validator.ts against the Rust validator
+ *
+ * Both validators check the same golden fixtures in
+ * packages/rust-validator/fixtures/parity (the Rust side runs them from
+ * tests/parity.rs), so a rule that changes in only one implementation fails
+ * one of the two suites.
+ */
+
+import { describe, it, expect } from 'vitest';
+import fs from 'fs';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { validateWorkflowFile, type WorkflowMetadata } from './workflow-validator.js';
+
+const FIXTURES_DIR = path.resolve(
+	path.dirname(fileURLToPath(import.meta.url)),
+	'../../../rust-validator/fixtures/parity'
+);
+
+/** Metadata keys shared by both validators */
+const METADATA_KEYS = ['name', 'type', 'integrations', 'trigger', 'hasAI', 'pricing'] as const;
+
+interface ParitySnapshot {
+	valid: boolean;
+	errors: string[];
+	warnings: string[];
+	metadata: WorkflowMetadata;
+}
+
+/** Whether a metadata value carries nothing: `[]` or `{}` */
+function isEmpty(value: unknown): boolean {
+	if (Array.isArray(value)) return value.length === 0;
+	return typeof value === 'object' && value !== null && Object.keys(value).length === 0;
+}
+
+/**
+ * Snapshot form of the metadata: empty integrations lists and pricing blocks
+ * without a model or price are left out, as tests/parity.rs does
+ */
+function pickMetadata(metadata: WorkflowMetadata = {}): WorkflowMetadata {
+	const picked: Record<string, unknown> = {};
+	for (const key of METADATA_KEYS) {
+		if (metadata[key] !== undefined && !isEmpty(metadata[key])) {
+			picked[key] = metadata[key];
+		}
+	}
+	return picked as WorkflowMetadata;
+}
+
+const sharedCodes: string[] = JSON.parse(
+	fs.readFileSync(path.join(FIXTURES_DIR, 'shared-codes.json'), 'utf-8')
+);
+
+const fixtures = fs
+	.readdirSync(FIXTURES_DIR)
+	.filter((file) => file.endsWith('.ts'))
+	.sort();
+
+describe('workflow-validator.ts - Rust parity', () => {
+	it('should find the shared fixtures', () => {
+		expect(fixtures.length).toBeGreaterThan(0);
+	});
+
+	it.each(fixtures)('%s matches its snapshot', async (fixture) => {
+		const expected: ParitySnapshot = JSON.parse(
+			fs.readFileSync(path.join(FIXTURES_DIR, fixture.replace(/\.ts$/, '.expected.json')), 'utf-8')
+		);
+
+		const result = await validateWorkflowFile(path.join(FIXTURES_DIR, fixture));
+
+		expect({
+			valid: result.valid,
+			errors: result.errors.map((e) => e.code),
+			warnings: result.warnings.map((w) => w.code),
+			metadata: pickMetadata(result.metadata),
+		}).toEqual({
+			valid: expected.valid,
+			errors: expected.errors,
+			warnings: expected.warnings,
+			metadata: pickMetadata(expected.metadata),
+		});
+	});
+
+	it('should only emit codes the Rust validator shares', async () => {
+		for (const fixture of fixtures) {
+			const result = await validateWorkflowFile(path.join(FIXTURES_DIR, fixture));
+			for (const diagnostic of [...result.errors, ...result.warnings]) {
+				expect(sharedCodes).toContain(diagnostic.code);
+			}
+		}
+	});
+});
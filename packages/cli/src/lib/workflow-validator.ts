@@ -93,6 +93,12 @@ const CRON_PATTERNS = [
 	/^(\*|[0-6])(\/(0|[1-9][0-9]?))?$|^\*\/[0-9]+$/, // weekday
 ] as const;
 
+/**
+ * Largest value allowed in each cron field's ranges and lists
+ * (minute, hour, day, month, weekday)
+ */
+const CRON_FIELD_MAX = [59, 23, 31, 12, 6] as const;
+
 /**
  * Pre-compiled import patterns for blocked modules and packages.
  * Map from module/package name to compiled regex.
@@ -777,7 +783,14 @@ function isValidCron(expr: string): boolean {
 		const part = parts[i];
 		if (part === '*') continue;
 		if (PATTERNS.cronStepWildcard.test(part)) continue;
-		if (PATTERNS.cronRangeList.test(part)) continue;
+		// Ranges and lists must stay within the field's bounds
+		if (PATTERNS.cronRangeList.test(part)) {
+			const inRange = part
+				.split(/[,-]/)
+				.every((num) => Number(num) <= CRON_FIELD_MAX[i]);
+			if (!inRange) return false;
+			continue;
+		}
 		if (!CRON_PATTERNS[i].test(part)) return false;
 	}
 
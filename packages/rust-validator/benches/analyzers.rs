@@ -0,0 +1,58 @@
+//! Per-analyzer timing over the workflow corpus and a 10,000-line workflow,
+//! so a rule that slows down shows up on its own rather than only in the
+//! total.
+//!
+//! ```text
+//! cargo bench --bench analyzers
+//! cargo bench --bench analyzers -- taint
+//! ```
+//!
+//! Each run includes the probe scan; an analyzer that needs the blanked code
+//! or the parsed definition pays for computing it, as the first one to ask
+//! does in a full validation.
+
+mod common;
+
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use workway_validator::{analyzer_names, run_analyzer};
+
+fn bench_corpus(c: &mut Criterion) {
+    let files = common::corpus();
+    let bytes: usize = files.iter().map(|(_, content)| content.len()).sum();
+    let mut group = c.benchmark_group("analyzer/corpus");
+    group.throughput(Throughput::Bytes(bytes as u64));
+    for name in analyzer_names() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &files, |b, files| {
+            b.iter(|| {
+                for (_, content) in files {
+                    black_box(run_analyzer(name, black_box(content)));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_synthetic(c: &mut Criterion) {
+    let content = common::synthetic_workflow(10_000);
+    let mut group = c.benchmark_group("analyzer/synthetic");
+    group.throughput(Throughput::Bytes(content.len() as u64));
+    for name in analyzer_names() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &content, |b, content| {
+            b.iter(|| run_analyzer(name, black_box(content)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = bench_corpus, bench_synthetic
+}
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+//! Inputs shared by the benchmarks.
+
+// Each benchmark uses a different subset
+#![allow(dead_code)]
+
+use std::path::Path;
+
+/// Every `packages/workflows/src/*/index.ts`, sorted by name
+pub fn corpus() -> Vec<(String, String)> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../workflows/src");
+    let mut files: Vec<(String, String)> = std::fs::read_dir(&root)
+        .expect("packages/workflows/src")
+        .flatten()
+        .filter_map(|entry| {
+            let content = std::fs::read_to_string(entry.path().join("index.ts")).ok()?;
+            Some((entry.file_name().to_string_lossy().into_owned(), content))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+const HEADER: &str = r#"import { defineWorkflow, webhook } from '@workway/sdk';
+import { createAIClient, AIModels } from '@workway/sdk/workers-ai';
+
+export default defineWorkflow({
+  name: 'Synthetic Workflow',
+  type: 'ai-enhanced',
+  integrations: [
+    { service: 'notion', scopes: ['read_pages'] },
+    { service: 'slack', scopes: ['chat:write'] },
+  ],
+  trigger: webhook({ service: 'stripe', event: 'payment.succeeded' }),
+  pricing: { model: 'subscription', price: 10, executions: 100 },
+
+  async execute({ trigger, inputs, integrations, env }) {
+    const ai = createAIClient(env);
+    const results = [];
+"#;
+
+const FOOTER: &str = r#"    return { success: true, data: results };
+  },
+});
+"#;
+
+/// A well-formed workflow with at least `lines` lines: the usual header and
+/// an `execute` body made of repeated steps that exercise most rules
+pub fn synthetic_workflow(lines: usize) -> String {
+    let mut content = String::from(HEADER);
+    let mut step = 0;
+    while content.lines().count() < lines {
+        content.push_str(&format!(
+            r#"    const page{step} = await integrations.notion.pages.retrieve({{ page_id: inputs.page{step} }});
+    if (!page{step}.success) {{
+      console.log('missing page', {step});
+    }}
+    for (const item of page{step}.data.items.slice(0, 10)) {{
+      results.push(JSON.stringify(item));
+    }}
+    const summary{step} = await ai.generateText({{ model: AIModels.LLAMA_3_8B, prompt: page{step}.data.title, max_tokens: 256 }});
+    await integrations.slack.chat.postMessage({{ channel: inputs.channel, text: `Step {step}: ${{summary{step}.data}}` }});
+"#
+        ));
+        step += 1;
+    }
+    content.push_str(FOOTER);
+    content
+}
+
+/// Inputs that stress backtracking, bracket matching and repeated prefixes
+pub fn pathological() -> Vec<(&'static str, String)> {
+    let execute = |body: &str| format!("export default defineWorkflow({{\n  async execute() {{\n{}\n  }}\n}});\n", body);
+    vec![
+        ("unclosed_braces", execute(&"{ if (x) ".repeat(20_000))),
+        ("deep_nesting", execute(&format!("{}await step();{}", "for (const a of b) {\n".repeat(2_000), "}\n".repeat(2_000)))),
+        ("unterminated_string", format!("export default defineWorkflow({{\n  name: '{}", "x".repeat(200_000))),
+        ("long_line", execute(&"total = total + item.price * 2; ".repeat(30_000))),
+        ("many_imports", format!("{}{}", "import { x } from './module';\n".repeat(10_000), execute("return { success: true };"))),
+        ("repeated_prefixes", execute(&"console.integrations.fetch.catch (e) { for ( while ( schedule( ".repeat(10_000))),
+    ]
+}
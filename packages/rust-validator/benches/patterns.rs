@@ -0,0 +1,36 @@
+//! Per-pattern timing over a 10,000-line workflow, so a change to one
+//! pattern in `patterns.rs` that slows it down shows up on its own.
+//!
+//! ```text
+//! cargo bench --bench patterns
+//! cargo bench --bench patterns -- AWAIT_IN_FOR_LOOP
+//! ```
+
+mod common;
+
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use workway_validator::named_patterns;
+
+fn bench_patterns(c: &mut Criterion) {
+    let content = common::synthetic_workflow(10_000);
+    let mut group = c.benchmark_group("pattern");
+    group.throughput(Throughput::Bytes(content.len() as u64));
+    for (name, pattern) in named_patterns() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &content, |b, content| {
+            b.iter(|| pattern.find_iter(black_box(content)).count())
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    targets = bench_patterns
+}
+criterion_main!(benches);
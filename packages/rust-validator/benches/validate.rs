@@ -0,0 +1,61 @@
+//! Validation throughput: the workflows in `packages/workflows`, synthetic
+//! large files and pathological inputs.
+//!
+//! ```text
+//! cargo bench --bench validate
+//! ```
+
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use workway_validator::validate_workflow;
+
+fn bench_corpus(c: &mut Criterion) {
+    let files = common::corpus();
+    let bytes: usize = files.iter().map(|(_, content)| content.len()).sum();
+
+    let mut group = c.benchmark_group("validate");
+    group.throughput(Throughput::Bytes(bytes as u64));
+    group.bench_function(format!("corpus ({} files)", files.len()), |b| {
+        b.iter(|| {
+            for (_, content) in &files {
+                black_box(validate_workflow(black_box(content)));
+            }
+        })
+    });
+    group.finish();
+
+    let (name, largest) = files.iter().max_by_key(|(_, content)| content.len()).unwrap();
+    let mut group = c.benchmark_group("validate_file");
+    group.throughput(Throughput::Bytes(largest.len() as u64));
+    group.bench_function(name.as_str(), |b| b.iter(|| validate_workflow(black_box(largest))));
+    group.finish();
+}
+
+fn bench_synthetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("synthetic");
+    group.sample_size(20);
+    for lines in [1_000, 10_000, 50_000] {
+        let content = common::synthetic_workflow(lines);
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::new("lines", lines), &content, |b, content| {
+            b.iter(|| validate_workflow(black_box(content)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_pathological(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pathological");
+    group.sample_size(10);
+    for (name, content) in common::pathological() {
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &content, |b, content| {
+            b.iter(|| validate_workflow(black_box(content)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_corpus, bench_synthetic, bench_pathological);
+criterion_main!(benches);
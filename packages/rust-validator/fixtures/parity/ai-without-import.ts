@@ -0,0 +1,16 @@
+import { defineWorkflow, webhook } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'Ticket Triage',
+	type: 'ai-enhanced',
+	integrations: [{ service: 'linear', scopes: ['issues:write'] }],
+	trigger: webhook({ service: 'linear', event: 'issue.created' }),
+	pricing: { model: 'subscription', price: 12, executions: 500 },
+
+	async execute({ trigger, integrations }) {
+		const ai = createAIClient();
+		const label = await ai.generateText({ model: AIModels.LLAMA_3_8B, prompt: trigger.data.title });
+		await integrations.linear.issues.update({ id: trigger.data.id, label });
+		return { success: true };
+	},
+});
@@ -0,0 +1,17 @@
+import { defineWorkflow, manual } from '@workway/sdk';
+import fs from 'fs';
+import axios from 'axios';
+const { execSync } = require('child_process');
+const crypto = require( "crypto" );
+
+export default defineWorkflow({
+	name: 'Node Modules',
+	trigger: manual(),
+	pricing: { model: 'usage', price: 0.05 },
+
+	async execute() {
+		const data = fs.readFileSync('/tmp/data.json', 'utf-8');
+		await axios.post('https://example.com', data);
+		return { success: true, hash: crypto.createHash('sha256') };
+	},
+});
@@ -0,0 +1,16 @@
+import { defineWorkflow, manual } from '@workway/sdk';
+import { createAIClient, AIModels } from '@workway/sdk/workers-ai';
+
+export default defineWorkflow({
+	name: 'Summarizer',
+	type: 'ai-native',
+	trigger: manual(),
+	pricing: { model: 'usage', price: 0.02 },
+
+	async execute({ inputs, env }) {
+		// Previously used OpenAI; moved to Workers AI
+		const ai = createAIClient(env);
+		const summary = await ai.generateText({ model: AIModels.LLAMA_3_8B, prompt: inputs.text });
+		return { success: true, data: summary };
+	},
+});
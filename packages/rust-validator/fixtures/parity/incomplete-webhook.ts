@@ -0,0 +1,12 @@
+import { defineWorkflow, webhook } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'Generic Hook',
+	trigger: webhook({ path: '/incoming' }),
+	pricing: { model: 'one-time', price: 15 },
+
+	async execute({ trigger }) {
+		const body = trigger.data;
+		console.log('received', body);
+	},
+});
@@ -0,0 +1,15 @@
+import { defineWorkflow, schedule } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'Nightly Digest',
+	type: 'integration',
+	integrations: ['slack', 'notion'],
+	trigger: schedule('0 25 * * *'),
+	pricing: { model: 'subscription', price: 8, executions: 30 },
+
+	async execute({ integrations }) {
+		const pages = await integrations.notion.search({ query: 'digest' });
+		await integrations.slack.chat.postMessage({ channel: '#daily', text: pages.length });
+		return { success: true };
+	},
+});
@@ -0,0 +1,9 @@
+export default {
+	name: 'Plain Export',
+	trigger: manual(),
+	pricing: { model: 'one-time', price: 5 },
+
+	async execute() {
+		return { success: true };
+	},
+};
@@ -0,0 +1,7 @@
+import { defineWorkflow } from '@workway/sdk';
+
+export default defineWorkflow({
+	description: 'Work in progress',
+	pricing: { price: 3 },
+	steps: [],
+});
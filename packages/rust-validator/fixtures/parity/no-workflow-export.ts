@@ -0,0 +1,6 @@
+import { manual } from '@workway/sdk';
+
+const workflow = {
+	title: 'Not exported',
+	trigger: manual(),
+};
@@ -0,0 +1,11 @@
+import { defineWorkflow } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'Manual Export',
+	type: 'integration',
+	trigger: { type: 'manual' },
+
+	async run({ inputs }) {
+		return { success: true, data: inputs };
+	},
+});
@@ -0,0 +1,16 @@
+import { defineWorkflow, poll } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'Feed Watcher',
+	trigger: poll({ service: 'rss', interval: 3600 }),
+	pricing: { model: 'subscription', price: 4.5 },
+
+	async execute({ trigger }) {
+		let page = 0;
+		while (page < 3) {
+			await trigger.fetchPage(page);
+			page++;
+		}
+		return { success: true };
+	},
+});
@@ -0,0 +1,17 @@
+import { defineWorkflow, webhook } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'CRM Bridge',
+	integrations: [
+		{ service: 'Salesforce' },
+		{ service: 'acme-crm' },
+		'zendesk',
+	],
+	trigger: webhook({ service: 'zendesk', event: 'ticket.created' }),
+	pricing: { model: 'subscription', price: 20 },
+
+	async execute({ trigger, integrations }) {
+		await integrations.salesforce.cases.create({ subject: trigger.data.subject });
+		return { success: true };
+	},
+});
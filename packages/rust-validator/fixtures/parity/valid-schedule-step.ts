@@ -0,0 +1,13 @@
+import { defineWorkflow, schedule } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'Quarter Hourly Sync',
+	trigger: schedule('*/15 * * * 1-5'),
+	integrations: [{ service: 'airtable', scopes: ['data.records:read'] }],
+	pricing: { model: 'usage', price: 0.01 },
+
+	async execute({ integrations }) {
+		const records = await integrations.airtable.records.list({ table: 'Leads' });
+		return { success: true, data: records };
+	},
+});
@@ -0,0 +1,18 @@
+import { defineWorkflow, webhook } from '@workway/sdk';
+
+export default defineWorkflow({
+	name: 'Payment Receipt',
+	type: 'integration',
+	integrations: [
+		{ service: 'stripe', scopes: ['read_payments'] },
+		{ service: 'gmail', scopes: ['send_email'] },
+	],
+	trigger: webhook({ service: 'stripe', event: 'payment.succeeded' }),
+	pricing: { model: 'subscription', price: 10, executions: 100 },
+
+	async execute({ trigger, integrations }) {
+		const payment = trigger.data;
+		await integrations.gmail.send({ to: payment.email, subject: 'Receipt' });
+		return { success: true };
+	},
+});
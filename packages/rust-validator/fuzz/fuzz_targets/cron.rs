@@ -0,0 +1,10 @@
+#![no_main]
+
+// Cron expressions, checked alone and through `schedule(...)`.
+
+use libfuzzer_sys::fuzz_target;
+use workway_validator_fuzz::{check_cron, decode};
+
+fuzz_target!(|data: &[u8]| {
+    check_cron(&decode(data));
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+// The object literal reader and bracket matcher, started at every opener.
+
+use libfuzzer_sys::fuzz_target;
+use workway_validator_fuzz::{check_literal, decode};
+
+fuzz_target!(|data: &[u8]| {
+    check_literal(&decode(data));
+});
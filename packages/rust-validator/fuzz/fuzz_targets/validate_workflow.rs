@@ -0,0 +1,11 @@
+#![no_main]
+
+// Validates arbitrary source files: no panics, and every span, line and fix
+// lies inside the input on a character boundary.
+
+use libfuzzer_sys::fuzz_target;
+use workway_validator_fuzz::{check_workflow, decode};
+
+fuzz_target!(|data: &[u8]| {
+    check_workflow(&decode(data));
+});
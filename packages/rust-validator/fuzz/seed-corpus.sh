@@ -0,0 +1,35 @@
+#!/bin/bash
+# Builds the seed corpus for the fuzz targets from real workflows.
+#
+#   validate_workflow  every workflow in packages/workflows and the parity fixtures
+#   literal            the same files (each is full of object literals)
+#   cron               every cron expression those files schedule
+#
+# The corpus directory is not checked in; run this once before fuzzing.
+# Cases worth keeping go in regressions/<target>/ instead.
+
+set -e
+
+cd "$(dirname "$0")"
+WORKFLOWS=../../workflows/src
+FIXTURES=../fixtures/parity
+
+mkdir -p corpus/validate_workflow corpus/literal corpus/cron
+
+for file in "$WORKFLOWS"/*/index.ts "$FIXTURES"/*.ts; do
+    [ -f "$file" ] || continue
+    name="$(basename "$(dirname "$file")")-$(basename "$file")"
+    cp "$file" "corpus/validate_workflow/$name"
+    cp "$file" "corpus/literal/$name"
+done
+
+grep -rhoE "(schedule\(\s*|cron:\s*)['\"][^'\"]+['\"]" "$WORKFLOWS" "$FIXTURES" \
+    | sed -E "s/.*['\"]([^'\"]+)['\"]/\1/" \
+    | sort -u \
+    | while IFS= read -r expr; do
+        printf '%s' "$expr" > "corpus/cron/$(printf '%s' "$expr" | sha1sum | cut -c1-16)"
+    done
+
+echo "validate_workflow: $(ls corpus/validate_workflow | wc -l) files"
+echo "literal:           $(ls corpus/literal | wc -l) files"
+echo "cron:              $(ls corpus/cron | wc -l) expressions"
@@ -0,0 +1,141 @@
+//! Properties checked by the fuzz targets.
+//!
+//! The targets in `fuzz_targets/` only decode their input and call these
+//! functions. `tests/fuzz_regressions.rs` includes this file too, so every
+//! checked-in case under `regressions/` is replayed by a plain `cargo test`
+//! without a nightly toolchain.
+
+use workway_validator::{
+    extract_manifest, is_valid_cron, matching_close, parse_literal_at, validate_workflow, Span,
+};
+
+/// Source files are read lossily: invalid bytes become U+FFFD, so every input
+/// is exercised and multi-byte characters end up next to the patterns.
+pub fn decode(data: &[u8]) -> String {
+    String::from_utf8_lossy(data).into_owned()
+}
+
+fn check_span(content: &str, span: &Span, what: &str) {
+    assert!(
+        span.start <= span.end && span.end <= content.len(),
+        "{} span {}..{} outside 0..{}",
+        what,
+        span.start,
+        span.end,
+        content.len()
+    );
+    assert!(
+        content.is_char_boundary(span.start) && content.is_char_boundary(span.end),
+        "{} span {}..{} splits a character",
+        what,
+        span.start,
+        span.end
+    );
+}
+
+fn check_line(content: &str, line: u32, what: &str) {
+    let lines = content.matches('\n').count() as u32 + 1;
+    assert!((1..=lines).contains(&line), "{} line {} outside 1..={}", what, line, lines);
+}
+
+/// Validates and extracts the manifest of `content`, checking that every
+/// reported location lies inside it and that every fix can be applied.
+pub fn check_workflow(content: &str) {
+    let result = validate_workflow(content);
+    assert_eq!(result.valid, result.errors().next().is_none(), "valid disagrees with errors");
+
+    for diagnostic in &result.diagnostics {
+        let what = diagnostic.code.as_str();
+        if let Some(span) = &diagnostic.span {
+            check_span(content, span, what);
+        }
+        if let Some(line) = diagnostic.line {
+            check_line(content, line, what);
+        }
+        for related in &diagnostic.related {
+            if let Some(span) = &related.span {
+                check_span(content, span, what);
+            }
+            if let Some(line) = related.line {
+                check_line(content, line, what);
+            }
+        }
+        for fix in &diagnostic.fixes {
+            let mut edits: Vec<_> = fix.edits.iter().collect();
+            edits.sort_by_key(|edit| std::cmp::Reverse(edit.span.start));
+            let mut fixed = content.to_string();
+            let mut limit = content.len();
+            for edit in edits {
+                check_span(content, &edit.span, what);
+                assert!(edit.span.end <= limit, "{} fix has overlapping edits", what);
+                fixed.replace_range(edit.span.start..edit.span.end, &edit.replacement);
+                limit = edit.span.start;
+            }
+        }
+    }
+
+    serde_json::to_string(&result).unwrap();
+    extract_manifest(content).to_json();
+}
+
+/// Checks `expr` on its own and as it reaches the validator through
+/// `schedule(...)`, where the two must agree.
+pub fn check_cron(expr: &str) {
+    let valid = is_valid_cron(expr);
+    if valid {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        assert_eq!(fields.len(), 5, "accepted {:?} without five fields", expr);
+        assert!(
+            expr.chars().all(|c| c.is_ascii_digit() || c.is_whitespace() || "*/,-".contains(c)),
+            "accepted {:?} with characters outside the cron syntax",
+            expr
+        );
+    }
+
+    // The schedule pattern stops at the first quote, and an empty or
+    // whitespace-only expression is never captured
+    if expr.contains(['\'', '"', '`']) || expr.trim().is_empty() {
+        return;
+    }
+    let content = format!(
+        "import {{ defineWorkflow, schedule }} from '@workway/sdk';\n\
+         export default defineWorkflow({{\n\
+         \tname: 'Fuzz',\n\
+         \ttrigger: schedule('{}'),\n\
+         \tasync execute() {{ return {{ success: true }}; }},\n\
+         }});\n",
+        expr
+    );
+    let result = validate_workflow(&content);
+    let reported = result.diagnostics.iter().any(|d| d.code == "INVALID_CRON");
+    assert_eq!(reported, !valid, "validator and is_valid_cron disagree on {:?}", expr);
+    check_workflow(&content);
+}
+
+/// Most openers a single input is read from, so one input stays linear
+const MAX_OPENERS: usize = 256;
+
+/// Reads a literal and matches brackets from every opening bracket and quote,
+/// checking that each returned offset is inside `content` and on a character
+/// boundary.
+pub fn check_literal(content: &str) {
+    let openers = content
+        .char_indices()
+        .filter(|(_, c)| matches!(c, '{' | '[' | '(' | '\'' | '"' | '`'))
+        .take(MAX_OPENERS);
+
+    for (pos, c) in openers {
+        let (literal, end) = parse_literal_at(content, pos);
+        assert!(pos <= end && end <= content.len(), "literal at {} ends at {}", pos, end);
+        assert!(content.is_char_boundary(end), "literal at {} ends inside a character", pos);
+        literal.to_json();
+
+        if matches!(c, '{' | '[' | '(') {
+            if let Some(close) = matching_close(content, pos) {
+                assert!(close > pos && close < content.len(), "bracket at {} closed at {}", pos, close);
+                let closer = content.as_bytes()[close];
+                assert!(matches!(closer, b'}' | b']' | b')'), "bracket at {} closed by {:?}", pos, closer as char);
+            }
+        }
+    }
+}
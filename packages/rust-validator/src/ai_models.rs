@@ -0,0 +1,274 @@
+//! Workers AI model catalog and per-run cost estimation.
+//!
+//! Mirrors `AIModels` in `packages/sdk/src/workers-ai.ts` and `AI_MODEL_COSTS`
+//! in `packages/sdk/src/pricing-constants.ts`. Keep the three in sync when
+//! Cloudflare adds or reprices a model. The SDK records no lifecycle status,
+//! so neither does the catalog.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::patterns::*;
+use crate::diagnostic::Diagnostic;
+use crate::scan::{Probe, Scan};
+use crate::validator::WorkflowMetadata;
+
+/// Flat per-run price charged to users (`EXECUTION_PRICING.PER_RUN`)
+pub const PRICE_PER_RUN: f64 = 0.01;
+
+/// Tokens assumed for a text generation call without `max_tokens`
+const DEFAULT_TEXT_TOKENS: u32 = 1000;
+
+/// Tokens assumed for an embedding or translation call
+const DEFAULT_INPUT_TOKENS: u32 = 500;
+
+/// What a model does, which also decides how its cost is metered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    /// Per 1M tokens
+    Text,
+    /// Per 1M tokens
+    Embeddings,
+    /// Per image
+    Image,
+    /// Per minute of audio
+    Audio,
+    /// Per 1M tokens
+    Translation,
+    /// Per 1M requests
+    Classification,
+}
+
+/// A Workers AI model known to the validator
+#[derive(Debug, Clone)]
+pub struct AiModel {
+    /// `AIModels` constant name, if the SDK exposes one
+    pub constant: Option<&'static str>,
+    /// Model id passed to `env.AI.run()`
+    pub id: &'static str,
+    pub kind: ModelKind,
+    /// Cost per metering unit (see [`ModelKind`]), if published
+    pub cost: Option<f64>,
+}
+
+const fn model(constant: Option<&'static str>, id: &'static str, kind: ModelKind, cost: Option<f64>) -> AiModel {
+    AiModel { constant, id, kind, cost }
+}
+
+use ModelKind::*;
+
+/// Known Workers AI models
+pub static AI_MODEL_CATALOG: &[AiModel] = &[
+    // Text generation
+    model(Some("LLAMA_2_7B"), "@cf/meta/llama-2-7b-chat-int8", Text, Some(0.005)),
+    model(Some("LLAMA_2_7B_FP16"), "@cf/meta/llama-2-7b-chat-fp16", Text, None),
+    model(Some("LLAMA_3_8B"), "@cf/meta/llama-3-8b-instruct", Text, Some(0.01)),
+    // Not in AIModels, but called by id from the shipped workflows
+    model(None, "@cf/meta/llama-3.1-8b-instruct", Text, None),
+    model(Some("MISTRAL_7B"), "@cf/mistral/mistral-7b-instruct-v0.1", Text, Some(0.02)),
+    model(Some("PHI_2"), "@cf/microsoft/phi-2", Text, Some(0.005)),
+    model(Some("QWEN_15_7B"), "@cf/qwen/qwen1.5-7b-chat-awq", Text, None),
+    model(Some("DEEPSEEK_CODER"), "@cf/deepseek-ai/deepseek-coder-6.7b-instruct-awq", Text, Some(0.01)),
+    // Code generation
+    model(Some("CODE_LLAMA"), "@cf/meta/codellama-7b-instruct-awq", Text, None),
+    model(Some("DEEPSEEK_MATH"), "@cf/deepseek-ai/deepseek-math-7b-instruct", Text, None),
+    // Embeddings
+    model(Some("BGE_SMALL"), "@cf/baai/bge-small-en-v1.5", Embeddings, Some(0.001)),
+    model(Some("BGE_BASE"), "@cf/baai/bge-base-en-v1.5", Embeddings, Some(0.002)),
+    model(Some("BGE_LARGE"), "@cf/baai/bge-large-en-v1.5", Embeddings, Some(0.004)),
+    // Image generation
+    model(Some("STABLE_DIFFUSION_XL"), "@cf/stabilityai/stable-diffusion-xl-base-1.0", Image, Some(0.02)),
+    model(Some("DREAMSHAPER"), "@cf/lykon/dreamshaper-8-lcm", Image, Some(0.01)),
+    // Image classification
+    model(Some("RESNET_50"), "@cf/microsoft/resnet-50", Classification, Some(0.002)),
+    // Speech recognition
+    model(Some("WHISPER"), "@cf/openai/whisper", Audio, Some(0.006)),
+    model(Some("WHISPER_TINY"), "@cf/openai/whisper-tiny-en-v1", Audio, Some(0.002)),
+    // Translation
+    model(Some("M2M100"), "@cf/meta/m2m100-1.2b", Translation, Some(0.005)),
+    // Sentiment analysis
+    model(Some("DISTILBERT_SST2"), "@cf/huggingface/distilbert-sst-2-int8", Classification, Some(0.001)),
+];
+
+/// Looks up a model by `AIModels` constant name
+pub fn find_by_constant(constant: &str) -> Option<&'static AiModel> {
+    AI_MODEL_CATALOG.iter().find(|m| m.constant == Some(constant))
+}
+
+/// Looks up a model by its `@cf/...` id
+pub fn find_by_id(id: &str) -> Option<&'static AiModel> {
+    AI_MODEL_CATALOG.iter().find(|m| m.id == id)
+}
+
+/// AI usage summary attached to workflow metadata
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiCostEstimate {
+    /// Model ids referenced by the workflow
+    pub models: Vec<String>,
+    /// Estimated Workers AI cost of one run (USD)
+    pub estimated_cost_per_run: f64,
+    /// Flat price charged per run (USD)
+    pub price_per_run: f64,
+    /// `price_per_run - estimated_cost_per_run`
+    pub estimated_margin: f64,
+    /// Referenced models without a published cost (excluded from the estimate)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unpriced_models: Vec<String>,
+}
+
+/// Cost of a single call to `model`, given the text that follows the model
+/// reference (used to pick up `max_tokens`).
+fn call_cost(model: &AiModel, call_args: &str) -> Option<f64> {
+    let rate = model.cost?;
+    let units = match model.kind {
+        Text => {
+            let tokens = MAX_TOKENS
+                .captures(call_args)
+                .and_then(|caps| caps[1].parse().ok())
+                .unwrap_or(DEFAULT_TEXT_TOKENS);
+            tokens as f64 / 1_000_000.0
+        }
+        Embeddings | Translation => DEFAULT_INPUT_TOKENS as f64 / 1_000_000.0,
+        Classification => 1.0 / 1_000_000.0,
+        Image | Audio => 1.0,
+    };
+    Some(rate * units)
+}
+
+/// Text following a model reference that belongs to the same call, bounded by
+/// the end of the enclosing object literal (or 400 bytes).
+fn call_args_after(content: &str, offset: usize) -> &str {
+    let rest = &content[offset..];
+    let mut end = rest.len().min(400);
+    while !rest.is_char_boundary(end) {
+        end -= 1;
+    }
+    let window = &rest[..end];
+    match window.find('}') {
+        Some(close) => &window[..close],
+        None => window,
+    }
+}
+
+pub(crate) fn validate_ai_models(
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
+    metadata: &mut WorkflowMetadata,
+) {
+    if !scan.has(Probe::AiModelConstant) && !scan.has(Probe::AiModelId) {
+        return;
+    }
+    let content = scan.content();
+    let mut estimate = AiCostEstimate {
+        price_per_run: PRICE_PER_RUN,
+        ..Default::default()
+    };
+
+    // (model, span of the reference)
+    let mut references: Vec<(&'static AiModel, Range<usize>)> = Vec::new();
+
+    let constants = scan.has(Probe::AiModelConstant).then(|| AI_MODEL_CONSTANT.captures_iter(content));
+    for caps in constants.into_iter().flatten() {
+        let name = caps.get(1).unwrap();
+        match find_by_constant(name.as_str()) {
+            Some(model) => references.push((model, name.range())),
+            None => diagnostics.push(
+                Diagnostic::warning("UNKNOWN_AI_MODEL", &format!("AIModels.{} does not exist", name.as_str()))
+                    .with_span(content, name.range())
+                    .with_suggestion("Use a model from AIModels, e.g. AIModels.LLAMA_3_8B"),
+            ),
+        }
+    }
+
+    let ids = scan.has(Probe::AiModelId).then(|| AI_MODEL_ID.captures_iter(content));
+    for caps in ids.into_iter().flatten() {
+        let id = caps.get(1).unwrap();
+        match find_by_id(id.as_str()) {
+            Some(model) => references.push((model, id.range())),
+            None => diagnostics.push(
+                Diagnostic::warning("UNKNOWN_AI_MODEL", &format!("Unknown Workers AI model: {}", id.as_str()))
+                    .with_span(content, id.range())
+                    .with_suggestion("Check the model id against https://developers.cloudflare.com/workers-ai/models/"),
+            ),
+        }
+    }
+
+    if references.is_empty() {
+        return;
+    }
+
+    references.sort_by_key(|(_, span)| span.start);
+    for (model, span) in &references {
+        match call_cost(model, call_args_after(content, span.start)) {
+            Some(cost) => estimate.estimated_cost_per_run += cost,
+            None if !estimate.unpriced_models.iter().any(|m| m == model.id) => {
+                estimate.unpriced_models.push(model.id.to_string())
+            }
+            None => {}
+        }
+        if !estimate.models.iter().any(|m| m == model.id) {
+            estimate.models.push(model.id.to_string());
+        }
+    }
+
+    estimate.estimated_margin = estimate.price_per_run - estimate.estimated_cost_per_run;
+    if estimate.estimated_margin < 0.0 {
+        diagnostics.push(
+            Diagnostic::warning(
+                "AI_COST_EXCEEDS_PRICE",
+                &format!(
+                    "Estimated AI cost per run (${:.4}) exceeds the ${:.2} per-run price",
+                    estimate.estimated_cost_per_run, estimate.price_per_run
+                ),
+            )
+            .with_suggestion("Use a smaller model or lower max_tokens"),
+        );
+    }
+
+    metadata.ai = Some(estimate);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Severity;
+
+    #[test]
+    fn test_catalog_mirrors_sdk_constants() {
+        assert_eq!(find_by_constant("LLAMA_3_8B").unwrap().id, "@cf/meta/llama-3-8b-instruct");
+        assert_eq!(find_by_id("@cf/microsoft/phi-2").unwrap().constant, Some("PHI_2"));
+        assert!(find_by_constant("GPT_4").is_none());
+    }
+
+    #[test]
+    fn test_cost_estimate_uses_max_tokens() {
+        let content = r#"
+const result = await ai.generateText({ model: AIModels.LLAMA_3_8B, prompt, max_tokens: 500 });
+const vec = await env.AI.run('@cf/baai/bge-small-en-v1.5', { text });
+"#;
+        let mut diagnostics = Vec::new();
+        let mut metadata = WorkflowMetadata::default();
+        validate_ai_models(&Scan::new(content), &mut diagnostics, &mut metadata);
+
+        let ai = metadata.ai.unwrap();
+        assert_eq!(ai.models.len(), 2);
+        let expected = 0.01 * 500.0 / 1e6 + 0.001 * 500.0 / 1e6;
+        assert!((ai.estimated_cost_per_run - expected).abs() < 1e-12);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_models_are_warnings() {
+        let content = "const a = AIModels.LLAMA_3_70B;\nconst b = await env.AI.run('@cf/meta/llama-9-instruct', {});\n";
+        let mut diagnostics = Vec::new();
+        validate_ai_models(&Scan::new(content), &mut diagnostics, &mut WorkflowMetadata::default());
+
+        let found: Vec<_> = diagnostics.iter().map(|d| (d.code.as_str(), d.severity)).collect();
+        assert_eq!(
+            found,
+            vec![("UNKNOWN_AI_MODEL", Severity::Warning), ("UNKNOWN_AI_MODEL", Severity::Warning)]
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! Declared workflow type versus actual AI usage.
+//!
+//! `type: 'integration' | 'ai-enhanced' | 'ai-native'` decides the runtime a
+//! workflow is deployed to and how it is presented in the marketplace, but
+//! nothing ties it to the code. This module counts Workers AI call sites and
+//! estimates how central their output is: whether it only enriches data that
+//! is passed on, decides which branch runs, or feeds further AI calls in a
+//! multi-step pipeline. The estimate is then checked against the declared type.
+//!
+//! Results are followed through plain assignments by name, without scoping,
+//! so the role is an estimate rather than a data-flow proof.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::{Diagnostic, TextEdit};
+use crate::patterns::*;
+use crate::scan::Scan;
+use crate::source::{binding_names, code_only, identifiers, matching_close, result_bindings};
+use crate::validator::WorkflowMetadata;
+
+/// Call sites from which a workflow counts as a multi-step AI pipeline even
+/// when no call consumes another's output
+const PIPELINE_CALL_SITES: u32 = 3;
+
+/// How central AI is to a workflow, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AiRole {
+    /// No Workers AI calls
+    None,
+    /// AI output is passed on (stored, posted, returned) but never branched on
+    Enrichment,
+    /// AI output decides which branch runs: classification, routing, filtering
+    ControlFlow,
+    /// AI output feeds further AI calls, or the workflow makes several calls
+    Pipeline,
+}
+
+impl AiRole {
+    /// Workflow type that fits this role
+    pub fn expected_type(self) -> &'static str {
+        match self {
+            AiRole::None => "integration",
+            AiRole::Enrichment | AiRole::ControlFlow => "ai-enhanced",
+            AiRole::Pipeline => "ai-native",
+        }
+    }
+}
+
+impl fmt::Display for AiRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AiRole::None => "not used",
+            AiRole::Enrichment => "only used to enrich data",
+            AiRole::ControlFlow => "used to decide control flow",
+            AiRole::Pipeline => "chained across several calls",
+        })
+    }
+}
+
+/// AI usage summary attached to workflow metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiUsage {
+    /// Workers AI call sites, including those in helper functions
+    pub call_sites: u32,
+    pub role: AiRole,
+    /// Workflow type that fits `role`
+    pub suggested_type: String,
+}
+
+fn mentions(text: &str, names: &HashSet<String>) -> bool {
+    identifiers(text).any(|(_, id)| names.contains(id))
+}
+
+/// Byte range of the argument list of the call whose `(` ends `call`.
+fn call_args(content: &str, call: &Range<usize>) -> Range<usize> {
+    let open = call.end - 1;
+    let close = matching_close(content, open).unwrap_or(content.len());
+    open + 1..close
+}
+
+/// Helper functions whose bodies call AI, with their body ranges.
+fn ai_helpers(content: &str, calls: &[Range<usize>]) -> Vec<(String, Range<usize>)> {
+    let mut helpers = Vec::new();
+    for caps in FUNCTION_DECL.captures_iter(content) {
+        let whole = caps.get(0).unwrap();
+        let Some(name) = caps.get(1).or_else(|| caps.get(2)) else {
+            continue;
+        };
+        let Some(params_end) = matching_close(content, whole.end() - 1) else {
+            continue;
+        };
+        // Skip a return type annotation and `=>` up to the body
+        let rest = &content[params_end + 1..];
+        let Some(offset) = rest.find(['{', ';']) else {
+            continue;
+        };
+        let between = rest[..offset].trim();
+        if rest.as_bytes()[offset] != b'{' || !(between.is_empty() || between.starts_with(':') || between == "=>") {
+            continue;
+        }
+        let open = params_end + 1 + offset;
+        let Some(close) = matching_close(content, open) else {
+            continue;
+        };
+        let body = open + 1..close;
+        if calls.iter().any(|call| body.contains(&call.start)) {
+            helpers.push((name.as_str().to_string(), body));
+        }
+    }
+    helpers
+}
+
+/// Whether a condition decides on the value of AI output rather than only
+/// checking that there is some: `label === 'urgent'` does, while `summary`,
+/// `result.success` and `points.length > 0` are guards. A bare name counts
+/// when it holds a decision already made on AI output (`decisions`).
+fn decides_on(condition: &str, tainted: &HashSet<String>, decisions: &HashSet<String>) -> bool {
+    condition.split("&&").flat_map(|clause| clause.split("||")).any(|clause| {
+        let clause = clause.trim().trim_start_matches(['!', '(']).trim_end_matches(')').trim();
+        if clause.chars().all(|c| c.is_ascii_alphanumeric() || "_$.?".contains(c)) {
+            return decisions.contains(clause);
+        }
+        let is_guard = [".length", ".success", ".error", ".ok", "undefined", "null"].iter().any(|g| clause.contains(g));
+        !is_guard && COMPARISON.is_match(clause) && mentions(clause, tainted)
+    })
+}
+
+/// Whether `code` looks a value up by AI output: `channels[category]`.
+fn indexes_by(code: &str, tainted: &HashSet<String>) -> bool {
+    let bytes = code.as_bytes();
+    code.match_indices('[').any(|(open, _)| {
+        let preceded_by_value = open > 0 && (bytes[open - 1].is_ascii_alphanumeric() || matches!(bytes[open - 1], b'_' | b'$' | b']' | b')'));
+        let key = code[open + 1..].split(']').next().unwrap_or_default().trim();
+        preceded_by_value && tainted.contains(key)
+    })
+}
+
+/// Counts AI call sites and estimates their role.
+pub fn analyze_ai_usage(content: &str) -> AiUsage {
+    // Prompts and comments mention words like `summary`; only code counts
+    analyze_code(&code_only(content))
+}
+
+/// [`analyze_ai_usage`] over a file whose strings and comments are already
+/// blanked.
+fn analyze_code(content: &str) -> AiUsage {
+    let calls: Vec<Range<usize>> = AI_CALL.find_iter(content).map(|m| m.range()).collect();
+    let helpers = ai_helpers(content, &calls);
+
+    // Calls whose result is AI output: direct calls, and calls to helpers
+    // from outside their own body
+    let mut sources = calls.clone();
+    for call in CALL_START.find_iter(content) {
+        let name = call.as_str().trim_end_matches('(').trim_end();
+        let outside = |(helper, body): &(String, Range<usize>)| *helper == name && !body.contains(&call.start());
+        if helpers.iter().any(outside) && !content[..call.start()].trim_end().ends_with("function") {
+            sources.push(call.range());
+        }
+    }
+
+    let mut tainted: HashSet<String> = sources
+        .iter()
+        .flat_map(|call| result_bindings(content, call.start))
+        .collect();
+
+    // Follow results through assignments until nothing new is reached
+    let assignments: Vec<(Vec<String>, &str)> = ASSIGNMENT
+        .captures_iter(content)
+        .map(|caps| (binding_names(&caps[1]), caps.get(2).unwrap().as_str()))
+        .collect();
+    let mut decisions: HashSet<String> = HashSet::new();
+    loop {
+        let before = tainted.len() + decisions.len();
+        for (names, rhs) in &assignments {
+            if mentions(rhs, &tainted) {
+                tainted.extend(names.iter().cloned());
+                // `x => ...` callbacks filter or map; only a plain boolean is a decision
+                if !rhs.contains("=>") && decides_on(rhs, &tainted, &decisions) {
+                    decisions.extend(names.iter().cloned());
+                }
+            }
+        }
+        if tainted.len() + decisions.len() == before {
+            break;
+        }
+    }
+
+    let drives_control_flow = CONDITION_START.find_iter(content).any(|m| {
+        let condition = call_args(content, &m.range());
+        let text = &content[condition.clone()];
+        let decides = if m.as_str().starts_with("switch") { mentions(text, &tainted) } else { decides_on(text, &tainted, &decisions) };
+        decides || sources.iter().any(|call| condition.contains(&call.start))
+    }) || indexes_by(content, &tainted);
+    let chained = calls
+        .iter()
+        .any(|call| mentions(&content[call_args(content, call)], &tainted));
+
+    let call_sites = calls.len() as u32;
+    let role = if call_sites == 0 {
+        AiRole::None
+    } else if chained || call_sites >= PIPELINE_CALL_SITES {
+        AiRole::Pipeline
+    } else if drives_control_flow {
+        AiRole::ControlFlow
+    } else {
+        AiRole::Enrichment
+    };
+
+    AiUsage {
+        call_sites,
+        role,
+        suggested_type: role.expected_type().to_string(),
+    }
+}
+
+pub(crate) fn validate_workflow_type(
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
+    metadata: &mut WorkflowMetadata,
+) {
+    let content = scan.content();
+    let usage = analyze_code(scan.code());
+    // The blanked code keeps offsets, so a call in a comment is not the first
+    let first_call = AI_CALL.find(scan.code()).map(|m| m.range());
+
+    if let Some(declared) = WORKFLOW_TYPE.captures(content).and_then(|caps| caps.get(1)) {
+        let suggested = usage.role.expected_type();
+        let fix = |d: Diagnostic| {
+            d.with_span(content, declared.range()).with_fix(
+                &format!("Declare type '{}'", suggested),
+                vec![TextEdit::replace(declared.range(), suggested)],
+            )
+        };
+
+        match (declared.as_str(), usage.role) {
+            ("ai-enhanced" | "ai-native", AiRole::None) => diagnostics.push(fix(
+                Diagnostic::warning(
+                    "WORKFLOW_TYPE_MISMATCH",
+                    &format!("Workflow is declared '{}' but makes no Workers AI calls", declared.as_str()),
+                )
+                .with_suggestion("Declare type 'integration', or add the AI step this workflow is meant to have"),
+            )),
+            ("integration", _) if usage.role != AiRole::None => {
+                let mut diagnostic = fix(
+                    Diagnostic::warning(
+                        "WORKFLOW_TYPE_MISMATCH",
+                        &format!(
+                            "Workflow is declared 'integration' but makes {} Workers AI call(s)",
+                            usage.call_sites
+                        ),
+                    )
+                    .with_suggestion(&format!("Declare type '{}' so the AI runtime and pricing apply", suggested)),
+                );
+                if let Some(call) = first_call {
+                    diagnostic = diagnostic.with_related(content, call, "First AI call");
+                }
+                diagnostics.push(diagnostic);
+            }
+            ("ai-native", AiRole::Enrichment | AiRole::ControlFlow) => diagnostics.push(fix(
+                Diagnostic::info(
+                    "AI_ROLE_MISMATCH",
+                    &format!(
+                        "Workflow is declared 'ai-native' but AI is {} ({} call site(s))",
+                        usage.role, usage.call_sites
+                    ),
+                )
+                .with_suggestion("ai-native is for multi-step AI pipelines; one or two supporting calls fit 'ai-enhanced'"),
+            )),
+            ("ai-enhanced", AiRole::Pipeline) => diagnostics.push(fix(
+                Diagnostic::info(
+                    "AI_ROLE_MISMATCH",
+                    &format!(
+                        "Workflow is declared 'ai-enhanced' but AI is {} ({} call site(s))",
+                        usage.role, usage.call_sites
+                    ),
+                )
+                .with_suggestion("Multi-step AI pipelines fit 'ai-native', which adds caching and a matching price range"),
+            )),
+            _ => {}
+        }
+    }
+
+    metadata.ai_usage = Some(usage);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(body: &str) -> AiRole {
+        analyze_ai_usage(body).role
+    }
+
+    #[test]
+    fn test_role_follows_ai_output() {
+        assert_eq!(role("const total = items.length;\nif (total > 5) notify();"), AiRole::None);
+
+        let enrichment = "const summary = await integrations.ai.generateText({ prompt });\n\
+                          await slack.post({ text: summary.data });";
+        assert_eq!(role(enrichment), AiRole::Enrichment);
+
+        let routing = "const result = await env.AI.run(model, { text });\n\
+                       const label = result.label;\n\
+                       if (label === 'urgent') { await page(); }";
+        assert_eq!(role(routing), AiRole::ControlFlow);
+
+        let helper = "async function classify(ai: any, text: string): Promise<string> {\n\
+                      \tconst res = await ai.run(model, { text });\n\
+                      \treturn res.response;\n\
+                      }\n\
+                      const kind = await classify(env.AI, email.body);\n\
+                      switch (kind) { case 'bug': break; }";
+        assert_eq!(role(helper), AiRole::ControlFlow);
+
+        let pipeline = "const research = await ai.generateText({ prompt: topic });\n\
+                        const draft = await ai.generateText({ prompt: `Write about ${research.data}` });";
+        assert_eq!(role(pipeline), AiRole::Pipeline);
+    }
+
+    #[test]
+    fn test_declared_type_is_checked() {
+        let check = |content: &str| {
+            let mut diagnostics = Vec::new();
+            let mut metadata = WorkflowMetadata::default();
+            validate_workflow_type(&Scan::new(content), &mut diagnostics, &mut metadata);
+            diagnostics
+        };
+
+        let no_ai = check("type: 'ai-native',\nasync execute() { return { success: true }; }");
+        assert_eq!(no_ai[0].code, "WORKFLOW_TYPE_MISMATCH");
+        assert_eq!(no_ai[0].fixes[0].edits[0].replacement, "integration");
+
+        let undeclared = check("type: 'integration',\nconst r = await ai.generateText({ prompt });\nreturn r;");
+        assert_eq!(undeclared[0].code, "WORKFLOW_TYPE_MISMATCH");
+        assert_eq!(undeclared[0].related.len(), 1);
+        assert_eq!(undeclared[0].fixes[0].edits[0].replacement, "ai-enhanced");
+
+        let overstated = check("type: 'ai-native',\nconst r = await ai.generateText({ prompt });\nreturn r;");
+        assert_eq!(overstated[0].code, "AI_ROLE_MISMATCH");
+
+        assert!(check("type: 'ai-enhanced',\nconst r = await ai.generateText({ prompt });").is_empty());
+    }
+
+    #[test]
+    fn test_first_call_skips_comments_and_strings() {
+        let content = "type: 'integration',\n\
+                       // was: await ai.generateText({ prompt })\n\
+                       const hint = 'ai.generateText(';\n\
+                       const r = await ai.generateText({ prompt });\n\
+                       return r;";
+        let mut diagnostics = Vec::new();
+        validate_workflow_type(&Scan::new(content), &mut diagnostics, &mut WorkflowMetadata::default());
+        assert_eq!(diagnostics[0].code, "WORKFLOW_TYPE_MISMATCH");
+        assert_eq!(diagnostics[0].related[0].line, Some(4));
+    }
+}
@@ -0,0 +1,282 @@
+//! Baselines of known diagnostics.
+//!
+//! A baseline records the findings a project has accepted for now, so CI
+//! only fails on new ones. Entries are fingerprinted by code, file and the
+//! normalized source snippet the diagnostic points at - not by line number -
+//! so unrelated edits that shift code around do not invalidate the baseline.
+//! Only the fingerprint of the snippet is stored, never the source itself,
+//! so a baselined hardcoded secret is not copied into the baseline file.
+//! Identical findings in the same file are counted, so adding a second copy
+//! of a baselined mistake is still reported.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::Diagnostic;
+use crate::validator::ValidationResult;
+
+/// Default baseline file name
+pub const BASELINE_FILE_NAME: &str = ".workway-baseline.json";
+
+/// Version of the baseline file format
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Longest snippet included in a fingerprint
+const MAX_SNIPPET_LEN: usize = 120;
+
+/// A recorded finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub code: String,
+    pub file: String,
+    /// Message at the time the baseline was written (informational)
+    pub message: String,
+}
+
+/// A set of accepted findings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// 64-bit FNV-1a; stable across platforms and releases, unlike `DefaultHasher`.
+fn fnv1a(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.bytes().chain([0u8]) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Collapses whitespace and truncates, so formatting changes do not matter.
+fn normalize_snippet(text: &str) -> String {
+    let mut snippet = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if snippet.len() > MAX_SNIPPET_LEN {
+        let mut end = MAX_SNIPPET_LEN;
+        while !snippet.is_char_boundary(end) {
+            end -= 1;
+        }
+        snippet.truncate(end);
+    }
+    snippet
+}
+
+/// The source a diagnostic points at: its span, else its line, else nothing.
+fn snippet_for(content: &str, diagnostic: &Diagnostic) -> String {
+    if let Some(span) = diagnostic.span {
+        if let Some(text) = content.get(span.start..span.end) {
+            return normalize_snippet(text);
+        }
+    }
+    match diagnostic.line {
+        Some(line) => normalize_snippet(content.lines().nth(line.saturating_sub(1) as usize).unwrap_or_default()),
+        None => String::new(),
+    }
+}
+
+/// Normalizes a path for storage (`/` separators, no leading `./`).
+fn normalize_file(file: &str) -> String {
+    file.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+impl BaselineEntry {
+    pub fn new(file: &str, content: &str, diagnostic: &Diagnostic) -> Self {
+        let file = normalize_file(file);
+        let snippet = snippet_for(content, diagnostic);
+        Self {
+            fingerprint: format!("{:016x}", fnv1a(&[&diagnostic.code, &file, &snippet])),
+            code: diagnostic.code.clone(),
+            file,
+            message: diagnostic.message.clone(),
+        }
+    }
+}
+
+impl Baseline {
+    /// Records every diagnostic of a validated file.
+    pub fn add(&mut self, file: &str, content: &str, result: &ValidationResult) {
+        self.entries
+            .extend(result.diagnostics.iter().map(|d| BaselineEntry::new(file, content, d)));
+    }
+
+    /// Replaces the entries of a validated file with its current diagnostics,
+    /// keeping the entries of every other file.
+    pub fn update(&mut self, file: &str, content: &str, result: &ValidationResult) {
+        let key = normalize_file(file);
+        self.entries.retain(|e| e.file != key);
+        self.add(file, content, result);
+    }
+
+    /// Parses a baseline file.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let baseline: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(format!(
+                "unsupported baseline version {} (expected {})",
+                baseline.version, BASELINE_VERSION
+            ));
+        }
+        Ok(baseline)
+    }
+
+    /// Serializes the baseline with entries in a stable order, so rewriting
+    /// an unchanged baseline produces no diff.
+    pub fn to_json(&self) -> String {
+        let mut sorted = self.clone();
+        sorted.entries.sort_by(|a, b| {
+            (&a.file, &a.code, &a.message, &a.fingerprint).cmp(&(&b.file, &b.code, &b.message, &b.fingerprint))
+        });
+        serde_json::to_string_pretty(&sorted).unwrap_or_default()
+    }
+
+    /// Starts comparing validation results against this baseline.
+    pub fn filter(&self) -> BaselineFilter<'_> {
+        let mut remaining: HashMap<&str, usize> = HashMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry.fingerprint.as_str()).or_default() += 1;
+        }
+        BaselineFilter {
+            baseline: self,
+            remaining,
+            seen_files: BTreeSet::new(),
+            suppressed: 0,
+        }
+    }
+}
+
+/// Removes baselined findings from results, file by file.
+pub struct BaselineFilter<'a> {
+    baseline: &'a Baseline,
+    /// Unmatched entries per fingerprint
+    remaining: HashMap<&'a str, usize>,
+    seen_files: BTreeSet<String>,
+    suppressed: usize,
+}
+
+impl BaselineFilter<'_> {
+    /// Drops diagnostics recorded in the baseline and recomputes validity.
+    pub fn apply(&mut self, file: &str, content: &str, result: ValidationResult) -> ValidationResult {
+        self.seen_files.insert(normalize_file(file));
+        let ValidationResult { diagnostics, metadata, .. } = result;
+
+        let new = diagnostics
+            .into_iter()
+            .filter(|d| {
+                let fingerprint = BaselineEntry::new(file, content, d).fingerprint;
+                match self.remaining.get_mut(fingerprint.as_str()) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        self.suppressed += 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect();
+        ValidationResult::new(new, metadata)
+    }
+
+    /// Number of findings matched by the baseline so far
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+
+    /// Baseline entries for files that were checked but no longer occur.
+    /// Entries for files that were not checked are left alone.
+    pub fn fixed(&self) -> Vec<&BaselineEntry> {
+        let mut remaining = self.remaining.clone();
+        self.baseline
+            .entries
+            .iter()
+            .filter(|e| self.seen_files.contains(&e.file))
+            .filter(|e| match remaining.get_mut(e.fingerprint.as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::validate_workflow;
+
+    const BEFORE: &str = r#"
+import { defineWorkflow } from '@workway/sdk';
+export default defineWorkflow({
+    name: 'Sync',
+    trigger: webhook({ service: 'stripe', event: 'payment.succeeded' }),
+    async execute() {
+        try { await sync(); } catch (e) {}
+        return { success: true };
+    }
+});
+"#;
+
+    #[test]
+    fn test_baseline_ignores_line_shifts() {
+        let mut baseline = Baseline::default();
+        baseline.add("./workflows/sync.ts", BEFORE, &validate_workflow(BEFORE));
+        let baseline = Baseline::parse(&baseline.to_json()).unwrap();
+
+        // Shift everything down and add a new finding
+        let after = format!("// header\n\n{}\nconst apiKey = 'sk_live_abcdefghijklmnop';\n", BEFORE);
+        let mut filter = baseline.filter();
+        let result = filter.apply("workflows/sync.ts", &after, validate_workflow(&after));
+
+        let codes: Vec<_> = result.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, vec!["HARDCODED_SECRET"]);
+        assert!(!result.valid);
+        assert_eq!(filter.suppressed(), baseline.entries.len());
+        assert!(filter.fixed().is_empty());
+    }
+
+    #[test]
+    fn test_fixed_entries_are_reported() {
+        let mut baseline = Baseline::default();
+        baseline.add("a.ts", BEFORE, &validate_workflow(BEFORE));
+        baseline.add("b.ts", BEFORE, &validate_workflow(BEFORE));
+
+        let fixed_content = BEFORE.replace("catch (e) {}", "catch (e) { throw e; }");
+        let mut filter = baseline.filter();
+        let result = filter.apply("a.ts", &fixed_content, validate_workflow(&fixed_content));
+
+        assert!(result.diagnostics.is_empty());
+        let fixed: Vec<_> = filter.fixed().iter().map(|e| (e.file.as_str(), e.code.as_str())).collect();
+        assert_eq!(fixed, vec![("a.ts", "EMPTY_CATCH")]);
+    }
+
+    #[test]
+    fn test_update_keeps_other_files() {
+        let mut baseline = Baseline::default();
+        baseline.add("a.ts", BEFORE, &validate_workflow(BEFORE));
+        baseline.add("b.ts", BEFORE, &validate_workflow(BEFORE));
+        let per_file = baseline.entries.len() / 2;
+
+        let fixed_content = BEFORE.replace("catch (e) {}", "catch (e) { throw e; }");
+        baseline.update("./a.ts", &fixed_content, &validate_workflow(&fixed_content));
+
+        assert_eq!(baseline.entries.iter().filter(|e| e.file == "b.ts").count(), per_file);
+        assert!(baseline.entries.iter().all(|e| e.file != "a.ts" || e.code != "EMPTY_CATCH"));
+        assert_eq!(baseline.entries.len(), 2 * per_file - 1);
+    }
+}
@@ -0,0 +1,750 @@
+//! Language server for WORKWAY workflow files.
+//!
+//! Speaks LSP over stdio. Open TypeScript/JavaScript documents that call
+//! `defineWorkflow` are validated on open and on every change, using the
+//! nearest `.workway-validator.toml`. Configurations are read once and kept
+//! until the client reports that a config file or `package.json` changed,
+//! which revalidates every open document. The server also provides:
+//!
+//! - quick fixes for diagnostics that carry mechanical fixes; a diagnostic
+//!   that only carries a suggestion lists it as a disabled action
+//! - a `window/showMessage` error when a config file cannot be read
+//! - hover documentation for diagnostic codes (from the code catalog)
+//! - completion of integration names, trigger helpers and cron fields
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument,
+    DidChangeWatchedFiles,
+    DidCloseTextDocument,
+    DidOpenTextDocument,
+    Notification as _,
+    PublishDiagnostics,
+    ShowMessage,
+};
+use lsp_types::request::{CodeActionRequest, Completion, HoverRequest, RegisterCapability, Request as _};
+use lsp_types::{
+    CodeAction,
+    CodeActionDisabled,
+    CodeActionKind,
+    CodeActionOrCommand,
+    CodeActionParams,
+    CodeActionProviderCapability,
+    CodeDescription,
+    CompletionItem,
+    CompletionItemKind,
+    CompletionOptions,
+    CompletionParams,
+    CompletionResponse,
+    DiagnosticRelatedInformation,
+    DiagnosticSeverity,
+    DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions,
+    DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams,
+    Documentation,
+    FileSystemWatcher,
+    GlobPattern,
+    Hover,
+    HoverContents,
+    HoverParams,
+    HoverProviderCapability,
+    InitializeParams,
+    InsertTextFormat,
+    Location,
+    MarkupContent,
+    MarkupKind,
+    MessageType,
+    NumberOrString,
+    Position,
+    PublishDiagnosticsParams,
+    Range,
+    Registration,
+    RegistrationParams,
+    ServerCapabilities,
+    ShowMessageParams,
+    TextDocumentSyncCapability,
+    TextDocumentSyncKind,
+    Url,
+    WorkspaceEdit,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use workway_validator::{
+    explain,
+    validate_workflow,
+    validate_workflow_with_config,
+    ConfigCache,
+    ConfigError,
+    Diagnostic,
+    Severity,
+    ValidationResult,
+    ValidatorConfig,
+    CONFIG_FILE_NAME,
+    CRON_FIELDS,
+    KNOWN_INTEGRATIONS,
+};
+
+/// Trigger helpers exported by the SDK: (name, snippet, description)
+const TRIGGER_SNIPPETS: &[(&str, &str, &str)] = &[
+    ("webhook", "webhook({ service: '${1:stripe}', event: '${2:payment.succeeded}' })", "Run when a service sends a webhook event"),
+    ("schedule", "schedule('${1:0 9 * * *}')", "Run on a cron schedule"),
+    ("cron", "cron({ schedule: '${1:0 9 * * *}', timezone: '${2:UTC}' })", "Run on a cron schedule (alias of schedule)"),
+    ("manual", "manual()", "Run when the user triggers the workflow"),
+    ("poll", "poll({ service: '${1:rss}', interval: ${2:3600} })", "Check a service for changes at an interval (seconds, at least 60)"),
+];
+
+/// Common cron schedules offered at the start of an expression
+const CRON_PRESETS: &[(&str, &str)] = &[
+    ("0 9 * * *", "Every day at 09:00"),
+    ("0 9 * * 1-5", "Weekdays at 09:00"),
+    ("0 * * * *", "Every hour"),
+    ("*/15 * * * *", "Every 15 minutes"),
+    ("0 0 1 * *", "First day of every month at midnight"),
+];
+
+/// Cursor inside a cron string: `schedule('`, `cron: '`, `schedule: '`
+static CRON_STRING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:\bschedule\s*\(\s*|\b(?:cron|schedule)\s*:\s*)['"`]([^'"`]*)$"#).unwrap()
+});
+
+/// Cursor inside a `service: '...'` string
+static SERVICE_STRING: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bservice\s*:\s*['"`][\w-]*$"#).unwrap());
+
+/// Cursor inside a string literal that was just opened
+static OPEN_STRING: Lazy<Regex> = Lazy::new(|| Regex::new(r#"['"`][\w-]*$"#).unwrap());
+
+/// Cursor where a trigger expression goes
+static TRIGGER_POSITION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\btrigger\s*:|\bwebhooks\s*:\s*\[[^\]]*)\s*\w*$").unwrap()
+});
+
+static WORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z0-9_]+").unwrap());
+
+/// An open document and its latest validation result
+struct Document {
+    text: String,
+    result: ValidationResult,
+}
+
+/// LSP position (UTF-16 columns) of a byte offset.
+fn position_at(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+/// Byte offset of an LSP position, clamped to the line and document.
+fn offset_at(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let line_end = text[line_start..].find('\n').map_or(text.len(), |i| line_start + i);
+    let mut units = 0;
+    for (i, c) in text[line_start..line_end].char_indices() {
+        if units >= position.character {
+            return line_start + i;
+        }
+        units += c.len_utf16() as u32;
+    }
+    line_end
+}
+
+fn span_range(text: &str, start: usize, end: usize) -> Range {
+    Range::new(position_at(text, start), position_at(text, end))
+}
+
+/// The span of a diagnostic, else its whole line, else the start of the file.
+fn diagnostic_range(text: &str, diagnostic: &Diagnostic) -> Range {
+    if let Some(span) = diagnostic.span {
+        return span_range(text, span.start, span.end);
+    }
+    match diagnostic.line {
+        Some(line) => {
+            let line = line.saturating_sub(1);
+            let len = text.lines().nth(line as usize).map_or(0, |l| l.encode_utf16().count());
+            Range::new(Position::new(line, 0), Position::new(line, len as u32))
+        }
+        None => Range::default(),
+    }
+}
+
+fn overlaps(a: &Range, b: &Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+fn to_lsp_diagnostic(uri: &Url, text: &str, diagnostic: &Diagnostic) -> lsp_types::Diagnostic {
+    let severity = match diagnostic.severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+        Severity::Hint => DiagnosticSeverity::HINT,
+    };
+    let message = match &diagnostic.suggestion {
+        Some(suggestion) => format!("{}\n{}", diagnostic.message, suggestion),
+        None => diagnostic.message.clone(),
+    };
+    let related: Vec<DiagnosticRelatedInformation> = diagnostic
+        .related
+        .iter()
+        .filter_map(|r| {
+            let span = r.span?;
+            Some(DiagnosticRelatedInformation {
+                location: Location::new(uri.clone(), span_range(text, span.start, span.end)),
+                message: r.message.clone(),
+            })
+        })
+        .collect();
+
+    lsp_types::Diagnostic {
+        range: diagnostic_range(text, diagnostic),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(diagnostic.code.clone())),
+        code_description: explain(&diagnostic.code)
+            .and_then(|info| Url::parse(&info.docs_url()).ok())
+            .map(|href| CodeDescription { href }),
+        source: Some("workway".to_string()),
+        message,
+        related_information: (!related.is_empty()).then_some(related),
+        ..Default::default()
+    }
+}
+
+/// Validates a document with the configuration and SDK version nearest to it
+/// on disk. A config file that cannot be read is returned with the result,
+/// which then uses the default configuration.
+fn validate_document(configs: &mut ConfigCache, uri: &Url, text: &str) -> (ValidationResult, Option<ConfigError>) {
+    if !text.contains("defineWorkflow") {
+        return (ValidationResult::new(Vec::new(), None), None);
+    }
+    let Ok(path) = uri.to_file_path() else {
+        return (validate_workflow(text), None);
+    };
+    let (config, relative, error) = match configs.config_for(&path) {
+        Ok((config, relative)) => (config, relative, None),
+        Err(error) => {
+            let mut config = ValidatorConfig::default();
+            config.sdk.version = configs.sdk_version(&path);
+            (config, None, Some(error))
+        }
+    };
+    (validate_workflow_with_config(text, &config, relative.as_deref()), error)
+}
+
+fn markdown(value: String) -> HoverContents {
+    HoverContents::Markup(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
+
+/// Hover documentation for the diagnostic under the cursor, or for a
+/// diagnostic code written in the source.
+fn hover(document: &Document, position: Position) -> Option<Hover> {
+    let cursor = Range::new(position, position);
+    let diagnostic = document
+        .result
+        .diagnostics
+        .iter()
+        .find(|d| overlaps(&diagnostic_range(&document.text, d), &cursor));
+
+    let offset = offset_at(&document.text, position);
+    let word = WORD
+        .find_iter(&document.text)
+        .find(|m| m.start() <= offset && offset <= m.end())
+        .map(|m| m.as_str());
+
+    let code = diagnostic.map(|d| d.code.as_str()).or(word)?;
+    match explain(code) {
+        Some(info) => Some(Hover {
+            contents: markdown(format!(
+                "**{}**: {}\n\n{}\n\n```ts\n// Bad\n{}\n\n// Good\n{}\n```\n\n[Documentation]({})",
+                info.code,
+                info.title,
+                info.explanation,
+                info.bad_example,
+                info.good_example,
+                info.docs_url()
+            )),
+            range: None,
+        }),
+        // Custom rules have no catalog entry; show what the rule says
+        None => diagnostic.map(|d| Hover {
+            contents: markdown(format!(
+                "**{}**\n\n{}\n\n{}",
+                d.code,
+                d.message,
+                d.suggestion.as_deref().unwrap_or_default()
+            )),
+            range: None,
+        }),
+    }
+}
+
+/// Quick fixes for diagnostics in `range`. A diagnostic without fixes lists
+/// its suggestion as a disabled action: the advice shows up in the client's
+/// quick fix list, but there is nothing to apply.
+fn code_actions(uri: &Url, document: &Document, range: Range) -> Vec<CodeActionOrCommand> {
+    let mut actions = Vec::new();
+    for diagnostic in &document.result.diagnostics {
+        if !overlaps(&diagnostic_range(&document.text, diagnostic), &range) {
+            continue;
+        }
+        for (i, fix) in diagnostic.fixes.iter().enumerate() {
+            let edits = fix
+                .edits
+                .iter()
+                .map(|e| lsp_types::TextEdit {
+                    range: span_range(&document.text, e.span.start, e.span.end),
+                    new_text: e.replacement.clone(),
+                })
+                .collect();
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: fix.description.clone(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![to_lsp_diagnostic(uri, &document.text, diagnostic)]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri.clone(), edits)])),
+                    ..Default::default()
+                }),
+                is_preferred: Some(i == 0),
+                ..Default::default()
+            }));
+        }
+        if let (true, Some(suggestion)) = (diagnostic.fixes.is_empty(), &diagnostic.suggestion) {
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: suggestion.clone(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![to_lsp_diagnostic(uri, &document.text, diagnostic)]),
+                disabled: Some(CodeActionDisabled {
+                    reason: format!("{} has no automatic fix", diagnostic.code),
+                }),
+                ..Default::default()
+            }));
+        }
+    }
+    actions
+}
+
+fn cron_completions(expression: &str) -> Vec<CompletionItem> {
+    // The field being typed: one past the last complete one after whitespace
+    let complete = expression.split_whitespace().count();
+    let index = if expression.trim_end().len() < expression.len() || expression.is_empty() {
+        complete
+    } else {
+        complete - 1
+    };
+    let Some(&(name, min, max)) = CRON_FIELDS.get(index) else {
+        return Vec::new();
+    };
+
+    let mut items: Vec<CompletionItem> = [
+        ("*".to_string(), format!("Every {}", name)),
+        (min.to_string(), format!("{} {}", name, min)),
+        (max.to_string(), format!("{} {}", name, max)),
+        (format!("{}-{}", min, max), format!("{} range ({}-{})", name, min, max)),
+    ]
+    .into_iter()
+    .map(|(label, detail)| CompletionItem {
+        label,
+        kind: Some(CompletionItemKind::VALUE),
+        detail: Some(detail),
+        ..Default::default()
+    })
+    .collect();
+
+    if expression.is_empty() {
+        items.extend(CRON_PRESETS.iter().map(|(cron, description)| CompletionItem {
+            label: cron.to_string(),
+            kind: Some(CompletionItemKind::SNIPPET),
+            detail: Some(description.to_string()),
+            ..Default::default()
+        }));
+    }
+    items
+}
+
+/// Whether `before` ends inside the `integrations: [...]` array.
+fn in_integrations_array(before: &str) -> bool {
+    let Some(start) = before.rfind("integrations") else {
+        return false;
+    };
+    let mut depth = 0i32;
+    for c in before[start..].chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0
+}
+
+fn completions(text: &str, offset: usize) -> Vec<CompletionItem> {
+    let before = &text[..offset];
+    let line = &before[before.rfind('\n').map_or(0, |i| i + 1)..];
+
+    if let Some(caps) = CRON_STRING.captures(line) {
+        return cron_completions(&caps[1]);
+    }
+
+    if SERVICE_STRING.is_match(line) || (OPEN_STRING.is_match(line) && in_integrations_array(before)) {
+        return KNOWN_INTEGRATIONS
+            .iter()
+            .map(|name| CompletionItem {
+                label: name.to_string(),
+                kind: Some(CompletionItemKind::VALUE),
+                detail: Some("WORKWAY integration".to_string()),
+                ..Default::default()
+            })
+            .collect();
+    }
+
+    if TRIGGER_POSITION.is_match(line) {
+        return TRIGGER_SNIPPETS
+            .iter()
+            .map(|(name, snippet, description)| CompletionItem {
+                label: name.to_string(),
+                kind: Some(CompletionItemKind::FUNCTION),
+                detail: Some(description.to_string()),
+                documentation: Some(Documentation::String(format!("Trigger helper from @workwayco/sdk: {}", name))),
+                insert_text: Some(snippet.to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            })
+            .collect();
+    }
+
+    Vec::new()
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["'".into(), "\"".into(), " ".into(), ":".into()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Files whose contents decide how documents are validated
+const WATCHED_FILES: &[&str] = &[CONFIG_FILE_NAME, "package.json"];
+
+/// Asks the client to report changes to [`WATCHED_FILES`], if it can.
+fn register_watchers(connection: &Connection, params: &InitializeParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let supported = params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|w| w.did_change_watched_files)
+        .and_then(|c| c.dynamic_registration)
+        .unwrap_or(false);
+    if !supported {
+        return Ok(());
+    }
+    let watchers = WATCHED_FILES
+        .iter()
+        .map(|name| FileSystemWatcher {
+            glob_pattern: GlobPattern::String(format!("**/{}", name)),
+            kind: None,
+        })
+        .collect();
+    let registration = Registration {
+        id: "workway-watched-files".to_string(),
+        method: DidChangeWatchedFiles::METHOD.to_string(),
+        register_options: Some(serde_json::to_value(DidChangeWatchedFilesRegistrationOptions { watchers })?),
+    };
+    let request = Request::new(
+        RequestId::from("workway-register-watchers".to_string()),
+        RegisterCapability::METHOD.to_string(),
+        RegistrationParams { registrations: vec![registration] },
+    );
+    connection.sender.send(Message::Request(request))?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<Url, Document>,
+    /// Configurations read so far, cleared when a watched file changes
+    configs: ConfigCache,
+    /// Config errors already shown, so each is shown once until the files
+    /// change again
+    shown_errors: HashSet<String>,
+    /// Config errors to show
+    messages: Vec<String>,
+}
+
+impl Server {
+    fn run(&mut self, connection: &Connection) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    for uri in self.handle_notification(notification) {
+                        self.publish(connection, uri)?;
+                    }
+                    for message in std::mem::take(&mut self.messages) {
+                        show_error(connection, message)?;
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let Request { id, method, params } = request;
+        let result = match method.as_str() {
+            HoverRequest::METHOD => serde_json::from_value::<HoverParams>(params).map(|p| {
+                let position = p.text_document_position_params;
+                let hover = self
+                    .documents
+                    .get(&position.text_document.uri)
+                    .and_then(|d| hover(d, position.position));
+                serde_json::to_value(hover)
+            }),
+            CodeActionRequest::METHOD => serde_json::from_value::<CodeActionParams>(params).map(|p| {
+                let uri = p.text_document.uri;
+                let actions = self
+                    .documents
+                    .get(&uri)
+                    .map(|d| code_actions(&uri, d, p.range))
+                    .unwrap_or_default();
+                serde_json::to_value(actions)
+            }),
+            Completion::METHOD => serde_json::from_value::<CompletionParams>(params).map(|p| {
+                let position = p.text_document_position;
+                let items = self
+                    .documents
+                    .get(&position.text_document.uri)
+                    .map(|d| completions(&d.text, offset_at(&d.text, position.position)))
+                    .unwrap_or_default();
+                serde_json::to_value(CompletionResponse::Array(items))
+            }),
+            _ => {
+                return Response::new_err(id, ErrorCode::MethodNotFound as i32, format!("unsupported request: {}", method));
+            }
+        };
+
+        match result {
+            Ok(Ok(value)) => Response::new_ok(id, value),
+            Ok(Err(e)) | Err(e) => Response::new_err(id, ErrorCode::InvalidParams as i32, e.to_string()),
+        }
+    }
+
+    /// Updates document state; returns the documents whose diagnostics changed.
+    fn handle_notification(&mut self, notification: Notification) -> Vec<Url> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<DidOpenTextDocumentParams>(notification.params) else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.update(uri.clone(), params.text_document.text);
+                vec![uri]
+            }
+            DidChangeTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<DidChangeTextDocumentParams>(notification.params) else {
+                    return Vec::new();
+                };
+                let Some(change) = params.content_changes.into_iter().last() else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.update(uri.clone(), change.text);
+                vec![uri]
+            }
+            DidCloseTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<DidCloseTextDocumentParams>(notification.params) else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                vec![uri]
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let Ok(params) = serde_json::from_value::<DidChangeWatchedFilesParams>(notification.params) else {
+                    return Vec::new();
+                };
+                let relevant = params.changes.iter().any(|change| {
+                    let name = change.uri.path_segments().and_then(|mut s| s.next_back()).unwrap_or_default();
+                    WATCHED_FILES.contains(&name)
+                });
+                if !relevant {
+                    return Vec::new();
+                }
+                self.configs.clear();
+                self.shown_errors.clear();
+                self.revalidate_all()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn update(&mut self, uri: Url, text: String) {
+        let (result, error) = validate_document(&mut self.configs, &uri, &text);
+        self.report(error);
+        self.documents.insert(uri, Document { text, result });
+    }
+
+    /// Revalidates every open document; returns their URIs.
+    fn revalidate_all(&mut self) -> Vec<Url> {
+        let mut errors = Vec::new();
+        for (uri, document) in self.documents.iter_mut() {
+            let (result, error) = validate_document(&mut self.configs, uri, &document.text);
+            document.result = result;
+            errors.extend(error);
+        }
+        for error in errors {
+            self.report(Some(error));
+        }
+        self.documents.keys().cloned().collect()
+    }
+
+    /// Queues a config error to be shown, unless it already was.
+    fn report(&mut self, error: Option<ConfigError>) {
+        if let Some(message) = error.map(|e| e.to_string()) {
+            if self.shown_errors.insert(message.clone()) {
+                self.messages.push(message);
+            }
+        }
+    }
+
+    fn publish(&self, connection: &Connection, uri: Url) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let diagnostics = match self.documents.get(&uri) {
+            Some(document) => document
+                .result
+                .diagnostics
+                .iter()
+                .map(|d| to_lsp_diagnostic(&uri, &document.text, d))
+                .collect(),
+            None => Vec::new(),
+        };
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        connection.sender.send(Message::Notification(notification))?;
+        Ok(())
+    }
+}
+
+fn show_error(connection: &Connection, message: String) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let params = ShowMessageParams {
+        typ: MessageType::ERROR,
+        message: format!("workway: {}; using the default configuration", message),
+    };
+    let notification = Notification::new(ShowMessage::METHOD.to_string(), params);
+    connection.sender.send(Message::Notification(notification))?;
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (connection, io_threads) = Connection::stdio();
+    let params: InitializeParams = serde_json::from_value(connection.initialize(serde_json::to_value(capabilities())?)?)?;
+    register_watchers(&connection, &params)?;
+    Server::default().run(&connection)?;
+    // The writer thread finishes once the connection's sender is dropped
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_use_utf16_columns() {
+        let text = "const a = '😀';\nconst b = 1;";
+        let offset = text.find("';").unwrap();
+        assert_eq!(position_at(text, offset), Position::new(0, 13));
+        assert_eq!(offset_at(text, Position::new(0, 13)), offset);
+        assert_eq!(offset_at(text, Position::new(1, 6)), text.find("b =").unwrap());
+        assert_eq!(offset_at(text, Position::new(9, 0)), text.len());
+    }
+
+    #[test]
+    fn test_completion_contexts() {
+        let labels = |text: &str| -> Vec<String> { completions(text, text.len()).into_iter().map(|i| i.label).collect() };
+
+        assert!(labels("  integrations: [\n    { service: 'sl").contains(&"slack".to_string()));
+        assert!(labels("  integrations: ['github', '").contains(&"notion".to_string()));
+        assert!(labels("  trigger: ").contains(&"webhook".to_string()));
+        assert!(labels("  trigger: schedule('").contains(&"0 9 * * 1-5".to_string()));
+        assert_eq!(labels("  trigger: schedule('0 9 ")[3], "1-31");
+        assert_eq!(labels("  trigger: schedule('0  9\t")[3], "1-31");
+        assert_eq!(labels("  trigger: schedule('0   9")[3], "0-23");
+        assert!(labels("  name: 'x").is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_are_disabled_code_actions() {
+        let uri = Url::parse("file:///workflow.ts").unwrap();
+        let text = "export default defineWorkflow({\n  name: 'x',\n  integrations: [],\n  async execute() {\n    try { await fetch(url); } catch (e) {}\n  },\n});\n".to_string();
+        let result = validate_workflow(&text);
+        let with_suggestion_only: Vec<&Diagnostic> =
+            result.diagnostics.iter().filter(|d| d.fixes.is_empty() && d.suggestion.is_some()).collect();
+        assert!(!with_suggestion_only.is_empty());
+
+        let document = Document { text, result: result.clone() };
+        let whole = Range::new(Position::new(0, 0), Position::new(99, 0));
+        let titles: Vec<String> = code_actions(&uri, &document, whole)
+            .into_iter()
+            .filter_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) if action.edit.is_none() => {
+                    assert!(action.disabled.is_some(), "'{}' has nothing to apply", action.title);
+                    Some(action.title)
+                }
+                _ => None,
+            })
+            .collect();
+        for diagnostic in with_suggestion_only {
+            assert!(titles.contains(diagnostic.suggestion.as_ref().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_config_errors_are_shown_once() {
+        let dir = std::env::temp_dir().join(format!("workway-lsp-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(CONFIG_FILE_NAME), "[rules\n").unwrap();
+        let uri = Url::from_file_path(dir.join("workflow.ts")).unwrap();
+        let text = "export default defineWorkflow({\n  async execute() {\n    try { a(); } catch (e) {}\n  },\n});\n";
+
+        let mut server = Server::default();
+        server.update(uri.clone(), text.to_string());
+        server.update(uri.clone(), format!("{}\n", text));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(server.messages.len(), 1);
+        assert!(server.messages[0].contains(CONFIG_FILE_NAME));
+        // Still validated, under the defaults
+        assert!(server.documents[&uri].result.diagnostics.iter().any(|d| d.code == "EMPTY_CATCH"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Native command-line validator for CI.
+//!
+//! ```text
+//! workway-validator [OPTIONS] [PATHS]...
+//! ```
+//!
+//! Validates workflow files (directories are searched for files that call
+//! `defineWorkflow`), applying the nearest `.workway-validator.toml` to each
+//! file. Findings recorded in the baseline file are not reported.
+//!
+//! `--format` selects the report format (text, json, jsonl, junit, github or
+//! checkstyle). For machine-readable formats the summary goes to stderr so
+//! stdout (or `--output`) holds only the report.
+//!
+//! `--collection` also checks the workflows against each other: supersededBy
+//! targets, duplicate ids and names, and near-duplicates.
+//!
+//! `--watch` keeps running after the first report and revalidates workflows
+//! as they change (see `watch.rs`).
+
+mod watch;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+use workway_validator::{
+    validate_collection,
+    validate_workflow_with_config,
+    Baseline,
+    ConfigCache,
+    FileReport,
+    ReportFormat,
+    Severity,
+    ValidationResult,
+    BASELINE_FILE_NAME,
+};
+
+#[derive(Parser)]
+#[command(name = "workway-validator", version, about = "Validate WORKWAY workflow files")]
+struct Args {
+    /// Workflow files, or directories to search for workflows
+    #[arg(default_value = ".")]
+    paths: Vec<PathBuf>,
+
+    /// Report format: text, json, jsonl, junit, github or checkstyle
+    #[arg(long, value_name = "FORMAT", default_value = "text")]
+    format: ReportFormat,
+
+    /// Write the report to a file instead of stdout
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Fail on warnings as well as errors
+    #[arg(long)]
+    strict: bool,
+
+    /// Baseline of accepted findings
+    #[arg(long, value_name = "FILE", default_value = BASELINE_FILE_NAME)]
+    baseline: PathBuf,
+
+    /// Record the current findings of the checked files in the baseline file
+    /// and exit; entries for other files are kept
+    #[arg(long)]
+    write_baseline: bool,
+
+    /// Report every finding, ignoring the baseline file
+    #[arg(long, conflicts_with = "write_baseline")]
+    no_baseline: bool,
+
+    /// Also check the workflows against each other (supersededBy, duplicates)
+    #[arg(long, conflicts_with = "watch")]
+    collection: bool,
+
+    /// Keep running and revalidate workflows when files change
+    #[arg(long, conflicts_with_all = ["write_baseline", "output"])]
+    watch: bool,
+}
+
+/// A validated file
+pub(crate) struct Checked {
+    /// Path relative to the baseline's directory, used as the baseline key
+    key: String,
+    content: String,
+    result: ValidationResult,
+}
+
+/// Directories never searched for workflows
+pub(crate) const SKIPPED_DIRS: &[&str] = &["node_modules", "dist", "build", ".git", ".wrangler", "target"];
+
+pub(crate) fn is_source_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let is_script = [".ts", ".js", ".mts", ".mjs"].iter().any(|ext| name.ends_with(ext));
+    is_script && !name.ends_with(".d.ts") && !name.contains(".test.") && !name.contains(".spec.")
+}
+
+/// Expands directories into the workflow files they contain. Files named
+/// explicitly are always validated.
+pub(crate) fn collect_files(paths: &[PathBuf]) -> std::io::Result<Vec<PathBuf>> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name();
+            if path.is_dir() {
+                if !SKIPPED_DIRS.iter().any(|skip| name == *skip) {
+                    walk(&path, files)?;
+                }
+            } else if is_source_file(&path) {
+                let content = std::fs::read_to_string(&path).unwrap_or_default();
+                if content.contains("defineWorkflow") {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            walk(path, &mut files)?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+/// `file` relative to `root`, falling back to the path as given.
+fn relative_to(root: &Path, file: &Path) -> String {
+    let absolute = std::fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+    let relative = absolute.strip_prefix(root).unwrap_or(file);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Validates `content` as the workflow at `file`, with the nearest
+/// configuration and SDK version. `root` is the baseline's directory.
+pub(crate) fn check_source(
+    root: &Path,
+    configs: &mut ConfigCache,
+    file: &Path,
+    content: String,
+) -> Result<Checked, String> {
+    let (config, relative) = configs.config_for(file).map_err(|e| e.to_string())?;
+    let result = validate_workflow_with_config(&content, &config, relative.as_deref());
+    Ok(Checked {
+        key: relative_to(root, file),
+        content,
+        result,
+    })
+}
+
+/// Adds the collection checks to each file's result, under the file's
+/// configuration. `files` and `checked` are in the same order.
+fn check_collection(configs: &mut ConfigCache, files: &[PathBuf], checked: &mut [Checked]) -> Result<(), String> {
+    let pairs: Vec<(&str, &str)> = checked.iter().map(|c| (c.key.as_str(), c.content.as_str())).collect();
+    let found = validate_collection(&pairs);
+    for ((c, file), mut diagnostics) in checked.iter_mut().zip(files).zip(found) {
+        if let Some(loaded) = configs.discover(file).map_err(|e| e.to_string())? {
+            let relative = loaded.relative_path(file);
+            loaded.config.for_path(relative.as_deref()).apply_rules(&mut diagnostics);
+        }
+        let mut all = std::mem::take(&mut c.result.diagnostics);
+        all.extend(diagnostics);
+        c.result = ValidationResult::new(all, c.result.metadata.take());
+    }
+    Ok(())
+}
+
+fn load_baseline(args: &Args) -> Result<Baseline, String> {
+    if args.no_baseline || !args.baseline.is_file() {
+        return Ok(Baseline::default());
+    }
+    let json = std::fs::read_to_string(&args.baseline).map_err(|e| format!("{}: {}", args.baseline.display(), e))?;
+    Baseline::parse(&json).map_err(|e| format!("{}: {}", args.baseline.display(), e))
+}
+
+fn run(args: Args) -> Result<bool, String> {
+    let files = collect_files(&args.paths).map_err(|e| e.to_string())?;
+    let baseline_dir = args
+        .baseline
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let root = std::fs::canonicalize(&baseline_dir).map_err(|e| format!("{}: {}", baseline_dir.display(), e))?;
+
+    let mut configs = ConfigCache::default();
+    let mut checked = Vec::new();
+    for file in &files {
+        let content = std::fs::read_to_string(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+        checked.push(check_source(&root, &mut configs, file, content)?);
+    }
+    if args.collection {
+        check_collection(&mut configs, &files, &mut checked)?;
+    }
+
+    if args.write_baseline {
+        // Only the checked files are rewritten; entries for the rest are kept
+        let mut baseline = load_baseline(&args)?;
+        for c in &checked {
+            baseline.update(&c.key, &c.content, &c.result);
+        }
+        std::fs::write(&args.baseline, baseline.to_json() + "\n")
+            .map_err(|e| format!("{}: {}", args.baseline.display(), e))?;
+        let recorded: usize = checked.iter().map(|c| c.result.diagnostics.len()).sum();
+        println!(
+            "Recorded {} finding(s) from {} file(s) in {} ({} entr{} in total)",
+            recorded,
+            checked.len(),
+            args.baseline.display(),
+            baseline.entries.len(),
+            if baseline.entries.len() == 1 { "y" } else { "ies" }
+        );
+        return Ok(true);
+    }
+
+    let baseline = load_baseline(&args)?;
+
+    let mut filter = baseline.filter();
+    let (mut errors, mut warnings) = (0, 0);
+    for c in checked.iter_mut() {
+        let result = std::mem::replace(&mut c.result, ValidationResult::new(Vec::new(), None));
+        c.result = filter.apply(&c.key, &c.content, result);
+        errors += c.result.errors().count();
+        warnings += c.result.with_severity(Severity::Warning).count();
+    }
+
+    let reports: Vec<FileReport> = checked
+        .iter()
+        .map(|c| FileReport { path: &c.key, result: &c.result })
+        .collect();
+    let report = args.format.reporter().render(&reports);
+    match &args.output {
+        Some(path) => std::fs::write(path, report).map_err(|e| format!("{}: {}", path.display(), e))?,
+        None => print!("{}", report),
+    }
+
+    // Keep stdout machine-readable for tool formats
+    let summary = |line: String| {
+        if args.format.is_human_readable() && args.output.is_none() {
+            println!("{}", line);
+        } else {
+            eprintln!("{}", line);
+        }
+    };
+
+    let fixed = filter.fixed();
+    if !fixed.is_empty() {
+        summary(String::new());
+        summary(format!(
+            "{} baseline entr{} no longer occur (prune with --write-baseline):",
+            fixed.len(),
+            if fixed.len() == 1 { "y" } else { "ies" }
+        ));
+        for entry in &fixed {
+            summary(format!("  {} [{}] {}", entry.file, entry.code, entry.message));
+        }
+    }
+
+    summary(String::new());
+    summary(format!(
+        "{} file(s) checked: {} error(s), {} warning(s){}",
+        checked.len(),
+        errors,
+        warnings,
+        match filter.suppressed() {
+            0 => String::new(),
+            n => format!(", {} baselined", n),
+        }
+    ));
+
+    if args.watch {
+        let mut state = watch::WatchState::new(root, baseline, configs, files.iter().zip(checked));
+        return state.run(&args.paths).map(|_| true);
+    }
+
+    Ok(errors == 0 && (!args.strict || warnings == 0))
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
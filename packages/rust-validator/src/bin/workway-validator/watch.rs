@@ -0,0 +1,437 @@
+//! Watch mode: revalidates workflows as files change.
+//!
+//! Uses the platform file watcher (inotify on Linux). Each workflow's
+//! validation result and its local imports (followed transitively) are kept
+//! between rounds. When files change, only the changed workflows and the
+//! workflows that import a changed module are revalidated; a workflow whose
+//! content and imports did not change keeps its previous result. A change to
+//! `.workway-validator.toml` (or `package.json`) rereads the configurations
+//! and revalidates everything.
+//!
+//! Each round prints the diagnostics that appeared (`+`) and were resolved
+//! (`-`). Diagnostics are matched by baseline fingerprint, so findings that
+//! only moved to another line are not reported as changes.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use workway_validator::{Baseline, BaselineEntry, ConfigCache, Diagnostic, Severity, CONFIG_FILE_NAME};
+
+use crate::{check_source, is_source_file, Checked, SKIPPED_DIRS};
+
+/// Quiet period that ends a round, so a save touching several files (or an
+/// editor writing through a temporary file) is handled once
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Relative module specifiers: `from './x'`, `import './x'`, `import('./x')`
+static LOCAL_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:\bfrom|\bimport\s*\(?)\s*['"](\.\.?/[^'"]+)['"]"#).unwrap()
+});
+
+/// Extensions tried when resolving an import without one
+const EXTENSIONS: &[&str] = &["ts", "mts", "js", "mjs"];
+
+fn with_extension_appended(path: &Path, extension: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// Resolves a relative import the way TypeScript's bundler resolution does:
+/// the path as written, with an extension added, `.js` written for a `.ts`
+/// source, or a directory's index file.
+fn resolve_import(importer: &Path, specifier: &str) -> Option<PathBuf> {
+    let base = importer.parent()?.join(specifier);
+    let mut candidates = vec![base.clone()];
+    candidates.extend(EXTENSIONS.iter().map(|ext| with_extension_appended(&base, ext)));
+    if base.extension().is_some_and(|ext| ext == "js") {
+        candidates.push(base.with_extension("ts"));
+    }
+    candidates.extend(EXTENSIONS.iter().map(|ext| base.join(format!("index.{}", ext))));
+    candidates
+        .into_iter()
+        .find(|p| p.is_file())
+        .and_then(|p| fs::canonicalize(p).ok())
+}
+
+/// Local modules `file` imports, directly or through other local modules.
+fn dependencies(file: &Path, content: &str) -> BTreeSet<PathBuf> {
+    let mut deps = BTreeSet::new();
+    let mut pending = vec![(file.to_path_buf(), content.to_string())];
+    while let Some((path, source)) = pending.pop() {
+        for caps in LOCAL_IMPORT.captures_iter(&source) {
+            let Some(dep) = resolve_import(&path, &caps[1]) else {
+                continue;
+            };
+            if dep != file && deps.insert(dep.clone()) {
+                if let Ok(text) = fs::read_to_string(&dep) {
+                    pending.push((dep, text));
+                }
+            }
+        }
+    }
+    deps
+}
+
+/// Diagnostics of `a` that have no counterpart in `b`, by fingerprint.
+fn unmatched<'a>(a: &'a Checked, b: Option<&Checked>) -> Vec<&'a Diagnostic> {
+    let fingerprint = |c: &Checked, d: &Diagnostic| BaselineEntry::new(&c.key, &c.content, d).fingerprint;
+    let mut remaining: HashMap<String, usize> = HashMap::new();
+    if let Some(b) = b {
+        for d in &b.result.diagnostics {
+            *remaining.entry(fingerprint(b, d)).or_default() += 1;
+        }
+    }
+    a.result
+        .diagnostics
+        .iter()
+        .filter(|d| match remaining.get_mut(&fingerprint(a, d)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// What one round changed for a file
+struct FileChange {
+    key: String,
+    appeared: Vec<Diagnostic>,
+    resolved: Vec<Diagnostic>,
+    deleted: bool,
+}
+
+impl FileChange {
+    fn new(old: Option<&Checked>, new: Option<&Checked>) -> Option<Self> {
+        let key = new.or(old)?.key.clone();
+        let appeared: Vec<Diagnostic> = new.map(|n| unmatched(n, old).into_iter().cloned().collect()).unwrap_or_default();
+        let resolved: Vec<Diagnostic> = old.map(|o| unmatched(o, new).into_iter().cloned().collect()).unwrap_or_default();
+        Some(Self {
+            key,
+            appeared,
+            resolved,
+            deleted: new.is_none(),
+        })
+    }
+
+    fn render(&self) -> String {
+        let mut out = self.key.clone();
+        if self.deleted {
+            out.push_str(" (deleted)");
+        }
+        for (sign, diagnostics) in [('+', &self.appeared), ('-', &self.resolved)] {
+            for d in diagnostics {
+                let line = d.line.map(|l| format!(":{}", l)).unwrap_or_default();
+                out.push_str(&format!("\n  {} {}{}: {}[{}] {}", sign, self.key, line, d.severity, d.code, d.message));
+            }
+        }
+        out
+    }
+}
+
+/// A workflow being watched
+struct Tracked {
+    checked: Checked,
+    /// Canonical paths of the local modules it imports
+    deps: BTreeSet<PathBuf>,
+}
+
+/// Results and import graph kept between watch rounds
+pub(crate) struct WatchState {
+    root: PathBuf,
+    baseline: Baseline,
+    /// Configurations read so far, cleared when one changes
+    configs: ConfigCache,
+    /// Workflows by canonical path
+    files: BTreeMap<PathBuf, Tracked>,
+    /// Files named on the command line
+    explicit: BTreeSet<PathBuf>,
+    /// Directories searched for new workflows
+    tree_dirs: BTreeSet<PathBuf>,
+    /// Every directory with a watch
+    watched_dirs: BTreeSet<PathBuf>,
+}
+
+impl WatchState {
+    /// Starts from the results of the initial run.
+    pub(crate) fn new<'a>(
+        root: PathBuf,
+        baseline: Baseline,
+        configs: ConfigCache,
+        checked: impl IntoIterator<Item = (&'a PathBuf, Checked)>,
+    ) -> Self {
+        let files = checked
+            .into_iter()
+            .filter_map(|(path, checked)| {
+                let path = fs::canonicalize(path).ok()?;
+                let deps = dependencies(&path, &checked.content);
+                Some((path, Tracked { checked, deps }))
+            })
+            .collect();
+        Self {
+            root,
+            baseline,
+            configs,
+            files,
+            explicit: BTreeSet::new(),
+            tree_dirs: BTreeSet::new(),
+            watched_dirs: BTreeSet::new(),
+        }
+    }
+
+    fn watch_dir(&mut self, watcher: &mut RecommendedWatcher, dir: &Path) -> Result<(), String> {
+        if self.watched_dirs.insert(dir.to_path_buf()) {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// Watches `dir` and its subdirectories, except skipped ones. Directories
+    /// are watched one by one so `node_modules` and build output never are.
+    fn watch_tree(&mut self, watcher: &mut RecommendedWatcher, dir: &Path) -> Result<(), String> {
+        self.tree_dirs.insert(dir.to_path_buf());
+        self.watch_dir(watcher, dir)?;
+        let entries = fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() && !SKIPPED_DIRS.iter().any(|skip| entry.file_name() == *skip) {
+                self.watch_tree(watcher, &path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Watches the directories of imported modules outside the watched trees.
+    fn watch_dependencies(&mut self, watcher: &mut RecommendedWatcher) -> Result<(), String> {
+        let dirs: BTreeSet<PathBuf> = self
+            .files
+            .values()
+            .flat_map(|t| &t.deps)
+            .filter_map(|dep| dep.parent().map(Path::to_path_buf))
+            .collect();
+        for dir in dirs {
+            self.watch_dir(watcher, &dir)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the paths of a file system event to `changed`.
+    fn collect(&mut self, watcher: &mut RecommendedWatcher, event: notify::Result<Event>, changed: &mut BTreeSet<PathBuf>) {
+        let Ok(event) = event else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        for path in event.paths {
+            if !path.is_dir() {
+                changed.insert(path);
+                continue;
+            }
+            // A directory created (or moved) into a watched tree
+            let in_tree = path.parent().is_some_and(|p| self.tree_dirs.contains(p));
+            let skipped = path.file_name().is_some_and(|n| SKIPPED_DIRS.iter().any(|skip| n == *skip));
+            if in_tree && !skipped && !self.watched_dirs.contains(&path) {
+                if let Err(e) = self.watch_tree(watcher, &path) {
+                    eprintln!("error: {}", e);
+                }
+                if let Ok(files) = crate::collect_files(&[path]) {
+                    changed.extend(files);
+                }
+            }
+        }
+    }
+
+    /// Whether a file not yet tracked should be validated as a workflow
+    fn is_new_workflow(&self, path: &Path) -> bool {
+        let in_scope = self.explicit.contains(path) || path.parent().is_some_and(|p| self.tree_dirs.contains(p));
+        in_scope
+            && is_source_file(path)
+            && fs::read_to_string(path).is_ok_and(|content| content.contains("defineWorkflow"))
+    }
+
+    fn check(&mut self, path: &Path, content: String) -> Result<Checked, String> {
+        let mut checked = check_source(&self.root, &mut self.configs, path, content)?;
+        let result = std::mem::replace(&mut checked.result, workway_validator::ValidationResult::new(Vec::new(), None));
+        checked.result = self.baseline.filter().apply(&checked.key, &checked.content, result);
+        Ok(checked)
+    }
+
+    /// Revalidates what `changed` affects and returns the differences.
+    fn process(&mut self, changed: &BTreeSet<PathBuf>) -> (usize, Vec<FileChange>) {
+        // package.json can change the SDK version deprecations are checked against
+        let config_changed = changed
+            .iter()
+            .any(|p| p.file_name().is_some_and(|n| n == CONFIG_FILE_NAME || n == "package.json"));
+        if config_changed {
+            self.configs.clear();
+        }
+        let mut targets: BTreeSet<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, t)| config_changed || changed.contains(*path) || !t.deps.is_disjoint(changed))
+            .map(|(path, _)| path.clone())
+            .collect();
+        targets.extend(
+            changed
+                .iter()
+                .filter(|p| !self.files.contains_key(*p) && self.is_new_workflow(p))
+                .cloned(),
+        );
+
+        let mut revalidated = 0;
+        let mut changes = Vec::new();
+        for path in targets {
+            let old = self.files.remove(&path);
+            let Ok(content) = fs::read_to_string(&path) else {
+                changes.extend(FileChange::new(old.as_ref().map(|t| &t.checked), None));
+                continue;
+            };
+
+            // Reuse the previous result when nothing it depends on changed
+            let old = match old {
+                Some(tracked)
+                    if !config_changed && tracked.checked.content == content && tracked.deps.is_disjoint(changed) =>
+                {
+                    self.files.insert(path, tracked);
+                    continue;
+                }
+                old => old,
+            };
+
+            revalidated += 1;
+            match self.check(&path, content) {
+                Ok(checked) => {
+                    changes.extend(FileChange::new(old.as_ref().map(|t| &t.checked), Some(&checked)));
+                    let deps = dependencies(&path, &checked.content);
+                    self.files.insert(path, Tracked { checked, deps });
+                }
+                Err(message) => {
+                    eprintln!("error: {}", message);
+                    if let Some(old) = old {
+                        self.files.insert(path, old);
+                    }
+                }
+            }
+        }
+        (revalidated, changes)
+    }
+
+    fn summary(&self, revalidated: usize, changes: &[FileChange]) -> String {
+        let count = |sev: Severity| -> usize {
+            self.files
+                .values()
+                .map(|t| t.checked.result.with_severity(sev).count())
+                .sum()
+        };
+        let appeared: usize = changes.iter().map(|c| c.appeared.len()).sum();
+        let resolved: usize = changes.iter().map(|c| c.resolved.len()).sum();
+        format!(
+            "{} file(s) revalidated: {} new, {} resolved; {} error(s), {} warning(s) in {} file(s)",
+            revalidated,
+            appeared,
+            resolved,
+            count(Severity::Error),
+            count(Severity::Warning),
+            self.files.len()
+        )
+    }
+
+    /// Watches `paths` until interrupted, printing each round's changes.
+    pub(crate) fn run(&mut self, paths: &[PathBuf]) -> Result<(), String> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+        for path in paths {
+            let path = fs::canonicalize(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            if path.is_dir() {
+                self.watch_tree(&mut watcher, &path)?;
+            } else if let Some(dir) = path.parent() {
+                // Watch the directory, not the file: editors often save by
+                // replacing the file, which would drop a watch on it
+                self.watch_dir(&mut watcher, dir)?;
+                self.explicit.insert(path);
+            }
+        }
+        self.watch_dependencies(&mut watcher)?;
+        eprintln!("\nWatching {} workflow(s) for changes (Ctrl-C to stop)", self.files.len());
+
+        while let Ok(event) = rx.recv() {
+            let mut changed = BTreeSet::new();
+            self.collect(&mut watcher, event, &mut changed);
+            while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+                self.collect(&mut watcher, event, &mut changed);
+            }
+
+            let (revalidated, changes) = self.process(&changed);
+            if revalidated == 0 && changes.is_empty() {
+                continue;
+            }
+            println!();
+            for change in changes.iter().filter(|c| c.deleted || !c.appeared.is_empty() || !c.resolved.is_empty()) {
+                println!("{}", change.render());
+            }
+            println!("{}", self.summary(revalidated, &changes));
+            self.watch_dependencies(&mut watcher)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workway_validator::validate_workflow;
+
+    fn checked(content: &str) -> Checked {
+        Checked {
+            key: "wf.ts".to_string(),
+            content: content.to_string(),
+            result: validate_workflow(content),
+        }
+    }
+
+    #[test]
+    fn test_moved_findings_are_not_changes() {
+        let before = checked(
+            "export default defineWorkflow({\n  async execute() {\n    try { a(); } catch (e) {}\n    const apiKey = 'sk_live_abcdefghijklmnop';\n  }\n});\n",
+        );
+        // The empty catch moves down a line, the secret is fixed, a loop is added
+        let after = checked(
+            "export default defineWorkflow({\n  async execute() {\n\n    try { a(); } catch (e) {}\n    for (const x of xs) { await a(x); }\n  }\n});\n",
+        );
+
+        let change = FileChange::new(Some(&before), Some(&after)).unwrap();
+        let codes = |ds: &[Diagnostic]| ds.iter().map(|d| (d.code.clone(), d.line.unwrap())).collect::<Vec<_>>();
+        assert_eq!(codes(&change.appeared), vec![("AWAIT_IN_LOOP".to_string(), 5)]);
+        assert_eq!(codes(&change.resolved), vec![("HARDCODED_SECRET".to_string(), 4)]);
+        assert!(change.render().contains("\n  - wf.ts:4: error[HARDCODED_SECRET]"));
+    }
+
+    #[test]
+    fn test_dependencies_follow_local_imports() {
+        let dir = std::env::temp_dir().join(format!("workway-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(dir.join("lib/index.ts"), "export * from './format.js';\n").unwrap();
+        fs::write(dir.join("lib/format.ts"), "import { x } from '../missing';\n").unwrap();
+        let workflow = dir.join("wf.ts");
+        let content = "import { defineWorkflow } from '@workway/sdk';\nimport { format } from './lib';\n";
+        fs::write(&workflow, content).unwrap();
+
+        let dir = fs::canonicalize(&dir).unwrap();
+        let deps = dependencies(&dir.join("wf.ts"), content);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(deps, BTreeSet::from([dir.join("lib/index.ts"), dir.join("lib/format.ts")]));
+    }
+}
@@ -0,0 +1,426 @@
+//! Static subrequest and CPU budget estimation.
+//!
+//! Cloudflare Workers cap outbound calls at 50 subrequests per invocation.
+//! Workflows usually hit that limit by calling `fetch` or an integration
+//! client inside a loop over a list whose size depends on user data. This
+//! module finds every outbound call site in `execute`, works out which loops
+//! enclose it and whether those loops have a static bound, and turns that
+//! into a minimum subrequest estimate plus a bounded/unbounded verdict.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::patterns::*;
+use crate::source::{code_only, execute_body, line_at, matching_close, split_top_level};
+use crate::diagnostic::Diagnostic;
+use crate::scan::Scan;
+use crate::validator::WorkflowMetadata;
+
+/// Subrequests allowed per Workers invocation (see docs/WORKERS_RUNTIME_GUIDE.md)
+pub const SUBREQUEST_LIMIT: u32 = 50;
+
+/// Budget estimate attached to workflow metadata
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetEstimate {
+    /// Number of outbound call sites in `execute`
+    pub call_sites: u32,
+    /// Subrequests made on every run, counting data-driven loops once
+    pub min_subrequests: u32,
+    /// Call sites inside loops whose iteration count depends on data
+    pub unbounded_call_sites: u32,
+    /// Unbounded call sites fanned out concurrently via `Promise.all`
+    pub parallel_call_sites: u32,
+    pub subrequests: SubrequestBudget,
+    pub cpu: CpuBudget,
+}
+
+/// Whether the subrequests made per run have a static upper bound
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubrequestBudget {
+    #[default]
+    Bounded,
+    /// Some call sites sit inside loops over data
+    Unbounded,
+}
+
+/// Expected CPU time per run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CpuBudget {
+    #[default]
+    Low,
+    /// Heavy work (parsing, sorting, crypto) per item of a data loop
+    Moderate,
+    /// Nested loops over data
+    High,
+}
+
+/// A loop (or iteration callback) inside the execute body
+struct Loop {
+    span: Range<usize>,
+    /// Static iteration count, if one can be determined
+    bound: Option<u32>,
+    /// Whether the loop body contains a CPU-heavy operation
+    cpu_heavy: bool,
+    /// Whether the loop counts towards nesting depth (lookups like `.find`
+    /// and `.some` short-circuit and are not treated as full scans)
+    nests: bool,
+}
+
+/// Counts the top-level elements of an array literal body (`'a', 'b'` -> 2).
+fn count_elements(list: &str) -> u32 {
+    split_top_level(list).len() as u32
+}
+
+/// Static bound for a for/while loop given its header text.
+fn statement_bound(keyword: &str, header: &str) -> Option<u32> {
+    if keyword.starts_with("while") {
+        return None;
+    }
+    if let Some(caps) = FOR_COUNTER_BOUND.captures(header) {
+        let start: u32 = caps[1].parse().ok()?;
+        let end: u32 = caps[3].parse().ok()?;
+        let inclusive = &caps[2] == "<=";
+        // `i <= u32::MAX` never terminates as far as the estimate is concerned
+        return Some(end.checked_add(u32::from(inclusive))?.saturating_sub(start));
+    }
+    if let Some(caps) = FOR_OF_LITERAL.captures(header) {
+        return Some(count_elements(&caps[1]));
+    }
+    SLICE_BOUND
+        .captures(header)
+        .and_then(|caps| caps[1].parse().ok())
+}
+
+/// Static bound for an iteration callback given the receiver text before it.
+fn receiver_bound(receiver: &str) -> Option<u32> {
+    let receiver = receiver.trim_end();
+    if let Some(caps) = SLICE_BOUND.captures(receiver) {
+        return caps[1].parse().ok();
+    }
+
+    // Array literal receiver: ['a', 'b'].map(...)
+    let bytes = receiver.as_bytes();
+    if bytes.last() != Some(&b']') {
+        return None;
+    }
+    let mut depth = 0usize;
+    for i in (0..bytes.len()).rev() {
+        match bytes[i] {
+            b']' => depth += 1,
+            b'[' => {
+                depth -= 1;
+                if depth == 0 {
+                    let before = receiver[..i].trim_end();
+                    let is_index = before.chars().last().is_some_and(|c| {
+                        c.is_alphanumeric() || c == '_' || c == '$' || c == ')' || c == ']'
+                    });
+                    if is_index {
+                        return None;
+                    }
+                    return Some(count_elements(&receiver[i + 1..receiver.len() - 1]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Finds every loop and iteration callback within `body` (absolute offsets).
+fn find_loops(content: &str, body: &Range<usize>) -> Vec<Loop> {
+    let text = &content[body.clone()];
+    let mut loops = Vec::new();
+
+    // A match inside a string or template literal can close past the body;
+    // such loops are skipped
+    let close_in_body = |open: usize| matching_close(content, open).filter(|&close| close < body.end);
+
+    for m in LOOP_STATEMENT.find_iter(text) {
+        let open = body.start + m.end() - 1;
+        let Some(close) = close_in_body(open) else {
+            continue;
+        };
+        let header = &content[open + 1..close];
+
+        // Loop body: a braced block or a single statement
+        let after = &content[close + 1..body.end];
+        let trimmed = after.trim_start();
+        let start = body.end - trimmed.len();
+        let end = if trimmed.starts_with('{') {
+            matching_close(content, start).unwrap_or(body.end)
+        } else {
+            trimmed.find(';').map(|p| start + p).unwrap_or(body.end)
+        };
+
+        loops.push(Loop {
+            span: open..end,
+            bound: statement_bound(m.as_str(), header),
+            cpu_heavy: CPU_HEAVY_OP.is_match(&content[open..end]),
+            nests: true,
+        });
+    }
+
+    for m in DO_LOOP.find_iter(text) {
+        let open = body.start + m.end() - 1;
+        let Some(close) = close_in_body(open) else {
+            continue;
+        };
+        loops.push(Loop {
+            span: open..close,
+            bound: None,
+            cpu_heavy: CPU_HEAVY_OP.is_match(&content[open..close]),
+            nests: true,
+        });
+    }
+
+    for m in ITERATOR_CALL.find_iter(text) {
+        let open = body.start + m.end() - 1;
+        let Some(close) = close_in_body(open) else {
+            continue;
+        };
+        let receiver = &content[body.start..body.start + m.start()];
+        let method = m.as_str()[1..].trim_end_matches(|c: char| c == '(' || c.is_whitespace());
+        loops.push(Loop {
+            span: open..close,
+            bound: receiver_bound(receiver),
+            cpu_heavy: CPU_HEAVY_OP.is_match(&content[open..close]),
+            nests: !matches!(method, "find" | "some" | "every" | "filter"),
+        });
+    }
+
+    loops
+}
+
+/// Spans of every loop and iteration callback within `body`.
+pub(crate) fn loop_spans(content: &str, body: &Range<usize>) -> Vec<Range<usize>> {
+    find_loops(content, body).into_iter().map(|l| l.span).collect()
+}
+
+/// Estimates the subrequest and CPU budget of the `execute` function.
+///
+/// Returns the estimate plus the offsets of call sites inside unbounded loops.
+pub fn estimate_budget(content: &str) -> Option<(BudgetEstimate, Vec<usize>)> {
+    let code = code_only(content);
+    let body = execute_body(&code)?;
+    Some(estimate_in(&code, body))
+}
+
+/// [`estimate_budget`] over `content` with strings and comments blanked,
+/// given the execute body.
+fn estimate_in(content: &str, body: Range<usize>) -> (BudgetEstimate, Vec<usize>) {
+    let loops = find_loops(content, &body);
+    let text = &content[body.clone()];
+    let fan_outs: Vec<Range<usize>> = PROMISE_ALL
+        .find_iter(text)
+        .filter_map(|m| {
+            let open = body.start + m.end() - 1;
+            matching_close(content, open).map(|close| open..close)
+        })
+        .collect();
+
+    let mut estimate = BudgetEstimate::default();
+    let mut unbounded_sites = Vec::new();
+
+    for m in OUTBOUND_CALL.find_iter(text) {
+        let offset = body.start + m.start();
+        let enclosing = loops.iter().filter(|l| l.span.contains(&offset));
+
+        let mut multiplier = 1u32;
+        let mut unbounded = false;
+        for l in enclosing {
+            match l.bound {
+                Some(n) => multiplier = multiplier.saturating_mul(n),
+                None => unbounded = true,
+            }
+        }
+
+        estimate.call_sites += 1;
+        estimate.min_subrequests = estimate.min_subrequests.saturating_add(multiplier);
+        if unbounded {
+            estimate.unbounded_call_sites += 1;
+            unbounded_sites.push(offset);
+            if fan_outs.iter().any(|f| f.contains(&offset)) {
+                estimate.parallel_call_sites += 1;
+            }
+        }
+    }
+
+    // CPU: nested data-driven loops are O(n^2); heavy work per item adds up
+    let data_loops: Vec<&Loop> = loops
+        .iter()
+        .filter(|l| l.bound.is_none() && l.nests)
+        .collect();
+    let max_nesting = data_loops
+        .iter()
+        .map(|l| {
+            data_loops
+                .iter()
+                .filter(|outer| outer.span != l.span && outer.span.contains(&l.span.start))
+                .count()
+                + 1
+        })
+        .max()
+        .unwrap_or(0);
+    let heavy_in_data_loop = loops.iter().any(|l| l.bound.is_none() && l.cpu_heavy);
+
+    estimate.subrequests = if estimate.unbounded_call_sites > 0 {
+        SubrequestBudget::Unbounded
+    } else {
+        SubrequestBudget::Bounded
+    };
+    estimate.cpu = if max_nesting >= 2 {
+        CpuBudget::High
+    } else if heavy_in_data_loop {
+        CpuBudget::Moderate
+    } else {
+        CpuBudget::Low
+    };
+
+    (estimate, unbounded_sites)
+}
+
+pub(crate) fn validate_budget(
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
+    metadata: &mut WorkflowMetadata,
+) {
+    let Some(body) = scan.execute_body() else {
+        return;
+    };
+    let (estimate, unbounded_sites) = estimate_in(scan.code(), body);
+
+    if estimate.min_subrequests > SUBREQUEST_LIMIT {
+        diagnostics.push(
+            Diagnostic::warning(
+                "SUBREQUEST_LIMIT",
+                &format!(
+                    "Execute makes at least {} subrequests per run (limit is {})",
+                    estimate.min_subrequests, SUBREQUEST_LIMIT
+                ),
+            )
+            .with_suggestion("Split the work across runs or batch API calls"),
+        );
+    }
+
+    if let Some(&first) = unbounded_sites.first() {
+        diagnostics.push(
+            Diagnostic::warning(
+                "UNBOUNDED_SUBREQUESTS",
+                &format!(
+                    "{} outbound call(s) inside loops over data - subrequests grow with input size (limit is {})",
+                    unbounded_sites.len(),
+                    SUBREQUEST_LIMIT
+                ),
+            )
+            .with_line(line_at(scan.content(), first))
+            .with_suggestion("Cap the list with .slice(0, N) or use a batch API endpoint"),
+        );
+    }
+
+    if estimate.cpu == CpuBudget::High {
+        diagnostics.push(
+            Diagnostic::warning(
+                "HIGH_CPU_BUDGET",
+                "Nested loops over data may exceed the Workers CPU time limit",
+            )
+            .with_suggestion(
+                "Index one list by key (Map/Set) instead of scanning it for every item",
+            ),
+        );
+    }
+
+    metadata.budget = Some(estimate);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_loop_multiplies_call_sites() {
+        let content = r#"
+async execute({ integrations }) {
+    for (let i = 0; i < 3; i++) {
+        await fetch(`https://example.com/${i}`);
+    }
+    await integrations.slack.chat.postMessage({ text: 'done' });
+    return { success: true };
+}
+"#;
+        let (estimate, unbounded) = estimate_budget(content).unwrap();
+        assert_eq!(estimate.call_sites, 2);
+        assert_eq!(estimate.min_subrequests, 4);
+        assert_eq!(estimate.subrequests, SubrequestBudget::Bounded);
+        assert!(unbounded.is_empty());
+    }
+
+    #[test]
+    fn test_data_driven_loops_are_unbounded() {
+        let content = r#"
+async execute({ inputs, integrations }) {
+    const pages = await integrations.notion.databases.query({ id: inputs.db });
+    await Promise.all(pages.map((p) => integrations.slack.chat.postMessage({ text: p.title })));
+    for (const row of pages.slice(0, 5)) {
+        await fetch(row.url);
+    }
+    return { success: true };
+}
+"#;
+        let (estimate, unbounded) = estimate_budget(content).unwrap();
+        assert_eq!(estimate.min_subrequests, 7);
+        assert_eq!(estimate.unbounded_call_sites, 1);
+        assert_eq!(estimate.parallel_call_sites, 1);
+        assert_eq!(unbounded.len(), 1);
+        assert_eq!(estimate.subrequests, SubrequestBudget::Unbounded);
+    }
+
+    #[test]
+    fn test_nested_data_loops_are_high_cpu() {
+        let content = r#"
+async execute({ inputs }) {
+    for (const a of inputs.left) {
+        for (const b of inputs.right) {
+            if (a.id === b.id) matches++;
+        }
+    }
+    return { success: true };
+}
+"#;
+        let (estimate, _) = estimate_budget(content).unwrap();
+        assert_eq!(estimate.cpu, CpuBudget::High);
+    }
+
+    #[test]
+    fn test_calls_in_strings_and_comments_are_not_call_sites() {
+        let content = r#"
+async execute({ inputs }) {
+    for (const row of inputs.rows) {
+        // await fetch(row.url);
+        log(`retry with fetch(${row.url})`);
+    }
+    return { success: true };
+}
+"#;
+        let (estimate, unbounded) = estimate_budget(content).unwrap();
+        assert_eq!(estimate.call_sites, 0);
+        assert!(unbounded.is_empty());
+    }
+
+    #[test]
+    fn test_literal_elements_count_at_top_level() {
+        assert_eq!(count_elements(r#"'a,b', { x: 1, y: 2 }"#), 2);
+        assert_eq!(count_elements("'a', 'b',"), 2);
+        assert_eq!(count_elements(" "), 0);
+        assert_eq!(receiver_bound("['a,b', [1, 2], `c,${d}`]"), Some(3));
+    }
+
+    #[test]
+    fn test_inclusive_bound_at_u32_max_is_unbounded() {
+        assert_eq!(statement_bound("for", "let i = 0; i <= 4294967295; i++"), None);
+        assert_eq!(statement_bound("for", "let i = 0; i < 4294967295; i++"), Some(u32::MAX));
+    }
+}
@@ -0,0 +1,333 @@
+//! Checks across a collection of workflows.
+//!
+//! Some mistakes only show when the marketplace directory is looked at as a
+//! whole: a `supersededBy` pointing at a workflow that does not exist, is
+//! itself deprecated or points back, two workflows claiming the same id or
+//! name, or two live workflows doing the same job. Each workflow is known by
+//! its manifest id, its directory name (`meeting-intelligence/index.ts`) and
+//! `pathway.primaryPair.workflowId`, since `supersededBy` uses any of them.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use crate::diagnostic::{Diagnostic, TextEdit};
+use crate::inputs::edit_distance;
+use crate::manifest::{exported_metadata, extract_manifest, workflow_definition, WorkflowManifest};
+
+/// Manifest similarity from which two live workflows are reported as
+/// near-duplicates
+pub const NEAR_DUPLICATE_SIMILARITY: f64 = 0.8;
+
+/// Features a manifest needs before it is compared, so two bare workflows do
+/// not count as duplicates
+const MIN_FEATURES: usize = 4;
+
+struct Member<'a> {
+    path: &'a str,
+    content: &'a str,
+    manifest: WorkflowManifest,
+    /// The names `supersededBy` may use for this workflow
+    names: Vec<String>,
+}
+
+impl Member<'_> {
+    fn id(&self) -> String {
+        self.manifest.id.clone().unwrap_or_else(|| self.names[0].clone())
+    }
+}
+
+/// Directory name for `<dir>/index.ts`, else the file name without extension.
+fn path_id(path: &str) -> &str {
+    let mut parts = path.rsplit(['/', '\\']);
+    let file = parts.next().unwrap_or(path);
+    let stem = file.split('.').next().unwrap_or(file);
+    match parts.next() {
+        Some(dir) if stem == "index" && !dir.is_empty() => dir,
+        _ => stem,
+    }
+}
+
+/// Span of the string value of `key` in the definition or its metadata.
+fn value_span(content: &str, key: &str) -> Option<Range<usize>> {
+    let definition = workflow_definition(content)?;
+    let inline = definition.get("metadata").and_then(|m| m.property(key).cloned());
+    let exported = || exported_metadata(content).and_then(|m| m.property(key).cloned());
+    let property = definition.property(key).cloned().or(inline).or_else(exported)?;
+    let value = property.value.as_str()?;
+    let quote = property.offset + content[property.offset..].find(['\'', '"', '`'])?;
+    Some(quote + 1..quote + 1 + value.len())
+}
+
+/// Attaches the span of `key`'s value, when it can be found.
+fn at(diagnostic: Diagnostic, member: &Member, key: &str) -> Diagnostic {
+    match value_span(member.content, key) {
+        Some(span) => diagnostic.with_span(member.content, span),
+        None => diagnostic,
+    }
+}
+
+/// What a workflow connects and how it is configured, for similarity.
+fn features(manifest: &WorkflowManifest) -> BTreeSet<String> {
+    let mut features = BTreeSet::new();
+    for integration in &manifest.integrations {
+        features.insert(format!("integration:{}", integration.service.to_lowercase()));
+    }
+    for trigger in &manifest.triggers {
+        features.insert(format!("trigger:{}:{}", trigger.trigger_type, trigger.service.as_deref().unwrap_or("")));
+        features.extend(trigger.events.iter().map(|e| format!("event:{}", e)));
+    }
+    for input in &manifest.inputs {
+        features.insert(format!("input:{}", input.key.to_lowercase().replace(['_', '-'], "")));
+    }
+    let frame = manifest.pathway.as_ref().and_then(|p| p.get("outcomeFrame")).and_then(|f| f.as_str());
+    features.extend(frame.map(|f| format!("outcome:{}", f)));
+    features
+}
+
+fn similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Runs the collection checks over workflow files given as `(path, content)`.
+/// Returns each file's diagnostics, in the order the files were given; paths
+/// are only used in messages and for directory names.
+pub fn validate_collection(files: &[(&str, &str)]) -> Vec<Vec<Diagnostic>> {
+    let members: Vec<Member> = files
+        .iter()
+        .map(|&(path, content)| {
+            let manifest = extract_manifest(content);
+            let pair_id = manifest
+                .pathway
+                .as_ref()
+                .and_then(|p| p.pointer("/primaryPair/workflowId"))
+                .and_then(|id| id.as_str())
+                .map(str::to_string);
+            let mut names = vec![path_id(path).to_string()];
+            names.extend(manifest.id.clone());
+            names.extend(pair_id);
+            names.dedup();
+            Member { path, content, manifest, names }
+        })
+        .collect();
+    let mut diagnostics: Vec<Vec<Diagnostic>> = vec![Vec::new(); members.len()];
+    let resolve = |target: &str| members.iter().position(|m| m.names.iter().any(|n| n == target));
+
+    // supersededBy: the target exists, is live, and the chain ends
+    for (i, member) in members.iter().enumerate() {
+        let Some(target) = member.manifest.superseded_by.as_deref() else {
+            continue;
+        };
+        let Some(first) = resolve(target) else {
+            let closest = members
+                .iter()
+                .filter(|m| !m.manifest.deprecated)
+                .map(|m| m.id())
+                .map(|id| (edit_distance(target, &id), id))
+                .filter(|(distance, _)| *distance <= 2)
+                .min();
+            let mut diagnostic = at(
+                Diagnostic::error(
+                    "SUPERSEDED_BY_MISSING",
+                    &format!("supersededBy '{}' does not match any workflow in the collection", target),
+                ),
+                member,
+                "supersededBy",
+            );
+            if let Some((_, id)) = closest {
+                diagnostic = diagnostic.with_suggestion(&format!("Did you mean '{}'?", id));
+                if let Some(span) = value_span(member.content, "supersededBy") {
+                    diagnostic = diagnostic.with_fix(&format!("Point to '{}'", id), vec![TextEdit::replace(span, &id)]);
+                }
+            }
+            diagnostics[i].push(diagnostic);
+            continue;
+        };
+
+        let mut chain = vec![i];
+        let mut next = Some(first);
+        while let Some(current) = next {
+            if let Some(start) = chain.iter().position(|&c| c == current) {
+                if start == 0 {
+                    let cycle: Vec<String> = chain.iter().chain([&current]).map(|&c| members[c].id()).collect();
+                    diagnostics[i].push(at(
+                        Diagnostic::error(
+                            "SUPERSEDED_BY_CYCLE",
+                            &format!("supersededBy forms a cycle: {}", cycle.join(" → ")),
+                        )
+                        .with_suggestion("Point the chain at a workflow that is not deprecated"),
+                        member,
+                        "supersededBy",
+                    ));
+                }
+                // A cycle further on is reported on its own members
+                chain.clear();
+                break;
+            }
+            chain.push(current);
+            next = members[current].manifest.superseded_by.as_deref().and_then(resolve);
+        }
+        if chain.is_empty() || !members[first].manifest.deprecated {
+            continue;
+        }
+
+        let last = &members[chain[chain.len() - 1]];
+        let mut diagnostic = Diagnostic::warning(
+            "SUPERSEDED_BY_DEPRECATED",
+            &format!("supersededBy '{}' is itself deprecated", target),
+        );
+        if last.manifest.deprecated {
+            diagnostic = diagnostic.with_suggestion("Point to a workflow that is not deprecated");
+        } else {
+            let id = last.id();
+            diagnostic = diagnostic.with_suggestion(&format!("Point to '{}', which replaces it", id));
+            if let Some(span) = value_span(member.content, "supersededBy") {
+                diagnostic = diagnostic.with_fix(&format!("Point to '{}'", id), vec![TextEdit::replace(span, &id)]);
+            }
+        }
+        diagnostics[i].push(at(diagnostic, member, "supersededBy"));
+    }
+
+    // Ids must be unique; names should be
+    for (i, member) in members.iter().enumerate() {
+        let id = member.id();
+        let same_id: Vec<&str> = members
+            .iter()
+            .enumerate()
+            .filter(|&(j, other)| j != i && other.id() == id)
+            .map(|(_, other)| other.path)
+            .collect();
+        if !same_id.is_empty() {
+            diagnostics[i].push(at(
+                Diagnostic::error(
+                    "DUPLICATE_WORKFLOW_ID",
+                    &format!("Workflow id '{}' is also used by {}", id, same_id.join(", ")),
+                )
+                .with_suggestion("Give each workflow a unique metadata.id"),
+                member,
+                "id",
+            ));
+        }
+
+        let Some(name) = member.manifest.name.as_deref().map(str::trim).filter(|n| !n.is_empty()) else {
+            continue;
+        };
+        let same_name: Vec<&str> = members
+            .iter()
+            .enumerate()
+            .filter(|&(j, other)| j != i && other.manifest.name.as_deref().is_some_and(|n| n.trim().eq_ignore_ascii_case(name)))
+            .map(|(_, other)| other.path)
+            .collect();
+        if !same_name.is_empty() {
+            diagnostics[i].push(at(
+                Diagnostic::warning(
+                    "DUPLICATE_WORKFLOW_NAME",
+                    &format!("Workflow name '{}' is also used by {}", name, same_name.join(", ")),
+                )
+                .with_suggestion("Rename one of them, or deprecate one in favor of the other"),
+                member,
+                "name",
+            ));
+        }
+    }
+
+    // Live workflows that connect and configure the same things
+    let all_features: Vec<BTreeSet<String>> = members.iter().map(|m| features(&m.manifest)).collect();
+    for (i, member) in members.iter().enumerate() {
+        if member.manifest.deprecated || all_features[i].len() < MIN_FEATURES {
+            continue;
+        }
+        for (j, other) in members.iter().enumerate() {
+            if j == i || other.manifest.deprecated || all_features[j].len() < MIN_FEATURES {
+                continue;
+            }
+            let score = similarity(&all_features[i], &all_features[j]);
+            if score < NEAR_DUPLICATE_SIMILARITY {
+                continue;
+            }
+            diagnostics[i].push(at(
+                Diagnostic::info(
+                    "NEAR_DUPLICATE_WORKFLOW",
+                    &format!(
+                        "Workflow is {:.0}% similar to {} (integrations, triggers and inputs)",
+                        score * 100.0,
+                        other.path
+                    ),
+                )
+                .with_suggestion(&format!("Merge them, or deprecate one with supersededBy: '{}'", other.id())),
+                member,
+                "name",
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow(name: &str, extra: &str) -> String {
+        format!(
+            "import {{ defineWorkflow, webhook }} from '@workway/sdk';\n\
+             export default defineWorkflow({{\n  name: '{}',{}\n  integrations: [{{ service: 'zoom' }}, {{ service: 'notion' }}],\n  \
+             inputs: {{ notionDatabaseId: {{ type: 'text' }}, includeTranscript: {{ type: 'boolean' }} }},\n  \
+             trigger: webhook({{ service: 'zoom', event: 'recording.completed' }}),\n  \
+             async execute() {{ return {{ success: true }}; }},\n}});\n",
+            name, extra
+        )
+    }
+
+    fn codes(files: &[(&str, String)]) -> Vec<Vec<(String, String)>> {
+        let files: Vec<(&str, &str)> = files.iter().map(|(p, c)| (*p, c.as_str())).collect();
+        validate_collection(&files)
+            .into_iter()
+            .map(|ds| ds.into_iter().map(|d| (d.code, d.message)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_superseded_by_chains() {
+        let files = [
+            ("summarizer/index.ts", workflow("Summarizer", "\n  deprecated: true,\n  supersededBy: 'notes',")),
+            ("notes/index.ts", workflow("Notes", "\n  deprecated: true,\n  supersededBy: 'meeting-intelligence',")),
+            ("meeting-intelligence/index.ts", workflow("Meeting Intelligence", "")),
+            ("a/index.ts", workflow("A", "\n  deprecated: true,\n  supersededBy: 'b',")),
+            ("b/index.ts", workflow("B", "\n  deprecated: true,\n  supersededBy: 'a',")),
+            ("typo/index.ts", workflow("Typo", "\n  deprecated: true,\n  supersededBy: 'meeting-inteligence',")),
+        ];
+        let found = codes(&files);
+        assert_eq!(found[0], vec![("SUPERSEDED_BY_DEPRECATED".to_string(), "supersededBy 'notes' is itself deprecated".to_string())]);
+        assert!(found[1].is_empty());
+        assert_eq!(found[3][0].1, "supersededBy forms a cycle: a → b → a");
+        assert_eq!(found[4][0].1, "supersededBy forms a cycle: b → a → b");
+        assert_eq!(found[5][0].0, "SUPERSEDED_BY_MISSING");
+
+        let contents: Vec<(&str, &str)> = files.iter().map(|(p, c)| (*p, c.as_str())).collect();
+        let diagnostics = validate_collection(&contents);
+        let edit = &diagnostics[0][0].fixes[0].edits[0];
+        assert_eq!(&files[0].1[edit.span.start..edit.span.end], "notes");
+        assert_eq!(edit.replacement, "meeting-intelligence");
+        assert_eq!(diagnostics[5][0].fixes[0].edits[0].replacement, "meeting-intelligence");
+    }
+
+    #[test]
+    fn test_duplicates_across_the_collection() {
+        let files = [
+            ("zoom-notes/index.ts", workflow("Zoom Notes", "\n  metadata: { id: 'zoom-notes' },")),
+            ("zoom-notes-v2/index.ts", workflow("zoom notes", "\n  metadata: { id: 'zoom-notes' },")),
+            ("old/index.ts", workflow("Old", "\n  deprecated: true,\n  supersededBy: 'zoom-notes',")),
+        ];
+        let found = codes(&files);
+        let first: Vec<&str> = found[0].iter().map(|(code, _)| code.as_str()).collect();
+        assert_eq!(first, vec!["DUPLICATE_WORKFLOW_ID", "DUPLICATE_WORKFLOW_NAME", "NEAR_DUPLICATE_WORKFLOW"]);
+        assert_eq!(found[1][0].1, "Workflow id 'zoom-notes' is also used by zoom-notes/index.ts");
+        assert_eq!(found[0][2].1, "Workflow is 100% similar to zoom-notes-v2/index.ts (integrations, triggers and inputs)");
+        // Deprecated workflows are expected to resemble their replacement
+        assert!(found[2].is_empty());
+    }
+}
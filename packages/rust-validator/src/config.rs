@@ -0,0 +1,568 @@
+//! Project configuration (`.workway-validator.toml`).
+//!
+//! ```toml
+//! # Turn rules off, back on, or change their severity
+//! [rules]
+//! EMPTY_CATCH = "off"
+//! MISSING_PRICING = "error"
+//! AWAIT_IN_LOOP = false
+//!
+//! [thresholds]
+//! max_console_statements = 10
+//!
+//! [modules]
+//! blocked = ["lodash"]   # reported as INCOMPATIBLE_NPM_PACKAGE
+//! allowed = ["events"]   # removed from the built-in blocked lists
+//!
+//! [integrations]
+//! known = ["acme-crm"]
+//!
+//! # SDK release checked for deprecated APIs (default: from package.json)
+//! [sdk]
+//! version = "^1.0.0"
+//!
+//! # House rules (see the custom_rules module for the pattern syntax)
+//! [[custom_rules]]
+//! code = "DATE_NOW_IDEMPOTENCY_KEY"
+//! pattern = 'idempotencyKey\s*:[^,}\n]*Date\.now\(\)'
+//! message = "Date.now() is not a stable idempotency key"
+//!
+//! # Later overrides win; globs are relative to the config file
+//! [[overrides]]
+//! files = ["legacy/**"]
+//! rules = { MISSING_PRICING = "off" }
+//! thresholds = { max_console_statements = 20 }
+//! ```
+//!
+//! Natively the file is discovered by walking up from the validated file
+//! ([`discover_config`], or [`ConfigCache`] when validating many files).
+//! Under WASM the same structure is passed as a JS object to
+//! `validate_workflow_with_options`, or parsed once into a `WorkflowValidator`.
+
+use std::collections::BTreeMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+
+use globset::{GlobBuilder, GlobSetBuilder};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::codes;
+use crate::custom_rules::CustomRule;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::patterns::KNOWN_INTEGRATIONS;
+use crate::sdk_deprecations::range_floor;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sdk_deprecations::discover_sdk_version;
+
+/// File name looked for when discovering configuration
+pub const CONFIG_FILE_NAME: &str = ".workway-validator.toml";
+
+/// Console statements allowed before `EXCESSIVE_LOGGING` fires
+const DEFAULT_MAX_CONSOLE_STATEMENTS: usize = 3;
+
+/// How a rule is configured: `"off"` / `false`, `"on"` / `true` (default
+/// severity), or a severity name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RuleSettingRepr", into = "RuleSettingRepr")]
+pub enum RuleSetting {
+    Off,
+    On,
+    Level(Severity),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RuleSettingRepr {
+    Enabled(bool),
+    Name(String),
+}
+
+impl TryFrom<RuleSettingRepr> for RuleSetting {
+    type Error = String;
+
+    fn try_from(repr: RuleSettingRepr) -> Result<Self, Self::Error> {
+        match repr {
+            RuleSettingRepr::Enabled(true) => Ok(RuleSetting::On),
+            RuleSettingRepr::Enabled(false) => Ok(RuleSetting::Off),
+            RuleSettingRepr::Name(name) => match name.to_ascii_lowercase().as_str() {
+                "off" => Ok(RuleSetting::Off),
+                "on" => Ok(RuleSetting::On),
+                _ => Severity::parse(&name).map(RuleSetting::Level).ok_or_else(|| {
+                    format!("invalid rule setting '{}' (expected off, on, error, warning, info or hint)", name)
+                }),
+            },
+        }
+    }
+}
+
+impl From<RuleSetting> for RuleSettingRepr {
+    fn from(setting: RuleSetting) -> Self {
+        match setting {
+            RuleSetting::Off => RuleSettingRepr::Enabled(false),
+            RuleSetting::On => RuleSettingRepr::Enabled(true),
+            RuleSetting::Level(severity) => RuleSettingRepr::Name(severity.as_str().to_string()),
+        }
+    }
+}
+
+/// Reads a rules table with its codes uppercased, so `await_in_loop` and
+/// `AWAIT_IN_LOOP` name the same entry when overrides are merged.
+fn uppercase_rules<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, RuleSetting>, D::Error> {
+    let rules = BTreeMap::<String, RuleSetting>::deserialize(deserializer)?;
+    Ok(rules.into_iter().map(|(code, setting)| (code.to_ascii_uppercase(), setting)).collect())
+}
+
+/// Numeric rule thresholds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Thresholds {
+    pub max_console_statements: usize,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            max_console_statements: DEFAULT_MAX_CONSOLE_STATEMENTS,
+        }
+    }
+}
+
+/// Thresholds set by an override; unset values keep the base setting
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThresholdOverrides {
+    pub max_console_statements: Option<usize>,
+}
+
+/// Additions to and exemptions from the built-in blocked module lists
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModuleConfig {
+    pub blocked: Vec<String>,
+    pub allowed: Vec<String>,
+}
+
+/// Integrations accepted in addition to the built-in list
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IntegrationConfig {
+    pub known: Vec<String>,
+}
+
+/// The SDK release workflows are written against
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SdkConfig {
+    /// A version or dependency range (`1.1.0`, `^1.0.0`); unset means the
+    /// range in the nearest `package.json`, then the latest release
+    pub version: Option<String>,
+}
+
+/// Settings applied to files matching any of `files`
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Override {
+    pub files: Vec<String>,
+    #[serde(deserialize_with = "uppercase_rules")]
+    pub rules: BTreeMap<String, RuleSetting>,
+    pub thresholds: ThresholdOverrides,
+}
+
+/// Validator configuration
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidatorConfig {
+    /// Keyed by uppercase code
+    #[serde(deserialize_with = "uppercase_rules")]
+    pub rules: BTreeMap<String, RuleSetting>,
+    pub thresholds: Thresholds,
+    pub modules: ModuleConfig,
+    pub integrations: IntegrationConfig,
+    pub sdk: SdkConfig,
+    pub custom_rules: Vec<CustomRule>,
+    pub overrides: Vec<Override>,
+}
+
+/// Why a configuration could not be loaded
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    Io { path: String, message: String },
+    Parse(String),
+    UnknownRule(String),
+    InvalidCustomRule { code: String, message: String },
+    InvalidGlob { pattern: String, message: String },
+    InvalidSdkVersion(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, message } => write!(f, "cannot read {}: {}", path, message),
+            ConfigError::Parse(message) => write!(f, "invalid {}: {}", CONFIG_FILE_NAME, message),
+            ConfigError::UnknownRule(code) => write!(f, "unknown rule '{}' in {}", code, CONFIG_FILE_NAME),
+            ConfigError::InvalidCustomRule { code, message } => {
+                write!(f, "invalid custom rule '{}' in {}: {}", code, CONFIG_FILE_NAME, message)
+            }
+            ConfigError::InvalidGlob { pattern, message } => {
+                write!(f, "invalid glob '{}' in {}: {}", pattern, CONFIG_FILE_NAME, message)
+            }
+            ConfigError::InvalidSdkVersion(version) => {
+                write!(f, "invalid sdk.version '{}' in {} (expected a version such as 1.1.0)", version, CONFIG_FILE_NAME)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn build_glob(pattern: &str) -> Result<globset::Glob, ConfigError> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| ConfigError::InvalidGlob {
+            pattern: pattern.to_string(),
+            message: e.kind().to_string(),
+        })
+}
+
+/// Normalizes a file path for glob matching (`/` separators, no leading `./`).
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+impl ValidatorConfig {
+    /// Parses and checks a `.workway-validator.toml` document.
+    pub fn parse(source: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(source).map_err(|e| ConfigError::Parse(e.message().to_string()))?;
+        config.check()?;
+        Ok(config)
+    }
+
+    /// Rejects rule names that are not diagnostic codes, invalid custom
+    /// rules, malformed globs and SDK versions. Compiles custom rule patterns.
+    pub fn check(&self) -> Result<(), ConfigError> {
+        for (i, rule) in self.custom_rules.iter().enumerate() {
+            let invalid = |message: String| ConfigError::InvalidCustomRule {
+                code: rule.code.clone(),
+                message,
+            };
+            rule.check().map_err(invalid)?;
+            if codes::explain(&rule.code).is_some() {
+                return Err(invalid("code is already used by a built-in rule".to_string()));
+            }
+            if self.custom_rules[..i].iter().any(|other| other.code == rule.code) {
+                return Err(invalid("code is declared twice".to_string()));
+            }
+        }
+
+        let rule_names = self.rules.keys().chain(self.overrides.iter().flat_map(|o| o.rules.keys()));
+        for code in rule_names {
+            let is_custom = self.custom_rules.iter().any(|r| r.code.eq_ignore_ascii_case(code));
+            if codes::explain(code).is_none() && !is_custom {
+                return Err(ConfigError::UnknownRule(code.clone()));
+            }
+        }
+        for pattern in self.overrides.iter().flat_map(|o| &o.files) {
+            build_glob(pattern)?;
+        }
+        if let Some(version) = self.sdk.version.as_ref().filter(|v| range_floor(v).is_none()) {
+            return Err(ConfigError::InvalidSdkVersion(version.clone()));
+        }
+        Ok(())
+    }
+
+    /// Returns the configuration with every override matching `path` merged
+    /// in. Without a path, overrides are ignored.
+    pub fn for_path(&self, path: Option<&str>) -> ValidatorConfig {
+        let mut resolved = ValidatorConfig {
+            overrides: Vec::new(),
+            ..self.clone()
+        };
+        let Some(path) = path.map(normalize_path) else {
+            return resolved;
+        };
+
+        for over in &self.overrides {
+            let mut globs = GlobSetBuilder::new();
+            for glob in over.files.iter().filter_map(|pattern| build_glob(pattern).ok()) {
+                globs.add(glob);
+            }
+            let matches = globs.build().map(|set| set.is_match(&path)).unwrap_or(false);
+            if !matches {
+                continue;
+            }
+            resolved.rules.extend(over.rules.iter().map(|(code, setting)| (code.clone(), *setting)));
+            if let Some(max) = over.thresholds.max_console_statements {
+                resolved.thresholds.max_console_statements = max;
+            }
+        }
+        resolved
+    }
+
+    /// The configured setting for a rule, matched case-insensitively.
+    pub fn rule(&self, code: &str) -> Option<RuleSetting> {
+        self.rules.get(&code.to_ascii_uppercase()).copied()
+    }
+
+    /// Drops diagnostics whose rule is off and re-levels the others as
+    /// configured.
+    pub fn apply_rules(&self, diagnostics: &mut Vec<Diagnostic>) {
+        diagnostics.retain_mut(|d| match self.rule(&d.code) {
+            Some(RuleSetting::Off) => false,
+            Some(RuleSetting::Level(severity)) => {
+                d.severity = severity;
+                true
+            }
+            Some(RuleSetting::On) | None => true,
+        });
+    }
+
+    /// Whether `module` is exempted from the built-in blocked lists
+    pub fn is_allowed_module(&self, module: &str) -> bool {
+        self.modules.allowed.iter().any(|m| m == module)
+    }
+
+    /// Whether `integration` is accepted (built-in or configured)
+    pub fn is_known_integration(&self, integration: &str) -> bool {
+        KNOWN_INTEGRATIONS.contains(&integration)
+            || self.integrations.known.iter().any(|k| k.eq_ignore_ascii_case(integration))
+    }
+}
+
+/// A configuration file read from disk
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub path: PathBuf,
+    pub config: ValidatorConfig,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl LoadedConfig {
+    /// `file` relative to the directory holding the config, for override globs
+    pub fn relative_path(&self, file: &Path) -> Option<String> {
+        let root = self.path.parent()?;
+        let file = std::fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        let relative = file.strip_prefix(root).ok()?;
+        Some(relative.to_string_lossy().into_owned())
+    }
+}
+
+/// The directory a lookup starts from: `start` itself or its parent.
+#[cfg(not(target_arch = "wasm32"))]
+fn start_dir(start: &Path) -> Option<PathBuf> {
+    let start = std::fs::canonicalize(start).unwrap_or_else(|_| start.to_path_buf());
+    if start.is_dir() {
+        Some(start)
+    } else {
+        start.parent().map(Path::to_path_buf)
+    }
+}
+
+/// The nearest `.workway-validator.toml` in `dir` or any of its ancestors.
+#[cfg(not(target_arch = "wasm32"))]
+fn find_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors().map(|d| d.join(CONFIG_FILE_NAME)).find(|candidate| candidate.is_file())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_config(path: PathBuf) -> Result<LoadedConfig, ConfigError> {
+    let source = std::fs::read_to_string(&path).map_err(|e| ConfigError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })?;
+    let config = ValidatorConfig::parse(&source)?;
+    Ok(LoadedConfig { path, config })
+}
+
+/// Finds the nearest `.workway-validator.toml` in `start` (a file or
+/// directory) or any of its ancestors.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn discover_config(start: &Path) -> Result<Option<LoadedConfig>, ConfigError> {
+    start_dir(start).and_then(|dir| find_config(&dir)).map(load_config).transpose()
+}
+
+/// Configurations and SDK versions discovered for files, cached by
+/// directory. Validating many files (a CLI run, an editor session) reads and
+/// parses each config file once, so custom rule patterns are compiled once.
+/// Call [`ConfigCache::clear`] when a config file or `package.json` changes.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default)]
+pub struct ConfigCache {
+    /// Nearest config file of each directory looked up
+    config_paths: HashMap<PathBuf, Option<PathBuf>>,
+    /// Config files by path
+    configs: HashMap<PathBuf, Result<Arc<LoadedConfig>, ConfigError>>,
+    /// SDK range of each directory looked up
+    sdk_versions: HashMap<PathBuf, Option<String>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ConfigCache {
+    /// The nearest config file of `file`, as [`discover_config`] finds it.
+    pub fn discover(&mut self, file: &Path) -> Result<Option<Arc<LoadedConfig>>, ConfigError> {
+        let Some(dir) = start_dir(file) else {
+            return Ok(None);
+        };
+        let path = self.config_paths.entry(dir).or_insert_with_key(|dir| find_config(dir));
+        let Some(path) = path.clone() else {
+            return Ok(None);
+        };
+        self.configs
+            .entry(path)
+            .or_insert_with_key(|path| load_config(path.clone()).map(Arc::new))
+            .clone()
+            .map(Some)
+    }
+
+    /// The SDK range from the nearest `package.json` of `file`, as
+    /// [`discover_sdk_version`] reads it.
+    pub fn sdk_version(&mut self, file: &Path) -> Option<String> {
+        let dir = start_dir(file)?;
+        self.sdk_versions
+            .entry(dir)
+            .or_insert_with_key(|dir| discover_sdk_version(dir))
+            .clone()
+    }
+
+    /// The configuration for `file` and the file's path relative to it, for
+    /// override globs: the nearest config file (or the defaults), with the
+    /// SDK range from `package.json` when the config sets none.
+    pub fn config_for(&mut self, file: &Path) -> Result<(ValidatorConfig, Option<String>), ConfigError> {
+        let loaded = self.discover(file)?;
+        let relative = loaded.as_ref().and_then(|l| l.relative_path(file));
+        let mut config = loaded.map(|l| l.config.clone()).unwrap_or_default();
+        if config.sdk.version.is_none() {
+            config.sdk.version = self.sdk_version(file);
+        }
+        Ok((config, relative))
+    }
+
+    /// Forgets everything read, so the next lookups see the files on disk.
+    pub fn clear(&mut self) {
+        self.config_paths.clear();
+        self.configs.clear();
+        self.sdk_versions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[rules]
+EMPTY_CATCH = "off"
+MISSING_PRICING = "error"
+
+[thresholds]
+max_console_statements = 10
+
+[[overrides]]
+files = ["legacy/**"]
+rules = { EMPTY_CATCH = true }
+thresholds = { max_console_statements = 20 }
+"#;
+
+    #[test]
+    fn test_overrides_apply_by_path() {
+        let config = ValidatorConfig::parse(CONFIG).unwrap();
+        assert_eq!(config.rule("empty_catch"), Some(RuleSetting::Off));
+        assert_eq!(config.rule("MISSING_PRICING"), Some(RuleSetting::Level(Severity::Error)));
+
+        let legacy = config.for_path(Some("./legacy/old/index.ts"));
+        assert_eq!(legacy.rule("EMPTY_CATCH"), Some(RuleSetting::On));
+        assert_eq!(legacy.thresholds.max_console_statements, 20);
+
+        let current = config.for_path(Some("workflows/new/index.ts"));
+        assert_eq!(current.rule("EMPTY_CATCH"), Some(RuleSetting::Off));
+        assert_eq!(current.thresholds.max_console_statements, 10);
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        assert!(matches!(
+            ValidatorConfig::parse("[rules]\nNOT_A_RULE = \"off\""),
+            Err(ConfigError::UnknownRule(_))
+        ));
+        assert!(matches!(
+            ValidatorConfig::parse("[rules]\nEMPTY_CATCH = \"loud\""),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            ValidatorConfig::parse("[[overrides]]\nfiles = [\"a/[\"]"),
+            Err(ConfigError::InvalidGlob { .. })
+        ));
+        assert!(matches!(
+            ValidatorConfig::parse("[sdk]\nversion = \"latest\""),
+            Err(ConfigError::InvalidSdkVersion(_))
+        ));
+        assert!(matches!(
+            ValidatorConfig::parse("[[custom_rules]]\ncode = \"EMPTY_CATCH\"\ntoken = \"x\"\nmessage = \"m\""),
+            Err(ConfigError::InvalidCustomRule { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rules_can_configure_custom_codes() {
+        let source = r#"
+[[custom_rules]]
+code = "NO_DATE_NOW"
+token = "Date.now()"
+message = "Use the trigger timestamp"
+
+[[overrides]]
+files = ["legacy/**"]
+rules = { NO_DATE_NOW = "off" }
+"#;
+        let config = ValidatorConfig::parse(source).unwrap();
+        assert_eq!(config.for_path(Some("legacy/a.ts")).rule("NO_DATE_NOW"), Some(RuleSetting::Off));
+        assert_eq!(config.for_path(Some("a.ts")).rule("NO_DATE_NOW"), None);
+    }
+
+    #[test]
+    fn test_override_rules_replace_base_rules_in_any_case() {
+        let source = r#"
+[rules]
+AWAIT_IN_LOOP = "off"
+
+[[overrides]]
+files = ["legacy/**"]
+rules = { await_in_loop = "error" }
+"#;
+        let config = ValidatorConfig::parse(source).unwrap();
+        let legacy = config.for_path(Some("legacy/a.ts"));
+        assert_eq!(legacy.rules.len(), 1);
+        assert_eq!(legacy.rule("AWAIT_IN_LOOP"), Some(RuleSetting::Level(Severity::Error)));
+    }
+
+    #[test]
+    fn test_config_cache_reads_each_file_once() {
+        let dir = std::env::temp_dir().join(format!("workway-config-cache-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("legacy")).unwrap();
+        std::fs::write(dir.join(CONFIG_FILE_NAME), "[rules]\nEMPTY_CATCH = \"off\"\n").unwrap();
+        std::fs::write(dir.join("package.json"), r#"{ "dependencies": { "@workwayco/sdk": "^1.2.0" } }"#).unwrap();
+
+        let mut cache = ConfigCache::default();
+        let first = cache.discover(&dir.join("a.ts")).unwrap().unwrap();
+        let second = cache.discover(&dir.join("legacy/b.ts")).unwrap().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        let (config, relative) = cache.config_for(&dir.join("legacy/b.ts")).unwrap();
+        assert_eq!(relative.as_deref(), Some(Path::new("legacy").join("b.ts").to_str().unwrap()));
+        assert_eq!(config.sdk.version.as_deref(), Some("^1.2.0"));
+
+        // Edits are seen once the cache is cleared
+        std::fs::write(dir.join(CONFIG_FILE_NAME), "[rules]\nNOT_A_RULE = \"off\"\n").unwrap();
+        assert!(cache.discover(&dir.join("a.ts")).unwrap().is_some());
+        cache.clear();
+        let error = cache.discover(&dir.join("a.ts"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(error, Err(ConfigError::UnknownRule(_))));
+    }
+}
@@ -0,0 +1,302 @@
+//! User-defined pattern rules.
+//!
+//! House rules are declared in `.workway-validator.toml`:
+//!
+//! ```toml
+//! [[custom_rules]]
+//! code = "NOTION_PAGE_WITHOUT_PARENT"
+//! severity = "error"
+//! token = "notion.pages.create("
+//! unless = '\bparent\s*:'
+//! message = "notion.pages.create must specify a parent"
+//! suggestion = "Pass parent: { database_id } or parent: { page_id }"
+//!
+//! [[custom_rules]]
+//! code = "DATE_NOW_IDEMPOTENCY_KEY"
+//! pattern = 'idempotencyKey\s*:[^,}\n]*Date\.now\(\)'
+//! scope = "execute"
+//! message = "Date.now() is not a stable idempotency key"
+//! ```
+//!
+//! A rule matches either a regular expression (`pattern`) or a token pattern
+//! (`token`). Token patterns ignore whitespace between tokens, match either
+//! quote style for string literals, and support two wildcards: `$NAME`
+//! matches an identifier or member chain (`user.email`) and `...` matches any
+//! text up to the end of the statement. When a match ends with an opening
+//! bracket, `unless` is checked against everything up to the matching close,
+//! so "call without argument X" rules can look at the whole call.
+//!
+//! Patterns are compiled on first use and the compiled form is shared by
+//! every clone of the configuration.
+
+use std::ops::Range;
+
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::budget::loop_spans;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::scan::Scan;
+use crate::source::matching_close;
+
+/// Category reported for custom rule diagnostics
+pub const CUSTOM_CATEGORY: &str = "custom";
+
+/// Where a custom rule applies
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleScope {
+    /// Anywhere in the file
+    #[default]
+    File,
+    /// Inside the `execute` function
+    Execute,
+    /// Inside a loop or iteration callback in `execute`
+    Loop,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    pattern: Regex,
+    unless: Option<Regex>,
+}
+
+/// A user-defined pattern rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRule {
+    pub code: String,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Regular expression to match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Token pattern to match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Regular expression that suppresses a match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unless: Option<String>,
+    #[serde(default)]
+    pub scope: RuleScope,
+    #[serde(skip)]
+    compiled: OnceCell<Result<CompiledRule, String>>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Warning
+}
+
+/// Identifier or member chain, used for `$NAME` in token patterns
+const METAVARIABLE: &str = r"[A-Za-z_$][\w$]*(?:\s*\??\.\s*[A-Za-z_$][\w$]*)*";
+
+/// Translates a token pattern into a regular expression.
+fn token_regex(token: &str) -> Result<String, String> {
+    let chars: Vec<char> = token.chars().collect();
+    let mut parts: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if chars[i..].starts_with(&['.', '.', '.']) {
+            parts.push(r"[^;]*?".to_string());
+            i += 3;
+        } else if c == '$' && chars.get(i + 1).is_some_and(|n| n.is_ascii_alphabetic()) {
+            i += 1;
+            while chars.get(i).is_some_and(|n| n.is_ascii_alphanumeric() || *n == '_') {
+                i += 1;
+            }
+            parts.push(METAVARIABLE.to_string());
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let start = i;
+            while chars.get(i).is_some_and(|n| n.is_alphanumeric() || *n == '_' || *n == '$') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            parts.push(format!(r"\b{}\b", regex::escape(&word)));
+        } else if matches!(c, '\'' | '"' | '`') {
+            let close = chars[i + 1..]
+                .iter()
+                .position(|n| *n == c)
+                .ok_or_else(|| format!("unterminated string in token pattern '{}'", token))?;
+            let text: String = chars[i + 1..i + 1 + close].iter().collect();
+            parts.push(format!(r#"['"`]{}['"`]"#, regex::escape(&text)));
+            i += close + 2;
+        } else {
+            parts.push(regex::escape(&c.to_string()));
+            i += 1;
+        }
+    }
+
+    if parts.is_empty() {
+        return Err("empty token pattern".to_string());
+    }
+    Ok(parts.join(r"\s*"))
+}
+
+impl CustomRule {
+    fn compile(&self) -> Result<CompiledRule, String> {
+        let source = match (&self.pattern, &self.token) {
+            (Some(pattern), None) => pattern.clone(),
+            (None, Some(token)) => token_regex(token)?,
+            _ => return Err("set exactly one of `pattern` or `token`".to_string()),
+        };
+        let pattern = Regex::new(&source).map_err(|e| e.to_string())?;
+        let unless = self
+            .unless
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        Ok(CompiledRule { pattern, unless })
+    }
+
+    fn compiled(&self) -> Result<&CompiledRule, &str> {
+        self.compiled
+            .get_or_init(|| self.compile())
+            .as_ref()
+            .map_err(String::as_str)
+    }
+
+    /// Compiles the rule's patterns, returning the first error.
+    pub fn check(&self) -> Result<(), String> {
+        let valid_code = self.code.starts_with(|c: char| c.is_ascii_uppercase())
+            && self.code.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if !valid_code {
+            return Err("codes must be UPPER_SNAKE_CASE".to_string());
+        }
+        self.compiled().map(|_| ()).map_err(str::to_string)
+    }
+}
+
+/// The text a match covers, extended to the matching close when the match
+/// ends with an opening bracket.
+fn match_extent(content: &str, range: Range<usize>) -> Range<usize> {
+    let opens_bracket = content[range.clone()].ends_with(['(', '{', '[']);
+    match opens_bracket.then(|| matching_close(content, range.end - 1)).flatten() {
+        Some(close) => range.start..close + 1,
+        None => range,
+    }
+}
+
+pub(crate) fn validate_custom_rules(
+    scan: &Scan,
+    rules: &[CustomRule],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if rules.is_empty() {
+        return;
+    }
+
+    let content = scan.content();
+    let execute = scan.execute_body();
+    let loops = match &execute {
+        Some(body) if rules.iter().any(|r| r.scope == RuleScope::Loop) => loop_spans(scan.code(), body),
+        _ => Vec::new(),
+    };
+
+    for rule in rules {
+        // Invalid rules are rejected when the configuration is checked
+        let Ok(compiled) = rule.compiled() else {
+            continue;
+        };
+
+        for m in compiled.pattern.find_iter(content) {
+            let in_scope = match rule.scope {
+                RuleScope::File => true,
+                RuleScope::Execute => execute.as_ref().is_some_and(|body| body.contains(&m.start())),
+                RuleScope::Loop => loops.iter().any(|l| l.contains(&m.start())),
+            };
+            if !in_scope {
+                continue;
+            }
+            if let Some(unless) = &compiled.unless {
+                if unless.is_match(&content[match_extent(content, m.range())]) {
+                    continue;
+                }
+            }
+
+            let mut diagnostic = Diagnostic::new(rule.severity, &rule.code, &rule.message).with_span(content, m.range());
+            diagnostic.category = Some(CUSTOM_CATEGORY.to_string());
+            if let Some(suggestion) = &rule.suggestion {
+                diagnostic = diagnostic.with_suggestion(suggestion);
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(toml_source: &str) -> CustomRule {
+        toml::from_str(toml_source).unwrap()
+    }
+
+    #[test]
+    fn test_token_pattern_with_unless() {
+        let content = r#"
+export default defineWorkflow({
+    async execute({ integrations }) {
+        await integrations.notion.pages.create({ parent: { database_id: db }, properties });
+        await integrations.notion.pages . create({
+            properties: { title: "x" },
+        });
+        return { success: true };
+    }
+});
+"#;
+        let rule = rule(
+            r#"
+code = "NOTION_PAGE_WITHOUT_PARENT"
+token = "notion.pages.create("
+unless = '\bparent\s*:'
+message = "notion.pages.create must specify a parent"
+"#,
+        );
+        assert!(rule.check().is_ok());
+
+        let mut diagnostics = Vec::new();
+        validate_custom_rules(&Scan::new(content), &[rule], &mut diagnostics);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(5));
+        assert_eq!(diagnostics[0].category.as_deref(), Some(CUSTOM_CATEGORY));
+    }
+
+    #[test]
+    fn test_loop_scope_and_metavariables() {
+        let content = r#"
+const stamp = Date.now();
+export default defineWorkflow({
+    async execute({ inputs }) {
+        for (const item of inputs.items) {
+            await send({ idempotencyKey: `${item.id}-${Date.now()}` });
+        }
+        log(user.email, Date.now());
+        return { success: true };
+    }
+});
+"#;
+        let in_loop = rule("code = \"DATE_NOW_IN_LOOP\"\ntoken = \"Date.now()\"\nscope = \"loop\"\nmessage = \"m\"");
+        let logged = rule("code = \"LOGGED_EMAIL\"\ntoken = \"log($OBJ.email, ...)\"\nmessage = \"m\"");
+        let mut diagnostics = Vec::new();
+        validate_custom_rules(&Scan::new(content), &[in_loop, logged], &mut diagnostics);
+
+        let lines: Vec<_> = diagnostics.iter().map(|d| (d.code.as_str(), d.line.unwrap())).collect();
+        assert_eq!(lines, vec![("DATE_NOW_IN_LOOP", 6), ("LOGGED_EMAIL", 8)]);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(rule("code = \"lower\"\ntoken = \"x\"\nmessage = \"m\"").check().is_err());
+        assert!(rule("code = \"BOTH\"\ntoken = \"x\"\npattern = \"x\"\nmessage = \"m\"").check().is_err());
+        assert!(rule("code = \"BAD_REGEX\"\npattern = \"(\"\nmessage = \"m\"").check().is_err());
+    }
+}
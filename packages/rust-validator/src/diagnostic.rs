@@ -0,0 +1,217 @@
+//! Validation diagnostics.
+//!
+//! Every finding is a [`Diagnostic`] with a [`Severity`]. Serialized, the
+//! severity is the `type` field, so errors and warnings keep the shape the
+//! TypeScript `ValidationError` / `ValidationWarning` interfaces expect.
+
+use std::fmt;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::codes;
+use crate::source::line_at;
+
+/// How serious a diagnostic is. Only errors make a workflow invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Hint => "hint",
+        }
+    }
+
+    /// Parses a severity name (case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "error" => Some(Severity::Error),
+            "warning" | "warn" => Some(Severity::Warning),
+            "info" => Some(Severity::Info),
+            "hint" => Some(Severity::Hint),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Byte range in the validated source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Self { start: range.start, end: range.end }
+    }
+}
+
+/// A secondary location that helps explain a diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelatedLocation {
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+}
+
+/// A text replacement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn replace(span: Range<usize>, replacement: &str) -> Self {
+        Self { span: span.into(), replacement: replacement.to_string() }
+    }
+
+    pub fn insert(offset: usize, text: &str) -> Self {
+        Self::replace(offset..offset, text)
+    }
+}
+
+/// A mechanical fix for a diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fix {
+    pub description: String,
+    pub edits: Vec<TextEdit>,
+}
+
+/// A validation finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    #[serde(rename = "type")]
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedLocation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic; the category is filled in from the code catalog.
+    pub fn new(severity: Severity, code: &str, message: &str) -> Self {
+        Self {
+            severity,
+            code: code.to_string(),
+            message: message.to_string(),
+            line: None,
+            suggestion: None,
+            category: codes::explain(code).map(|c| c.category.to_string()),
+            span: None,
+            related: Vec::new(),
+            fixes: Vec::new(),
+        }
+    }
+
+    pub fn error(code: &str, message: &str) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: &str, message: &str) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    pub fn info(code: &str, message: &str) -> Self {
+        Self::new(Severity::Info, code, message)
+    }
+
+    pub fn hint(code: &str, message: &str) -> Self {
+        Self::new(Severity::Hint, code, message)
+    }
+
+    pub fn with_suggestion(mut self, suggestion: &str) -> Self {
+        self.suggestion = Some(suggestion.to_string());
+        self
+    }
+
+    pub fn with_line(mut self, line: u32) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Attaches a source span and derives the line from it.
+    pub fn with_span(mut self, content: &str, span: Range<usize>) -> Self {
+        self.line = Some(line_at(content, span.start));
+        self.span = Some(span.into());
+        self
+    }
+
+    pub fn with_related(mut self, content: &str, span: Range<usize>, message: &str) -> Self {
+        self.related.push(RelatedLocation {
+            message: message.to_string(),
+            line: Some(line_at(content, span.start)),
+            span: Some(span.into()),
+        });
+        self
+    }
+
+    pub fn with_fix(mut self, description: &str, edits: Vec<TextEdit>) -> Self {
+        self.fixes.push(Fix { description: description.to_string(), edits });
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::ValidationResult;
+
+    #[test]
+    fn test_result_keeps_errors_and_warnings_arrays() {
+        let result = ValidationResult::new(
+            vec![
+                Diagnostic::warning("EXCESSIVE_LOGGING", "Too many console statements"),
+                Diagnostic::error("MISSING_SDK_IMPORT", "Missing SDK import").with_line(1),
+                Diagnostic::hint("EMPTY_CATCH", "Empty catch block"),
+            ],
+            None,
+        );
+        assert!(!result.valid);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["errors"][0]["type"], "error");
+        assert_eq!(json["errors"][0]["line"], 1);
+        assert_eq!(json["errors"][0]["category"], "imports");
+        assert_eq!(json["warnings"][0]["code"], "EXCESSIVE_LOGGING");
+        assert_eq!(json["info"][0]["type"], "hint");
+        assert!(json["errors"][0].get("fixes").is_none());
+
+        let back: ValidationResult = serde_json::from_value(json).unwrap();
+        assert_eq!(back.diagnostics.len(), 3);
+    }
+}
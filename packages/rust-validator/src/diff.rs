@@ -0,0 +1,631 @@
+//! Breaking-change detection between two versions of a workflow.
+//!
+//! Publishing a new version updates every installation. Some changes are
+//! safe (a new optional input), others break installed users until they act:
+//! a removed or newly required input, a new integration or scope to consent
+//! to, a different trigger, a higher price. This module compares the
+//! manifests of two versions, classifies each change, and checks that the
+//! `version` bump says as much.
+//!
+//! Change types and severities follow the SDK's `breaking-change-detector.ts`
+//! so both report changes the same way.
+
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::manifest::{extract_manifest, InputManifest, TriggerManifest, WorkflowManifest};
+
+/// What changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeType {
+    ConfigFieldRemoved,
+    ConfigFieldAdded,
+    ConfigFieldTypeChanged,
+    ConfigFieldRequiredAdded,
+    OauthProviderAdded,
+    OauthProviderRemoved,
+    OauthScopeAdded,
+    OauthScopeRemoved,
+    TriggerTypeChanged,
+    TriggerConfigChanged,
+    PricingChanged,
+}
+
+/// How a change affects installed users
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeSeverity {
+    /// Installations stop working or need the user to act
+    Breaking,
+    /// Installations keep working but behave differently
+    Warning,
+    Info,
+}
+
+/// A change between two versions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedChange {
+    #[serde(rename = "type")]
+    pub change_type: ChangeType,
+    pub severity: ChangeSeverity,
+    /// Input key, integration or pricing field the change is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migration_hint: Option<String>,
+}
+
+impl DetectedChange {
+    fn new(change_type: ChangeType, severity: ChangeSeverity, field: &str, message: String) -> Self {
+        Self {
+            change_type,
+            severity,
+            field: Some(field.to_string()),
+            message,
+            old_value: None,
+            new_value: None,
+            migration_hint: None,
+        }
+    }
+
+    fn values(mut self, old: Option<Value>, new: Option<Value>) -> Self {
+        self.old_value = old;
+        self.new_value = new;
+        self
+    }
+
+    fn hint(mut self, hint: &str) -> Self {
+        self.migration_hint = Some(hint.to_string());
+        self
+    }
+}
+
+/// Semver component a release increments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        })
+    }
+}
+
+/// Whether the `version` change matches the detected changes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionCheck {
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    /// Smallest bump the changes call for
+    pub required: Bump,
+    /// Bump between the two versions, if the new one is higher
+    pub actual: Option<Bump>,
+    pub ok: bool,
+    pub message: String,
+}
+
+/// Changes between two versions of a workflow
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowDiff {
+    pub has_breaking_changes: bool,
+    pub has_warnings: bool,
+    /// Breaking changes first, then warnings, then informational changes
+    pub changes: Vec<DetectedChange>,
+    pub version: VersionCheck,
+    pub summary: String,
+}
+
+impl WorkflowDiff {
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &DetectedChange> {
+        self.changes.iter().filter(|c| c.severity == ChangeSeverity::Breaking)
+    }
+}
+
+fn diff_inputs(old: &[InputManifest], new: &[InputManifest], changes: &mut Vec<DetectedChange>) {
+    use ChangeSeverity::*;
+    use ChangeType::*;
+
+    for input in old {
+        if !new.iter().any(|i| i.key == input.key) {
+            changes.push(
+                DetectedChange::new(ConfigFieldRemoved, Breaking, &input.key, format!("Input \"{}\" was removed", input.key))
+                    .values(Some(input.schema.clone()), None)
+                    .hint("Users with this input configured will need to reconfigure their installation"),
+            );
+        }
+    }
+
+    for input in new {
+        let key = &input.key;
+        let needs_value = input.required && input.default.is_none();
+        let Some(previous) = old.iter().find(|i| i.key == input.key) else {
+            changes.push(if needs_value {
+                DetectedChange::new(
+                    ConfigFieldRequiredAdded,
+                    Breaking,
+                    key,
+                    format!("New required input \"{}\" added without a default", key),
+                )
+                .values(None, Some(input.schema.clone()))
+                .hint("Add a default value or make the input optional to avoid breaking existing installations")
+            } else {
+                DetectedChange::new(ConfigFieldAdded, Info, key, format!("New input \"{}\" added", key))
+                    .values(None, Some(input.schema.clone()))
+            });
+            continue;
+        };
+
+        if previous.input_type != input.input_type {
+            changes.push(
+                DetectedChange::new(
+                    ConfigFieldTypeChanged,
+                    Breaking,
+                    key,
+                    format!(
+                        "Input \"{}\" type changed from \"{}\" to \"{}\"",
+                        key,
+                        previous.input_type.as_deref().unwrap_or("unknown"),
+                        input.input_type.as_deref().unwrap_or("unknown")
+                    ),
+                )
+                .values(
+                    previous.input_type.clone().map(Value::String),
+                    input.input_type.clone().map(Value::String),
+                )
+                .hint("Existing configurations may not be compatible with the new type"),
+            );
+        }
+
+        if !previous.required && needs_value {
+            changes.push(
+                DetectedChange::new(
+                    ConfigFieldRequiredAdded,
+                    Breaking,
+                    key,
+                    format!("Input \"{}\" became required without a default value", key),
+                )
+                .values(Some(Value::Bool(false)), Some(Value::Bool(true)))
+                .hint("Provide a default value or existing installations may fail"),
+            );
+        }
+    }
+}
+
+fn diff_integrations(old: &WorkflowManifest, new: &WorkflowManifest, changes: &mut Vec<DetectedChange>) {
+    use ChangeSeverity::*;
+    use ChangeType::*;
+
+    for integration in &new.integrations {
+        let service = &integration.service;
+        let Some(previous) = old.integrations.iter().find(|i| &i.service == service) else {
+            changes.push(if integration.optional {
+                DetectedChange::new(
+                    OauthProviderAdded,
+                    Info,
+                    service,
+                    format!("Optional integration \"{}\" added", service),
+                )
+            } else {
+                DetectedChange::new(OauthProviderAdded, Breaking, service, format!("New integration \"{}\" required", service))
+                    .hint(&format!(
+                        "Users will need to connect their {} account before the workflow can run",
+                        service
+                    ))
+            });
+            continue;
+        };
+
+        let added: Vec<&String> = integration.scopes.iter().filter(|s| !previous.scopes.contains(s)).collect();
+        if !added.is_empty() {
+            let list = added.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ");
+            let severity = if integration.optional { Warning } else { Breaking };
+            changes.push(
+                DetectedChange::new(OauthScopeAdded, severity, service, format!("\"{}\" requests new scopes: {}", service, list))
+                    .values(Some(serde_json::json!(previous.scopes)), Some(serde_json::json!(integration.scopes)))
+                    .hint(&format!("Users will need to reconnect {} to grant the new scopes", service)),
+            );
+        }
+
+        let removed: Vec<&String> = previous.scopes.iter().filter(|s| !integration.scopes.contains(s)).collect();
+        if !removed.is_empty() {
+            let list = removed.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ");
+            changes.push(
+                DetectedChange::new(OauthScopeRemoved, Info, service, format!("\"{}\" no longer requests: {}", service, list))
+                    .values(Some(serde_json::json!(previous.scopes)), Some(serde_json::json!(integration.scopes))),
+            );
+        }
+
+        if previous.optional && !integration.optional {
+            changes.push(
+                DetectedChange::new(OauthProviderAdded, Breaking, service, format!("Integration \"{}\" is no longer optional", service))
+                    .hint(&format!("Users who skipped {} will need to connect it", service)),
+            );
+        }
+    }
+
+    for integration in &old.integrations {
+        if !new.integrations.iter().any(|i| i.service == integration.service) {
+            changes.push(DetectedChange::new(
+                OauthProviderRemoved,
+                Info,
+                &integration.service,
+                format!("Integration \"{}\" no longer required", integration.service),
+            ));
+        }
+    }
+}
+
+fn diff_triggers(old: Option<&TriggerManifest>, new: Option<&TriggerManifest>, changes: &mut Vec<DetectedChange>) {
+    use ChangeSeverity::*;
+    use ChangeType::*;
+
+    let (Some(old), Some(new)) = (old, new) else {
+        return;
+    };
+
+    if old.trigger_type != new.trigger_type {
+        changes.push(
+            DetectedChange::new(
+                TriggerTypeChanged,
+                Breaking,
+                "trigger",
+                format!("Trigger type changed from \"{}\" to \"{}\"", old.trigger_type, new.trigger_type),
+            )
+            .values(Some(Value::String(old.trigger_type.clone())), Some(Value::String(new.trigger_type.clone())))
+            .hint("Existing installations may need to be reconfigured for the new trigger type"),
+        );
+        return;
+    }
+
+    if old.service != new.service {
+        changes.push(
+            DetectedChange::new(
+                TriggerConfigChanged,
+                Breaking,
+                "trigger.service",
+                format!(
+                    "Trigger service changed from \"{}\" to \"{}\"",
+                    old.service.as_deref().unwrap_or("none"),
+                    new.service.as_deref().unwrap_or("none")
+                ),
+            )
+            .values(old.service.clone().map(Value::String), new.service.clone().map(Value::String))
+            .hint("Existing webhook subscriptions point at the old service"),
+        );
+    }
+
+    let dropped: Vec<&String> = old.events.iter().filter(|e| !new.events.contains(e)).collect();
+    if !dropped.is_empty() {
+        let list = dropped.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ");
+        changes.push(
+            DetectedChange::new(TriggerConfigChanged, Warning, "trigger.events", format!("No longer triggered by: {}", list))
+                .values(Some(serde_json::json!(old.events)), Some(serde_json::json!(new.events))),
+        );
+    }
+    let added: Vec<&String> = new.events.iter().filter(|e| !old.events.contains(e)).collect();
+    if !added.is_empty() {
+        let list = added.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ");
+        changes.push(
+            DetectedChange::new(TriggerConfigChanged, Info, "trigger.events", format!("Also triggered by: {}", list))
+                .values(Some(serde_json::json!(old.events)), Some(serde_json::json!(new.events))),
+        );
+    }
+
+    if old.cron != new.cron || old.timezone != new.timezone {
+        let describe = |t: &TriggerManifest| match (&t.cron, &t.timezone) {
+            (Some(cron), Some(tz)) => format!("{} ({})", cron, tz),
+            (Some(cron), None) => cron.clone(),
+            _ => "none".to_string(),
+        };
+        changes.push(
+            DetectedChange::new(
+                TriggerConfigChanged,
+                Warning,
+                "trigger.cron",
+                format!("Schedule changed from \"{}\" to \"{}\"", describe(old), describe(new)),
+            )
+            .values(old.cron.clone().map(Value::String), new.cron.clone().map(Value::String)),
+        );
+    }
+}
+
+/// Pricing fields where a higher value costs users more
+const PRICE_FIELDS: [&str; 3] = ["price", "pricePerMonth", "pricePerExecution"];
+
+fn diff_pricing(old: Option<&Value>, new: Option<&Value>, changes: &mut Vec<DetectedChange>) {
+    use ChangeSeverity::*;
+    use ChangeType::PricingChanged;
+
+    let (Some(old), Some(new)) = (old, new) else {
+        return;
+    };
+
+    let old_model = old.get("model").and_then(Value::as_str);
+    let new_model = new.get("model").and_then(Value::as_str);
+    if old_model != new_model {
+        changes.push(
+            DetectedChange::new(
+                PricingChanged,
+                Breaking,
+                "pricing.model",
+                format!(
+                    "Pricing model changed from \"{}\" to \"{}\"",
+                    old_model.unwrap_or("none"),
+                    new_model.unwrap_or("none")
+                ),
+            )
+            .values(old.get("model").cloned(), new.get("model").cloned())
+            .hint("Installed users must accept the new pricing before their next billing cycle"),
+        );
+        return;
+    }
+
+    for field in PRICE_FIELDS {
+        let (Some(before), Some(after)) = (
+            old.get(field).and_then(Value::as_f64),
+            new.get(field).and_then(Value::as_f64),
+        ) else {
+            continue;
+        };
+        let path = format!("pricing.{}", field);
+        if after > before {
+            changes.push(
+                DetectedChange::new(PricingChanged, Breaking, &path, format!("Price increased from {} to {} ({})", before, after, field))
+                    .values(old.get(field).cloned(), new.get(field).cloned())
+                    .hint("Installed users must accept the new price before they are charged it"),
+            );
+        } else if after < before {
+            changes.push(
+                DetectedChange::new(PricingChanged, Info, &path, format!("Price decreased from {} to {} ({})", before, after, field))
+                    .values(old.get(field).cloned(), new.get(field).cloned()),
+            );
+        }
+    }
+
+    let free = |v: &Value| v.get("freeExecutions").and_then(Value::as_f64);
+    if let (Some(before), Some(after)) = (free(old), free(new)) {
+        if after < before {
+            changes.push(
+                DetectedChange::new(
+                    PricingChanged,
+                    Warning,
+                    "pricing.freeExecutions",
+                    format!("Free executions reduced from {} to {}", before, after),
+                )
+                .values(old.get("freeExecutions").cloned(), new.get("freeExecutions").cloned()),
+            );
+        }
+    }
+}
+
+/// Parses `major.minor.patch`, ignoring a leading `v` and any pre-release or
+/// build suffix.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v');
+    let core = core.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+fn check_version(old: Option<&str>, new: Option<&str>, changes: &[DetectedChange]) -> VersionCheck {
+    let required = if changes.iter().any(|c| c.severity == ChangeSeverity::Breaking) {
+        Bump::Major
+    } else if changes.is_empty() {
+        Bump::Patch
+    } else {
+        Bump::Minor
+    };
+    let mut check = VersionCheck {
+        old_version: old.map(str::to_string),
+        new_version: new.map(str::to_string),
+        required,
+        actual: None,
+        ok: false,
+        message: String::new(),
+    };
+
+    let (Some(old_parsed), Some(new_parsed)) = (old.and_then(parse_version), new.and_then(parse_version)) else {
+        check.ok = changes.is_empty();
+        check.message = match (old, new) {
+            (_, None) => "The new version has no version field".to_string(),
+            (None, _) => "The old version has no version field".to_string(),
+            _ => "Versions must be semver (major.minor.patch)".to_string(),
+        };
+        return check;
+    };
+
+    check.actual = match (new_parsed.0.cmp(&old_parsed.0), new_parsed.1.cmp(&old_parsed.1), new_parsed.2.cmp(&old_parsed.2)) {
+        (std::cmp::Ordering::Greater, _, _) => Some(Bump::Major),
+        (std::cmp::Ordering::Equal, std::cmp::Ordering::Greater, _) => Some(Bump::Minor),
+        (std::cmp::Ordering::Equal, std::cmp::Ordering::Equal, std::cmp::Ordering::Greater) => Some(Bump::Patch),
+        _ => None,
+    };
+
+    // Before 1.0.0 a minor bump may break, as in Cargo and npm's caret ranges
+    let enough = if required == Bump::Major && old_parsed.0 == 0 { Bump::Minor } else { required };
+
+    match check.actual {
+        None if changes.is_empty() => {
+            check.ok = true;
+            check.message = "No changes".to_string();
+        }
+        None => {
+            check.message = format!(
+                "Version must increase: {} -> {} needs at least a {} bump",
+                old.unwrap_or_default(),
+                new.unwrap_or_default(),
+                enough
+            );
+        }
+        Some(actual) if actual < enough => {
+            check.message = format!(
+                "{} -> {} is a {} bump, but the changes need a {} bump",
+                old.unwrap_or_default(),
+                new.unwrap_or_default(),
+                actual,
+                enough
+            );
+        }
+        Some(actual) => {
+            check.ok = true;
+            check.message = format!("{} bump covers the changes", actual);
+        }
+    }
+    check
+}
+
+fn summarize(changes: &[DetectedChange]) -> String {
+    let count = |severity| changes.iter().filter(|c| c.severity == severity).count();
+    let parts: Vec<String> = [
+        (count(ChangeSeverity::Breaking), "breaking change(s)"),
+        (count(ChangeSeverity::Warning), "warning(s)"),
+        (count(ChangeSeverity::Info), "informational change(s)"),
+    ]
+    .iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, label)| format!("{} {}", n, label))
+    .collect();
+
+    if parts.is_empty() {
+        "No changes detected".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// Compares two manifests.
+pub fn diff_manifests(old: &WorkflowManifest, new: &WorkflowManifest) -> WorkflowDiff {
+    let mut changes = Vec::new();
+    diff_inputs(&old.inputs, &new.inputs, &mut changes);
+    diff_integrations(old, new, &mut changes);
+    diff_triggers(old.triggers.first(), new.triggers.first(), &mut changes);
+    diff_pricing(old.pricing.as_ref(), new.pricing.as_ref(), &mut changes);
+
+    // Stable sort keeps detection order within a severity
+    changes.sort_by_key(|c| c.severity);
+
+    WorkflowDiff {
+        has_breaking_changes: changes.iter().any(|c| c.severity == ChangeSeverity::Breaking),
+        has_warnings: changes.iter().any(|c| c.severity == ChangeSeverity::Warning),
+        version: check_version(old.version.as_deref(), new.version.as_deref(), &changes),
+        summary: summarize(&changes),
+        changes,
+    }
+}
+
+/// Compares two versions of a workflow's source.
+pub fn diff_workflows(old: &str, new: &str) -> WorkflowDiff {
+    diff_manifests(&extract_manifest(old), &extract_manifest(new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1: &str = r#"
+import { defineWorkflow, webhook } from '@workway/sdk';
+
+export default defineWorkflow({
+    name: 'PR Notifier',
+    version: '1.2.0',
+    integrations: ['github', { service: 'slack', scopes: ['chat:write'] }],
+    inputs: {
+        channel: { type: 'string', label: 'Channel', required: true },
+        mention: { type: 'boolean', label: 'Mention' },
+        digest: { type: 'boolean', label: 'Digest' },
+    },
+    trigger: webhook({ service: 'github', events: ['pull_request.opened', 'pull_request.closed'] }),
+    pricing: { model: 'subscription', pricePerMonth: 5, freeExecutions: 100 },
+    async execute() { return { success: true }; },
+});
+"#;
+
+    #[test]
+    fn test_breaking_changes_need_major_bump() {
+        let v2 = V1
+            .replace("version: '1.2.0'", "version: '1.3.0'")
+            .replace("{ service: 'slack', scopes: ['chat:write'] }", "{ service: 'slack', scopes: ['chat:write', 'users:read'] }, 'linear'")
+            .replace("        digest: { type: 'boolean', label: 'Digest' },\n", "")
+            .replace("mention: { type: 'boolean', label: 'Mention' }", "mention: { type: 'string', label: 'Mention' }")
+            .replace("'pull_request.closed'", "'pull_request.merged'")
+            .replace("pricePerMonth: 5", "pricePerMonth: 8");
+        let diff = diff_workflows(V1, &v2);
+
+        let kinds: Vec<(ChangeType, ChangeSeverity, &str)> = diff
+            .changes
+            .iter()
+            .map(|c| (c.change_type, c.severity, c.field.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ChangeType::ConfigFieldRemoved, ChangeSeverity::Breaking, "digest"),
+                (ChangeType::ConfigFieldTypeChanged, ChangeSeverity::Breaking, "mention"),
+                (ChangeType::OauthScopeAdded, ChangeSeverity::Breaking, "slack"),
+                (ChangeType::OauthProviderAdded, ChangeSeverity::Breaking, "linear"),
+                (ChangeType::PricingChanged, ChangeSeverity::Breaking, "pricing.pricePerMonth"),
+                (ChangeType::TriggerConfigChanged, ChangeSeverity::Warning, "trigger.events"),
+                (ChangeType::TriggerConfigChanged, ChangeSeverity::Info, "trigger.events"),
+            ]
+        );
+        assert!(diff.has_breaking_changes);
+        assert_eq!(diff.version.required, Bump::Major);
+        assert_eq!(diff.version.actual, Some(Bump::Minor));
+        assert!(!diff.version.ok);
+        assert_eq!(diff.summary, "5 breaking change(s), 1 warning(s), 1 informational change(s)");
+    }
+
+    #[test]
+    fn test_compatible_changes_need_minor_bump() {
+        let v2 = V1
+            .replace("digest: { type: 'boolean', label: 'Digest' }", "digest: { type: 'boolean', label: 'Digest' },\n        emoji: { type: 'string' }")
+            .replace("pricePerMonth: 5", "pricePerMonth: 4")
+            .replace("['github', ", "[");
+
+        let patch = diff_workflows(V1, &v2.replace("1.2.0", "1.2.1"));
+        assert!(!patch.has_breaking_changes);
+        assert_eq!(patch.changes.len(), 3);
+        assert_eq!(patch.version.required, Bump::Minor);
+        assert!(!patch.version.ok);
+
+        let minor = diff_workflows(V1, &v2.replace("1.2.0", "1.3.0"));
+        assert!(minor.version.ok, "{}", minor.version.message);
+
+        let unchanged = diff_workflows(V1, V1);
+        assert!(unchanged.changes.is_empty());
+        assert!(unchanged.version.ok);
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_version("v2.0.0-beta.1"), Some((2, 0, 0)));
+        assert_eq!(parse_version("1.2"), None);
+        assert_eq!(parse_version("1.2.3.4"), None);
+
+        let breaking = [DetectedChange::new(ChangeType::ConfigFieldRemoved, ChangeSeverity::Breaking, "x", String::new())];
+        assert!(check_version(Some("0.3.1"), Some("0.4.0"), &breaking).ok);
+        assert!(!check_version(Some("0.3.1"), Some("0.3.2"), &breaking).ok);
+        assert!(check_version(Some("1.9.0"), Some("2.0.0"), &breaking).ok);
+    }
+}
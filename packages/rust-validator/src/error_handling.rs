@@ -0,0 +1,253 @@
+//! Error-handling coverage.
+//!
+//! `EMPTY_CATCH` only catches the most obvious way to lose an error. A
+//! workflow that calls out to other services can also fail without anyone
+//! noticing: no `onError` handler, an outbound call that is neither wrapped in
+//! `try` nor checked, a `catch` that only logs, or a plain `Error` that the
+//! platform cannot classify. This module finds those and reports what share of
+//! the outbound calls in `execute` are handled.
+//!
+//! A call counts as handled when it sits inside a `try` block or an SDK retry
+//! helper, has a `.catch(...)` attached, or its result is checked through
+//! `.success`, `.ok`, `.error` or `.status` (integration clients return an
+//! `ActionResult` rather than throwing).
+
+use std::ops::Range;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::Diagnostic;
+use crate::patterns::*;
+use crate::scan::Scan;
+use crate::source::{line_at, matching_close, result_bindings};
+use crate::validator::WorkflowMetadata;
+
+/// Unhandled call sites listed as related locations on `UNHANDLED_CALLS`
+const MAX_RELATED_CALLS: usize = 5;
+
+/// Error-handling summary attached to workflow metadata
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorHandlingCoverage {
+    /// Outbound call sites in `execute`
+    pub call_sites: u32,
+    /// Call sites whose failure is caught or checked
+    pub handled_call_sites: u32,
+    /// `handled_call_sites` as a percentage (100 when there are no calls)
+    pub coverage: u32,
+    pub has_on_error: bool,
+}
+
+/// Byte ranges of the blocks opened by every match of `pattern` (between the
+/// braces).
+fn blocks(content: &str, pattern: &Regex) -> Vec<Range<usize>> {
+    pattern
+        .find_iter(content)
+        .filter_map(|m| {
+            let open = m.end() - 1;
+            matching_close(content, open).map(|close| open + 1..close)
+        })
+        .collect()
+}
+
+/// Whether the result of the call at `call` is checked for failure later on.
+fn result_checked(content: &str, call: &Range<usize>, end: usize) -> bool {
+    let after = &content[call.end.min(end)..end];
+    let names = result_bindings(content, call.start);
+    !names.is_empty() && RESULT_CHECK.captures_iter(after).any(|caps| names.iter().any(|name| *name == caps[1]))
+}
+
+fn is_handled(content: &str, call: &Range<usize>, guarded: &[Range<usize>], body_end: usize) -> bool {
+    if guarded.iter().any(|block| block.contains(&call.start)) {
+        return true;
+    }
+    let close = matching_close(content, call.end - 1).unwrap_or(call.end);
+    content[close + 1..].trim_start().starts_with(".catch") || result_checked(content, call, body_end)
+}
+
+/// Whether a catch block does nothing but log: every statement is a
+/// `console.*(...)` call.
+fn only_logs(content: &str, body: &Range<usize>) -> bool {
+    let mut pos = body.start;
+    let mut logged = false;
+    loop {
+        let rest = &content[pos..body.end];
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ';');
+        if trimmed.is_empty() {
+            return logged;
+        }
+        pos += rest.len() - trimmed.len();
+        let Some(m) = CONSOLE_STATEMENTS.find(trimmed).filter(|m| m.start() == 0) else {
+            return false;
+        };
+        let after = &trimmed[m.end()..];
+        let Some(open) = after.find('(').filter(|&p| after[..p].trim().is_empty()) else {
+            return false;
+        };
+        let Some(close) = matching_close(content, pos + m.end() + open) else {
+            return false;
+        };
+        pos = (close + 1).min(body.end);
+        logged = true;
+    }
+}
+
+pub(crate) fn validate_error_handling(
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
+    metadata: &mut WorkflowMetadata,
+) {
+    // Strings and comments are blanked with offsets kept, so spans still
+    // point into `content`
+    let (content, code) = (scan.content(), scan.code());
+    let has_on_error = HAS_ON_ERROR.is_match(code);
+
+    for body in blocks(code, &CATCH_BLOCK) {
+        // A comment in the block marks the swallow as deliberate
+        let commented = content[body.clone()].contains("//") || content[body.clone()].contains("/*");
+        if !commented && only_logs(code, &body) {
+            let start = body.start.saturating_sub(1);
+            let catch = code[..start].rfind("catch").unwrap_or(start);
+            diagnostics.push(
+                Diagnostic::warning("LOG_ONLY_CATCH", "Catch block only logs the error")
+                    .with_span(content, catch..body.start)
+                    .with_suggestion("Return { success: false, error } or rethrow so the run fails and onError runs"),
+            );
+        }
+    }
+
+    for m in GENERIC_THROW.find_iter(code) {
+        diagnostics.push(
+            Diagnostic::info("GENERIC_ERROR_THROWN", "Thrown error is not an IntegrationError")
+                .with_span(content, m.range())
+                .with_suggestion("Throw new IntegrationError(ErrorCode.X, message) so the platform can pick a breakdown severity and retry policy"),
+        );
+    }
+
+    let Some(body) = scan.execute_body() else {
+        return;
+    };
+    let mut guarded = blocks(code, &TRY_BLOCK);
+    guarded.extend(RETRY_CALL.find_iter(code).filter_map(|m| {
+        let open = m.end() - 1;
+        matching_close(code, open).map(|close| open + 1..close)
+    }));
+
+    let calls: Vec<Range<usize>> = OUTBOUND_CALL
+        .find_iter(&code[body.clone()])
+        .map(|m| body.start + m.start()..body.start + m.end())
+        .chain(
+            RETRY_CALL
+                .find_iter(&code[body.clone()])
+                .filter(|m| m.as_str().starts_with("fetchWithRetry"))
+                .map(|m| body.start + m.start()..body.start + m.end()),
+        )
+        .collect();
+    let unhandled: Vec<&Range<usize>> = calls
+        .iter()
+        .filter(|call| !RETRY_CALL.is_match(&code[(*call).clone()]))
+        .filter(|call| !is_handled(code, call, &guarded, body.end))
+        .collect();
+
+    let call_sites = calls.len() as u32;
+    let handled = call_sites - unhandled.len() as u32;
+    let coverage = ErrorHandlingCoverage {
+        call_sites,
+        handled_call_sites: handled,
+        coverage: (handled * 100).checked_div(call_sites).unwrap_or(100),
+        has_on_error,
+    };
+
+    if call_sites > 0 && !has_on_error {
+        diagnostics.push(
+            Diagnostic::warning(
+                "MISSING_ON_ERROR",
+                &format!("Workflow makes {} outbound call(s) but has no onError handler", call_sites),
+            )
+            .with_line(line_at(content, calls[0].start))
+            .with_suggestion("Add: async onError({ error, inputs, integrations }) { ... } to notify the user or clean up"),
+        );
+    }
+
+    if let Some(first) = unhandled.first() {
+        let mut diagnostic = Diagnostic::warning(
+            "UNHANDLED_CALLS",
+            &format!(
+                "{} of {} outbound call(s) in execute are not wrapped in try/catch or checked ({}% covered)",
+                unhandled.len(),
+                call_sites,
+                coverage.coverage
+            ),
+        )
+        .with_span(content, (*first).clone())
+        .with_suggestion("Wrap calls in try/catch or withRetry(), or check result.success before using the data");
+        for call in unhandled.iter().skip(1).take(MAX_RELATED_CALLS) {
+            diagnostic = diagnostic.with_related(content, (*call).clone(), "Also unhandled");
+        }
+        diagnostics.push(diagnostic);
+    }
+
+    metadata.error_handling = Some(coverage);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(content: &str) -> (Vec<Diagnostic>, WorkflowMetadata) {
+        let mut diagnostics = Vec::new();
+        let mut metadata = WorkflowMetadata::default();
+        validate_error_handling(&Scan::new(content), &mut diagnostics, &mut metadata);
+        (diagnostics, metadata)
+    }
+
+    #[test]
+    fn test_coverage_counts_handled_calls() {
+        let content = r#"
+export default defineWorkflow({
+    async execute({ integrations }) {
+        try {
+            await fetch('https://example.com/a');
+        } catch (error) {
+            return { success: false, error: String(error) };
+        }
+        const page = await integrations.notion.pages.create({ parent });
+        if (!page.success) return { success: false };
+        await withRetry(() => integrations.slack.chat.postMessage({ text }));
+        const res = await fetch(url).catch(() => null);
+        await integrations.gmail.send({ to });
+        return { success: true };
+    },
+});
+"#;
+        let (diagnostics, metadata) = check(content);
+        let coverage = metadata.error_handling.unwrap();
+        assert_eq!((coverage.call_sites, coverage.handled_call_sites, coverage.coverage), (5, 4, 80));
+
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, vec!["MISSING_ON_ERROR", "UNHANDLED_CALLS"]);
+        let span = diagnostics[1].span.unwrap();
+        assert_eq!(&content[span.start..span.end], "integrations.gmail.send(");
+    }
+
+    #[test]
+    fn test_log_only_catch_and_generic_throw() {
+        let content = r#"
+async function load() {
+    try { await step(); } catch (e) { console.error('failed:', e); }
+    try { await step(); } catch (e) { console.log(e); return null; }
+    try { await step(); } catch {
+        // Best effort: the next run retries
+        console.warn('step skipped');
+    }
+    throw new Error('Not configured');
+}
+"#;
+        let (diagnostics, metadata) = check(content);
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, vec!["LOG_ONLY_CATCH", "GENERIC_ERROR_THROWN"]);
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert!(metadata.error_handling.is_none());
+    }
+}
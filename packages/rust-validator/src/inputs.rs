@@ -0,0 +1,675 @@
+//! Input references.
+//!
+//! `execute` reads the user's configuration through `inputs` (or the older
+//! `config`), as `inputs.channelId`, `inputs['channelId']` or
+//! `const { channelId } = inputs`. A misspelled key is simply `undefined` at
+//! runtime, so nothing fails until a downstream call does. This module matches
+//! those reads against the keys declared in `inputs`, `config` and
+//! `configFields`, ignoring case style (`notion_database_id` is read as
+//! `notionDatabaseId`).
+//!
+//! Reads are taken from the `execute` body. A key counts as used if it is read
+//! there or through `inputs.key` anywhere else in the file (helpers usually
+//! take `inputs` as a parameter); when `execute` passes the whole object on, a
+//! key also counts as used if its name appears anywhere outside the
+//! declarations. SDK placeholders in string values (`'{{inputs.timezone}}'`
+//! in a trigger config) are reads too; the platform substitutes them.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::diagnostic::{Diagnostic, TextEdit};
+use crate::literal::Literal;
+use crate::scan::Scan;
+use crate::source::matching_close;
+
+/// Execute context properties that carry the user's configuration
+const CONTEXT_KEYS: [&str; 2] = ["inputs", "config"];
+
+/// `inputs.key` / `config.key` anywhere in the file
+static CONTEXT_MEMBER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|[^\w$.])(?:inputs|config)\s*\??\.\s*([A-Za-z_$][\w$]*)").unwrap());
+
+/// SDK placeholders in string values: `{{inputs.sendTime.hour}}`
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*(?:inputs|config)\.([A-Za-z_$][\w$]*)").unwrap());
+
+static IDENTIFIER: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z_$][\w$]*").unwrap());
+
+static DESTRUCTURING: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:const|let|var)\s*\{").unwrap());
+
+/// What follows a value that is dereferenced: `.x` or `[`
+static DEREFERENCE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:\.\s*[A-Za-z_$]|\[)").unwrap());
+
+/// What follows a value that is checked or defaulted
+static GUARD_AFTER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:\?\?|\|\||&&|\?|[!=]==?)").unwrap());
+
+/// An input declared in `inputs`, `config` or `configFields`
+struct Declared {
+    key: String,
+    /// Span of the key in the declaration
+    span: Range<usize>,
+    /// Neither required nor defaulted
+    optional: bool,
+    source: &'static str,
+}
+
+/// A read of an input in `execute`
+struct Read {
+    key: String,
+    /// Span of the key
+    span: Range<usize>,
+    /// The expression that holds the value
+    value: Value,
+    /// Destructured with `=` default
+    defaulted: bool,
+    /// Shorthand destructuring (`{ key }`), where the key is also the variable
+    shorthand: bool,
+}
+
+/// An expression holding an input's value
+enum Value {
+    /// A variable it was destructured into
+    Binding(String),
+    /// `root.key` or `root?.key`
+    Member(String, String),
+    /// `root['key']` or `root?.['key']`
+    Index(String, String),
+}
+
+impl Value {
+    /// Ranges of the expression in `body`.
+    fn occurrences(&self, body: &str) -> Vec<Range<usize>> {
+        match self {
+            Value::Binding(name) => body
+                .match_indices(name.as_str())
+                .map(|(i, _)| i..i + name.len())
+                .filter(|m| {
+                    !body[..m.start].ends_with(is_ident_char) && !body[m.end..].starts_with(is_ident_char)
+                })
+                .collect(),
+            Value::Member(root, key) => root_uses(body, std::slice::from_ref(root))
+                .into_iter()
+                .filter_map(|(at, root)| {
+                    let after = at + root.len();
+                    let (offset, read) = member_key(&body[after..])?;
+                    (read == key).then(|| at..after + offset + key.len())
+                })
+                .collect(),
+            Value::Index(root, key) => root_uses(body, std::slice::from_ref(root))
+                .into_iter()
+                .filter_map(|(at, root)| {
+                    let quote = at + root.len() + index_quote(&body[at + root.len()..])?;
+                    let rest = body[quote + 1..].strip_prefix(key.as_str())?.strip_prefix(['\'', '"'])?;
+                    let rest = rest.trim_start().strip_prefix(']')?;
+                    Some(at..body.len() - rest.len())
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A property of a destructuring pattern: `key`, `key: alias` or
+/// `key = default`, with offsets relative to the pattern's `{`.
+struct Entry {
+    key: String,
+    key_start: usize,
+    binding: String,
+    binding_start: usize,
+    defaulted: bool,
+}
+
+/// Splits a destructuring pattern (`{ ... }`, with braces) into its
+/// properties. `...rest` is returned with the key `...`.
+fn destructure(pattern: &str) -> Vec<Entry> {
+    let bytes = pattern.as_bytes();
+    let end = matching_close(pattern, 0).unwrap_or(pattern.len());
+    let mut parts = Vec::new();
+    let (mut start, mut depth) = (1, 0);
+    for (i, &b) in bytes.iter().enumerate().take(end).skip(1) {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b',' if depth == 0 => {
+                parts.push(start..i);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(start..end);
+
+    parts
+        .into_iter()
+        .filter_map(|range| {
+            let part = &pattern[range.clone()];
+            let key_start = range.start + part.len() - part.trim_start().len();
+            let part = part.trim();
+            if part.is_empty() {
+                return None;
+            }
+            if let Some(rest) = part.strip_prefix("...") {
+                return Some(Entry {
+                    key: "...".to_string(),
+                    key_start,
+                    binding: rest.trim().to_string(),
+                    binding_start: key_start + 3,
+                    defaulted: false,
+                });
+            }
+            let key_len = part.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).unwrap_or(part.len());
+            let key = &part[..key_len];
+            let rest = part[key_len..].trim_start();
+            let (binding, binding_start, rest) = match rest.strip_prefix(':') {
+                Some(after) => {
+                    let binding_start = key_start + part.len() - after.trim_start().len();
+                    let after = after.trim_start();
+                    let binding_end = match after.as_bytes().first() {
+                        Some(b'{') | Some(b'[') => matching_close(after, 0).map_or(after.len(), |c| c + 1),
+                        _ => after.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(after.len()),
+                    };
+                    (after[..binding_end].to_string(), binding_start, &after[binding_end..])
+                }
+                None => (key.to_string(), key_start, rest),
+            };
+            (!key.is_empty()).then(|| Entry {
+                key: key.to_string(),
+                key_start,
+                binding,
+                binding_start,
+                defaulted: rest.trim_start().starts_with('='),
+            })
+        })
+        .collect()
+}
+
+/// Levenshtein distance between two strings.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = (previous + usize::from(ca != *cb)).min(row[j] + 1).min(current + 1);
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// A key with its case style removed. Declarations commonly use snake_case
+/// (`notion_database_id`) while `execute` reads the camelCase form the
+/// platform passes in (`notionDatabaseId`); both name the same input.
+fn key_form(key: &str) -> String {
+    key.to_lowercase().replace(['_', '-'], "")
+}
+
+/// The declared key a misspelled one most likely meant: at most two edits
+/// away, ignoring case style.
+fn closest<'a>(key: &str, declared: &'a [Declared]) -> Option<&'a str> {
+    let wanted = key_form(key);
+    declared
+        .iter()
+        .map(|d| (edit_distance(&wanted, &key_form(&d.key)), d.key.as_str()))
+        .filter(|&(distance, _)| distance <= 2 && distance * 2 < wanted.len())
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, key)| key)
+}
+
+/// Declared inputs, and whether the declarations can be read completely
+/// (no spreads or computed entries).
+fn declarations(scan: &Scan) -> Option<(Vec<Declared>, bool)> {
+    let (content, code, definition) = (scan.content(), scan.code(), scan.definition()?);
+    let mut declared = Vec::new();
+    let mut complete = true;
+    let mut found = false;
+
+    for source in CONTEXT_KEYS {
+        let Some(property) = definition.property(source) else {
+            continue;
+        };
+        found = true;
+        let block = code[property.offset..]
+            .find('{')
+            .map(|i| property.offset + i)
+            .and_then(|open| matching_close(code, open).map(|close| open..close));
+        match (&property.value, block) {
+            (Literal::Object(props), Some(block)) if !code[block.clone()].contains("...") => {
+                declared.extend(props.iter().map(|p| Declared {
+                    key: p.key.clone(),
+                    span: p.offset..p.offset + content[p.offset..].find([':', ',', '}']).unwrap_or(0),
+                    optional: !p.value.get("required").and_then(Literal::as_bool).unwrap_or(false)
+                        && p.value.get("default").is_none(),
+                    source,
+                }));
+            }
+            _ => complete = false,
+        }
+    }
+
+    if let Some(property) = definition.property("configFields") {
+        found = true;
+        match &property.value {
+            Literal::Array(fields) => {
+                for field in fields {
+                    let key = field.property("key").zip(field.get("key").and_then(Literal::as_str));
+                    let Some((key_property, key)) = key else {
+                        complete = false;
+                        continue;
+                    };
+                    let quote = content[key_property.offset..]
+                        .find(['\'', '"', '`'])
+                        .map_or(key_property.offset, |i| key_property.offset + i + 1);
+                    declared.push(Declared {
+                        key: key.to_string(),
+                        span: quote..quote + key.len(),
+                        optional: !field.get("required").and_then(Literal::as_bool).unwrap_or(false)
+                            && field.get("default").is_none(),
+                        source: "configFields",
+                    });
+                }
+            }
+            _ => complete = false,
+        }
+    }
+
+    found.then_some((declared, complete))
+}
+
+/// Expressions that hold the configuration object inside `execute`:
+/// `inputs`, an alias (`{ inputs: settings }`) or `ctx.inputs`, plus reads
+/// destructured directly in the parameter list.
+fn context_roots(code: &str, params: Range<usize>) -> (Vec<String>, Vec<(Entry, usize)>) {
+    let text = &code[params.clone()];
+    let trimmed = text.trim_start();
+    let offset = params.start + text.len() - trimmed.len();
+    let mut roots = Vec::new();
+    let mut reads = Vec::new();
+
+    if trimmed.starts_with('{') {
+        for entry in destructure(trimmed) {
+            if !CONTEXT_KEYS.contains(&entry.key.as_str()) {
+                continue;
+            }
+            if entry.binding.starts_with('{') {
+                let start = offset + entry.binding_start;
+                reads.extend(destructure(&entry.binding).into_iter().map(|inner| (inner, start)));
+            } else {
+                roots.push(entry.binding);
+            }
+        }
+    } else {
+        let name_len = trimmed.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).unwrap_or(trimmed.len());
+        if name_len > 0 {
+            let name = &trimmed[..name_len];
+            roots.extend(CONTEXT_KEYS.iter().map(|key| format!("{}.{}", name, key)));
+        }
+    }
+    (roots, reads)
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Offsets in `scope` where one of `roots` is used as a whole expression
+/// (not part of a longer name or a property of something else), in order.
+fn root_uses<'r>(scope: &str, roots: &'r [String]) -> Vec<(usize, &'r str)> {
+    let mut uses: Vec<(usize, &str)> = roots
+        .iter()
+        .flat_map(|root| scope.match_indices(root.as_str()).map(move |(i, _)| (i, root.as_str())))
+        .filter(|&(i, root)| {
+            !scope[..i].ends_with(|c: char| is_ident_char(c) || c == '.')
+                && !scope[i + root.len()..].starts_with(is_ident_char)
+        })
+        .collect();
+    uses.sort_unstable();
+    uses
+}
+
+/// The property name read by `.key` or `?.key` at the start of `after`,
+/// with its offset in `after`.
+fn member_key(after: &str) -> Option<(usize, &str)> {
+    let trimmed = after.trim_start();
+    let rest = trimmed.strip_prefix("?.").or_else(|| trimmed.strip_prefix('.'))?.trim_start();
+    if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$') {
+        return None;
+    }
+    let len = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+    Some((after.len() - rest.len(), &rest[..len]))
+}
+
+/// Offset in `after` of the opening quote of a `['key']` or `?.['key']`
+/// read at its start.
+fn index_quote(after: &str) -> Option<usize> {
+    let trimmed = after.trim_start();
+    let rest = trimmed.strip_prefix("?.").unwrap_or(trimmed).strip_prefix('[')?.trim_start();
+    rest.starts_with(['\'', '"']).then(|| after.len() - rest.len())
+}
+
+fn read_from_entry(entry: &Entry, base: usize) -> Read {
+    Read {
+        key: entry.key.clone(),
+        span: base + entry.key_start..base + entry.key_start + entry.key.len(),
+        value: Value::Binding(entry.binding.clone()),
+        defaulted: entry.defaulted,
+        shorthand: entry.binding == entry.key && entry.binding_start == entry.key_start,
+    }
+}
+
+/// Whether `value` has a property or element read (`value.x`, `value[0]`)
+/// in `body`, which throws when it is undefined.
+fn dereferenced(body: &str, value: &Value) -> bool {
+    value.occurrences(body).into_iter().any(|m| DEREFERENCE.is_match(&body[m.end..]))
+}
+
+/// Whether `text` ends with the keyword `word`.
+fn ends_with_word(text: &str, word: &str) -> bool {
+    text.strip_suffix(word)
+        .is_some_and(|before| !before.ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '$'))
+}
+
+/// Whether the code before a value checks it: `!`, `if (`, `&&`, `||` or
+/// `typeof`.
+fn guarded_before(before: &str) -> bool {
+    let trimmed = before.trim_end();
+    trimmed.ends_with('!')
+        || trimmed.ends_with("&&")
+        || trimmed.ends_with("||")
+        || (trimmed.len() < before.len() && ends_with_word(trimmed, "typeof"))
+        || trimmed.strip_suffix('(').is_some_and(|rest| ends_with_word(rest.trim_end(), "if"))
+}
+
+/// Whether `value` is checked or defaulted anywhere in `body`.
+fn guarded(body: &str, value: &Value) -> bool {
+    value
+        .occurrences(body)
+        .into_iter()
+        .any(|m| guarded_before(&body[..m.start]) || GUARD_AFTER.is_match(&body[m.end..]))
+}
+
+pub(crate) fn validate_inputs(scan: &Scan, diagnostics: &mut Vec<Diagnostic>) {
+    let (content, code) = (scan.content(), scan.code());
+    let Some((declared, complete)) = declarations(scan) else {
+        return;
+    };
+    let (Some(body), Some(params)) = (scan.execute_body(), scan.execute_params()) else {
+        return;
+    };
+
+    let (roots, param_reads) = context_roots(code, params);
+    let mut reads: Vec<Read> = Vec::new();
+    let mut escapes = param_reads.iter().any(|(entry, _)| entry.key == "...");
+    reads.extend(
+        param_reads
+            .iter()
+            .filter(|(entry, _)| entry.key != "...")
+            .map(|(entry, base)| read_from_entry(entry, *base)),
+    );
+
+    if !roots.is_empty() {
+        let scope = &code[body.clone()];
+        let uses = root_uses(scope, &roots);
+
+        for &(at, root) in &uses {
+            let after = at + root.len();
+            if let Some((offset, key)) = member_key(&scope[after..]) {
+                let start = body.start + after + offset;
+                reads.push(Read {
+                    key: key.to_string(),
+                    span: start..start + key.len(),
+                    value: Value::Member(root.to_string(), key.to_string()),
+                    defaulted: false,
+                    shorthand: false,
+                });
+            }
+        }
+        for &(at, root) in &uses {
+            let Some(offset) = index_quote(&scope[at + root.len()..]) else {
+                continue;
+            };
+            let quote = body.start + at + root.len() + offset;
+            let close = content[quote + 1..].find(content.as_bytes()[quote] as char).map_or(quote + 1, |i| quote + 1 + i);
+            let key = &content[quote + 1..close];
+            reads.push(Read {
+                key: key.to_string(),
+                span: quote + 1..close,
+                value: Value::Index(root.to_string(), key.to_string()),
+                defaulted: false,
+                shorthand: false,
+            });
+        }
+
+        let mut sources = Vec::new();
+        for m in DESTRUCTURING.find_iter(scope) {
+            let open = body.start + m.end() - 1;
+            let Some(close) = matching_close(code, open) else {
+                continue;
+            };
+            let rest = code[close + 1..].trim_start();
+            let Some(rhs) = rest.strip_prefix('=').map(str::trim_start).filter(|r| !r.starts_with('=')) else {
+                continue;
+            };
+            let is_root = roots.iter().any(|root| {
+                rhs.starts_with(root.as_str())
+                    && !rhs[root.len()..].starts_with(|c: char| c.is_alphanumeric() || matches!(c, '_' | '$' | '.' | '['))
+            });
+            if !is_root {
+                continue;
+            }
+            sources.push(code.len() - rhs.len());
+            for entry in destructure(&code[open..=close]) {
+                if entry.key == "..." {
+                    escapes = true;
+                } else {
+                    reads.push(read_from_entry(&entry, open));
+                }
+            }
+        }
+
+        for &(at, root) in &uses {
+            let after = scope[at + root.len()..].trim_start();
+            let member_access = after.starts_with('.') || after.starts_with('[') || after.starts_with("?.");
+            let object_key = after.starts_with(':') && !scope[..at].trim_end().ends_with('?');
+            if !member_access && !object_key && !sources.contains(&(body.start + at)) {
+                escapes = true;
+            }
+        }
+    }
+
+    let declared_key = |key: &str| declared.iter().any(|d| key_form(&d.key) == key_form(key));
+    let mut reported: Vec<&str> = Vec::new();
+    for read in &reads {
+        if !complete || declared_key(&read.key) || reported.contains(&read.key.as_str()) {
+            continue;
+        }
+        reported.push(&read.key);
+        let mut diagnostic = Diagnostic::error(
+            "UNDECLARED_INPUT",
+            &format!("Input '{}' is read in execute but not declared", read.key),
+        )
+        .with_span(content, read.span.clone());
+        match closest(&read.key, &declared) {
+            Some(suggestion) => {
+                let replacement = if read.shorthand {
+                    format!("{}: {}", suggestion, read.key)
+                } else {
+                    suggestion.to_string()
+                };
+                diagnostic = diagnostic
+                    .with_suggestion(&format!("Did you mean '{}'?", suggestion))
+                    .with_fix(
+                        &format!("Read '{}' instead", suggestion),
+                        vec![TextEdit::replace(read.span.clone(), &replacement)],
+                    );
+            }
+            None => {
+                diagnostic = diagnostic.with_suggestion(&format!("Declare '{}' in inputs, or fix the key", read.key));
+            }
+        }
+        for other in reads.iter().filter(|r| r.key == read.key && r.span != read.span) {
+            diagnostic = diagnostic.with_related(content, other.span.clone(), "Also read here");
+        }
+        diagnostics.push(diagnostic);
+    }
+
+    // Reads outside execute: helpers taking `inputs` or `config`, onError
+    // and placeholders, which only survive in the raw content
+    let accessed: Vec<String> = CONTEXT_MEMBER
+        .captures_iter(code)
+        .chain(PLACEHOLDER.captures_iter(content))
+        .map(|caps| key_form(&caps[1]))
+        .collect();
+    // When execute passes the object on, any property of that name may be it
+    let mentioned: HashSet<String> = if escapes {
+        let forms: HashSet<String> = declared.iter().map(|d| key_form(&d.key)).collect();
+        let mut form = String::new();
+        IDENTIFIER
+            .find_iter(code)
+            .filter_map(|m| {
+                // key_form without allocating; identifiers have no `-`
+                form.clear();
+                form.extend(m.as_str().chars().filter(|&c| c != '_').flat_map(char::to_lowercase));
+                let found = forms.get(&form)?;
+                (!declared.iter().any(|d| d.span.contains(&m.start()))).then(|| found.clone())
+            })
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    for input in &declared {
+        let form = key_form(&input.key);
+        let used = reads.iter().any(|r| key_form(&r.key) == form) || accessed.contains(&form) || mentioned.contains(&form);
+        if !used {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "UNUSED_INPUT",
+                    &format!("Input '{}' is declared but never read", input.key),
+                )
+                .with_span(content, input.span.clone())
+                .with_suggestion(&format!("Read it in execute, or remove it from {}", input.source)),
+            );
+        }
+    }
+
+    let scope = &code[body];
+    for input in declared.iter().filter(|d| d.optional) {
+        let uses: Vec<&Read> = reads.iter().filter(|r| key_form(&r.key) == key_form(&input.key)).collect();
+        let Some(first) = uses.iter().find(|r| dereferenced(scope, &r.value)) else {
+            continue;
+        };
+        if uses.iter().any(|r| r.defaulted || guarded(scope, &r.value)) {
+            continue;
+        }
+        diagnostics.push(
+            Diagnostic::info(
+                "UNGUARDED_OPTIONAL_INPUT",
+                &format!("Optional input '{}' is dereferenced without a default or null check", input.key),
+            )
+            .with_span(content, first.span.clone())
+            .with_related(content, input.span.clone(), "Declared here without required or default")
+            .with_suggestion(&format!("Add a default to the declaration, or read it as inputs.{} ?? fallback", input.key)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(content: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        validate_inputs(&Scan::new(content), &mut diagnostics);
+        diagnostics
+    }
+
+    #[test]
+    fn test_undeclared_unused_and_unguarded_inputs() {
+        let content = r#"
+export default defineWorkflow({
+    inputs: {
+        channelId: { type: 'string', required: true },
+        databaseId: { type: 'string', required: true },
+        reportTitle: { type: 'string', default: 'Weekly report' },
+        tag: { type: 'string' },
+        archive: { type: 'boolean' },
+    },
+    async execute({ inputs, integrations }) {
+        const { reportTitle, archive = false } = inputs;
+        await integrations.slack.chat.postMessage({ channel: inputs.chanelId, text: reportTitle });
+        const label = inputs.tag.toUpperCase();
+        if (inputs['missing']) return { success: false };
+        return { success: true, archive, label };
+    },
+});
+"#;
+        let diagnostics = codes(content);
+        let summary: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| {
+                let span = d.span.unwrap();
+                (d.code.as_str(), &content[span.start..span.end])
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("UNDECLARED_INPUT", "chanelId"),
+                ("UNDECLARED_INPUT", "missing"),
+                ("UNUSED_INPUT", "channelId"),
+                ("UNUSED_INPUT", "databaseId"),
+                ("UNGUARDED_OPTIONAL_INPUT", "tag"),
+            ]
+        );
+        assert_eq!(diagnostics[0].suggestion.as_deref(), Some("Did you mean 'channelId'?"));
+        assert_eq!(diagnostics[0].fixes[0].edits[0].replacement, "channelId");
+    }
+
+    #[test]
+    fn test_escaped_inputs_and_aliases() {
+        let content = r#"
+defineWorkflow({
+    config: {
+        notion_database_id: { type: 'string', required: true },
+        max_items: { type: 'number' },
+        retries: { type: 'number', default: 2 },
+    },
+    async execute({ config: settings, integrations }) {
+        const { notionDatabseId } = settings;
+        const limit = settings.maxItems ?? 10;
+        return buildReport(settings, notionDatabseId, limit);
+    },
+});
+function buildReport(config) {
+    return config.notionDatabaseId ? config.retries : 0;
+}
+"#;
+        let diagnostics = codes(content);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "UNDECLARED_INPUT");
+        let fix = &diagnostics[0].fixes[0].edits[0];
+        assert_eq!(fix.replacement, "notion_database_id: notionDatabseId");
+    }
+
+    #[test]
+    fn test_placeholders_count_as_reads() {
+        let content = r#"
+defineWorkflow({
+    inputs: {
+        sendTime: { type: 'object', default: { hour: 9 } },
+        timezone: { type: 'string', default: 'UTC' },
+    },
+    trigger: schedule({ cron: '0 {{inputs.sendTime.hour}} * * *', timezone: '{{ inputs.timezone }}' }),
+    async execute({ integrations }) {
+        return { success: true };
+    },
+});
+"#;
+        assert!(codes(content).is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! API hosts of first-class integrations.
+//!
+//! Mirrors the default `apiUrl` of each client in `packages/integrations/src`.
+//! A workflow that calls one of these hosts with `fetch` or `http.*` bypasses
+//! the integration: it has to carry its own token, gets no refresh on expiry,
+//! no rate-limit handling and no `ActionResult` errors. Add an entry here when
+//! an integration is added to the registry.
+
+use crate::diagnostic::Diagnostic;
+use crate::patterns::*;
+use crate::scan::Scan;
+use crate::validator::WorkflowMetadata;
+
+/// An API served by a first-class integration
+#[derive(Debug, Clone)]
+pub struct IntegrationHost {
+    /// Host, optionally followed by a path prefix (`slack.com/api`). A leading
+    /// `.` matches any subdomain (`.docusign.net`).
+    pub prefix: &'static str,
+    /// Service id as declared in `integrations: [...]`
+    pub service: &'static str,
+    /// Client property on `integrations` inside `execute`
+    pub client: &'static str,
+    /// Display name
+    pub name: &'static str,
+}
+
+const fn host(prefix: &'static str, service: &'static str, client: &'static str, name: &'static str) -> IntegrationHost {
+    IntegrationHost { prefix, service, client, name }
+}
+
+/// API hosts of the integrations in the registry
+pub static INTEGRATION_HOSTS: &[IntegrationHost] = &[
+    host("api.airtable.com", "airtable", "airtable", "Airtable"),
+    host("developer.api.autodesk.com", "autodesk", "autodesk", "Autodesk"),
+    host("studioapi.bluebeam.com", "bluebeam", "bluebeam", "Bluebeam"),
+    host("studioapi-au.bluebeam.com", "bluebeam", "bluebeam", "Bluebeam"),
+    host("studioapi-de.bluebeam.com", "bluebeam", "bluebeam", "Bluebeam"),
+    host("studioapi-uk.bluebeam.com", "bluebeam", "bluebeam", "Bluebeam"),
+    host("studioapi-se.bluebeam.com", "bluebeam", "bluebeam", "Bluebeam"),
+    host("app.gobridgit.com/rp/api", "bridgit-bench", "bridgitBench", "Bridgit Bench"),
+    host("api.calendly.com", "calendly", "calendly", "Calendly"),
+    host("discord.com/api", "discord", "discord", "Discord"),
+    host(".docusign.net/restapi", "docusign", "docusign", "DocuSign"),
+    host("api.dribbble.com", "dribbble", "dribbble", "Dribbble"),
+    host("www.dronedeploy.com/graphql", "dronedeploy", "dronedeploy", "DroneDeploy"),
+    host("api.equipmentwatch.com", "equipmentwatch", "equipmentwatch", "EquipmentWatch"),
+    host("api.followupboss.com", "follow-up-boss", "followUpBoss", "Follow Up Boss"),
+    host("api.github.com", "github", "github", "GitHub"),
+    host("sheets.googleapis.com", "google-sheets", "googleSheets", "Google Sheets"),
+    host("api.hubapi.com", "hubspot", "hubspot", "HubSpot"),
+    host("api.linear.app", "linear", "linear", "Linear"),
+    host("nexhealth.info/api", "nexhealth", "nexhealth", "NexHealth"),
+    host("api.notion.com", "notion", "notion", "Notion"),
+    host("api.procore.com", "procore", "procore", "Procore"),
+    host("sandbox.procore.com", "procore", "procore", "Procore"),
+    host("quickbooks.api.intuit.com", "quickbooks", "quickbooks", "QuickBooks"),
+    host("sandbox-quickbooks.api.intuit.com", "quickbooks", "quickbooks", "QuickBooks"),
+    host("api.intacct.com", "sage-intacct", "sageIntacct", "Sage Intacct"),
+    host("api.shovels.ai", "shovels", "shovels", "Shovels"),
+    host("slack.com/api", "slack", "slack", "Slack"),
+    host("api.stripe.com", "stripe", "stripe", "Stripe"),
+    host("api.todoist.com", "todoist", "todoist", "Todoist"),
+    host("api.typeform.com", "typeform", "typeform", "Typeform"),
+    host("api.eu.typeform.com", "typeform", "typeform", "Typeform"),
+    host("api.getweave.com", "weave", "weave", "Weave"),
+    host("www.googleapis.com/youtube", "youtube", "youtube", "YouTube"),
+    host("api.zoom.us", "zoom", "zoom", "Zoom"),
+];
+
+/// Finds the integration serving an absolute `http(s)` URL. Only the part of
+/// the URL up to the first template interpolation needs to be known.
+pub fn integration_for_url(url: &str) -> Option<&'static IntegrationHost> {
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))?;
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let host = rest[..authority_end].split(':').next()?.to_lowercase();
+    let path = &rest[authority_end..];
+    INTEGRATION_HOSTS.iter().find(|entry| {
+        let (entry_host, entry_path) = match entry.prefix.find('/') {
+            Some(i) => entry.prefix.split_at(i),
+            None => (entry.prefix, ""),
+        };
+        let host_matches = match entry_host.strip_prefix('.') {
+            Some(domain) => host.ends_with(entry_host) || host == domain,
+            None => host == entry_host,
+        };
+        host_matches
+            && path.starts_with(entry_path)
+            && matches!(path[entry_path.len()..].chars().next(), None | Some('/' | '?' | '#'))
+    })
+}
+
+/// The URL text of a string or template literal starting at `start`, up to
+/// its first `${`, with the span of the whole literal.
+fn literal_url(content: &str, start: usize) -> Option<(&str, std::ops::Range<usize>)> {
+    let quote = content[start..].chars().next().filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let end = start + 1 + content[start + 1..].find(quote)?;
+    let text = &content[start + 1..end];
+    let text = match quote {
+        '`' => text.split("${").next().unwrap_or(text),
+        _ => text,
+    };
+    Some((text, start..end + 1))
+}
+
+pub(crate) fn validate_raw_api_calls(
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
+    metadata: &WorkflowMetadata,
+) {
+    // Calls in comments and strings are not calls
+    let (content, code) = (scan.content(), scan.code());
+    for m in RAW_HTTP_CALL.find_iter(code) {
+        let after = &code[m.end()..];
+        let start = m.end() + after.len() - after.trim_start().len();
+        let Some((url, span)) = literal_url(content, start) else {
+            continue;
+        };
+        let Some(integration) = integration_for_url(url) else {
+            continue;
+        };
+
+        let declared = metadata
+            .integrations
+            .as_ref()
+            .is_some_and(|services| services.iter().any(|s| s == integration.service));
+        let suggestion = if declared {
+            format!("Use integrations.{} instead", integration.client)
+        } else {
+            format!(
+                "Add '{}' to integrations and use integrations.{} instead",
+                integration.service, integration.client
+            )
+        };
+        let call = m.as_str().trim_end_matches(|c: char| c == '(' || c.is_whitespace());
+        diagnostics.push(
+            Diagnostic::warning(
+                "RAW_INTEGRATION_API_CALL",
+                &format!("{}() calls the {} API directly", call, integration.name),
+            )
+            .with_span(content, span)
+            .with_suggestion(&suggestion),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration_for_url() {
+        let service = |url: &str| integration_for_url(url).map(|i| i.service);
+        assert_eq!(service("https://api.notion.com/v1/pages"), Some("notion"));
+        assert_eq!(service("https://slack.com/api/chat.postMessage"), Some("slack"));
+        assert_eq!(service("https://SHEETS.googleapis.com:443/v4/spreadsheets/"), Some("google-sheets"));
+        assert_eq!(service("https://na4.docusign.net/restapi/v2.1/accounts/"), Some("docusign"));
+        assert_eq!(service("https://www.googleapis.com/youtube/v3/videos"), Some("youtube"));
+        // Same host, different API
+        assert_eq!(service("https://www.googleapis.com/gmail/v1/users/me"), None);
+        assert_eq!(service("https://hooks.slack.com/services/T000/B000"), None);
+        assert_eq!(service("https://slack.com/apix"), None);
+        assert_eq!(service("https://api.notion.com.evil.example/v1"), None);
+        assert_eq!(service("/v1/pages"), None);
+    }
+
+    #[test]
+    fn test_raw_calls_to_integration_hosts() {
+        let content = r#"
+// fetch('https://api.notion.com/v1/pages') in a comment is ignored
+const page = await fetch('https://api.notion.com/v1/pages', { method: 'POST' });
+await http.post(`https://api.stripe.com/v1/customers/${id}`, body);
+await fetch(`${base}/v1/pages`);
+await fetch('https://example.com/hook');
+"#;
+        let metadata = WorkflowMetadata {
+            integrations: Some(vec!["notion".to_string()]),
+            ..Default::default()
+        };
+        let mut diagnostics = Vec::new();
+        validate_raw_api_calls(&Scan::new(content), &mut diagnostics, &metadata);
+
+        let found: Vec<(&str, Option<&str>)> =
+            diagnostics.iter().map(|d| (d.message.as_str(), d.suggestion.as_deref())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("fetch() calls the Notion API directly", Some("Use integrations.notion instead")),
+                (
+                    "http.post() calls the Stripe API directly",
+                    Some("Add 'stripe' to integrations and use integrations.stripe instead")
+                ),
+            ]
+        );
+        assert_eq!(diagnostics[0].line, Some(3));
+    }
+}
@@ -15,11 +15,33 @@
 //! console.log(result.errors);
 //! ```
 
+mod ai_models;
+mod ai_usage;
+mod baseline;
+mod budget;
+mod codes;
+mod collection;
+mod config;
+mod custom_rules;
+mod diagnostic;
+mod diff;
+mod error_handling;
+mod inputs;
+mod integration_hosts;
+mod literal;
+mod logging;
+mod manifest;
 mod patterns;
+mod readiness;
+mod report;
+mod scan;
+mod sdk_deprecations;
+mod source;
+mod taint;
 mod validator;
 
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
-use serde_wasm_bindgen;
 
 /// Initialize panic hook for better error messages in WASM
 #[wasm_bindgen(start)]
@@ -50,6 +72,203 @@ pub fn validate_workflow_wasm(content: &str) -> Result<JsValue, JsValue> {
         .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// Options accepted by [`validate_workflow_with_options`]
+#[derive(serde::Deserialize)]
+struct ValidateOptions {
+    /// Parsed `.workway-validator.toml` contents
+    #[serde(default)]
+    config: config::ValidatorConfig,
+    /// Path of the validated file relative to the config file, for overrides
+    #[serde(default)]
+    path: Option<String>,
+    /// Contents of the workflow's `package.json`, for the SDK version when
+    /// `config.sdk.version` is unset
+    #[serde(default, rename = "packageJson")]
+    package_json: Option<String>,
+}
+
+/// A project configuration checked once and reused across files.
+///
+/// Custom rule patterns are compiled when the validator is created, so
+/// validating many files (or the same file on every change) under one
+/// `.workway-validator.toml` does not parse or compile it again.
+#[wasm_bindgen]
+pub struct WorkflowValidator {
+    config: config::ValidatorConfig,
+}
+
+#[wasm_bindgen]
+impl WorkflowValidator {
+    /// Create a validator from a `config` object (see [`parse_config_wasm`]);
+    /// `undefined` uses the defaults.
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: JsValue) -> Result<WorkflowValidator, JsValue> {
+        let config: config::ValidatorConfig = if config.is_undefined() || config.is_null() {
+            Default::default()
+        } else {
+            serde_wasm_bindgen::from_value(config).map_err(|e| JsValue::from_str(&e.to_string()))?
+        };
+        config.check().map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(Self { config })
+    }
+
+    /// Create a validator from the text of a `.workway-validator.toml`.
+    #[wasm_bindgen(js_name = fromToml)]
+    pub fn from_toml(source: &str) -> Result<WorkflowValidator, JsValue> {
+        let config = config::ValidatorConfig::parse(source).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(Self { config })
+    }
+
+    /// Validate workflow content. `path` is the file's path relative to the
+    /// config file, for overrides; `packageJson` is the text of the
+    /// workflow's `package.json`, for the SDK version when the config sets
+    /// none.
+    pub fn validate(
+        &self,
+        content: &str,
+        path: Option<String>,
+        #[wasm_bindgen(js_name = packageJson)] package_json: Option<String>,
+    ) -> Result<JsValue, JsValue> {
+        let sdk_version = match &self.config.sdk.version {
+            Some(_) => None,
+            None => package_json.as_deref().and_then(sdk_version_from_package_json),
+        };
+        let result = match sdk_version {
+            Some(version) => {
+                let mut config = self.config.clone();
+                config.sdk.version = Some(version);
+                validator::validate_workflow_with_config(content, &config, path.as_deref())
+            }
+            None => validator::validate_workflow_with_config(content, &self.config, path.as_deref()),
+        };
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+/// Validate workflow content under a project configuration.
+///
+/// `options` is `{ config?, path?, packageJson? }`, where `config` has the
+/// same structure as `.workway-validator.toml` (see [`parse_config_wasm`]),
+/// `path` selects which `[[overrides]]` apply and `packageJson` supplies the
+/// SDK version deprecations are checked against. The config is checked on
+/// every call; use [`WorkflowValidator`] to validate many files under one.
+#[wasm_bindgen]
+pub fn validate_workflow_with_options(content: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options: ValidateOptions = if options.is_undefined() || options.is_null() {
+        ValidateOptions { config: Default::default(), path: None, package_json: None }
+    } else {
+        serde_wasm_bindgen::from_value(options).map_err(|e| JsValue::from_str(&e.to_string()))?
+    };
+    options.config.check().map_err(|e| JsValue::from_str(&e.to_string()))?;
+    WorkflowValidator { config: options.config }.validate(content, options.path, options.package_json)
+}
+
+/// Parse a `.workway-validator.toml` document into the `config` object
+/// expected by [`validate_workflow_with_options`].
+#[wasm_bindgen(js_name = parse_config)]
+pub fn parse_config_wasm(source: &str) -> Result<JsValue, JsValue> {
+    let config = config::ValidatorConfig::parse(source).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    config
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Extract the normalized workflow manifest as a JS object.
+///
+/// Returns everything that can be determined statically: triggers, inputs,
+/// integrations with scopes, pricing, metadata, pathway, visibility and
+/// deprecation. The shape is versioned by `schemaVersion`.
+#[wasm_bindgen(js_name = extract_manifest)]
+pub fn extract_manifest_wasm(content: &str) -> Result<JsValue, JsValue> {
+    let manifest = manifest::extract_manifest(content);
+    manifest
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Score a workflow's marketplace readiness.
+///
+/// Returns `{ score, listable, areas, checklist }`: a 0-100 score over
+/// completeness, robustness and security, whether any blocker remains, and
+/// every check with failed ones first in the order they should be fixed.
+#[wasm_bindgen(js_name = readiness)]
+pub fn readiness_wasm(content: &str) -> Result<JsValue, JsValue> {
+    let report = readiness::score_readiness(content);
+    report
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Compare two versions of a workflow for breaking changes.
+///
+/// Returns `{ hasBreakingChanges, hasWarnings, changes, version, summary }`.
+/// `version` reports whether the `version` bump is large enough for the
+/// changes: major for breaking ones (minor before 1.0.0), minor otherwise.
+#[wasm_bindgen(js_name = diff_workflows)]
+pub fn diff_workflows_wasm(old: &str, new: &str) -> Result<JsValue, JsValue> {
+    let diff = diff::diff_workflows(old, new);
+    diff
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// A workflow file passed to [`validate_collection_wasm`]
+#[derive(serde::Deserialize)]
+struct CollectionFile {
+    path: String,
+    content: String,
+}
+
+/// A file's findings returned by [`validate_collection_wasm`]
+#[derive(Serialize)]
+struct CollectionFileResult<'a> {
+    path: &'a str,
+    diagnostics: Vec<diagnostic::Diagnostic>,
+}
+
+/// Check a collection of workflows against each other.
+///
+/// `files` is `[{ path, content }]` for every workflow in the directory.
+/// Returns `[{ path, diagnostics }]` in the same order: supersededBy targets
+/// that are missing, deprecated or cyclic, duplicate ids and names, and live
+/// near-duplicates. Per-file validation is separate.
+#[wasm_bindgen(js_name = validate_collection)]
+pub fn validate_collection_wasm(files: JsValue) -> Result<JsValue, JsValue> {
+    let files: Vec<CollectionFile> =
+        serde_wasm_bindgen::from_value(files).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let pairs: Vec<(&str, &str)> = files.iter().map(|f| (f.path.as_str(), f.content.as_str())).collect();
+    let results: Vec<CollectionFileResult> = collection::validate_collection(&pairs)
+        .into_iter()
+        .zip(&files)
+        .map(|(diagnostics, file)| CollectionFileResult { path: &file.path, diagnostics })
+        .collect();
+    results
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Explain a diagnostic code.
+///
+/// Returns the code's category, default severity, long-form explanation,
+/// bad/good examples and docs URL, or `undefined` for unknown codes.
+#[wasm_bindgen(js_name = explain)]
+pub fn explain_wasm(code: &str) -> Result<JsValue, JsValue> {
+    match codes::explain(code) {
+        Some(info) => serde_wasm_bindgen::to_value(&codes::CodeDoc::from(info))
+            .map_err(|e| JsValue::from_str(&e.to_string())),
+        None => Ok(JsValue::UNDEFINED),
+    }
+}
+
+/// List every diagnostic code the validator can emit.
+#[wasm_bindgen(js_name = list_codes)]
+pub fn list_codes_wasm() -> Result<JsValue, JsValue> {
+    let docs: Vec<codes::CodeDoc> = codes::list_codes().iter().map(codes::CodeDoc::from).collect();
+    serde_wasm_bindgen::to_value(&docs)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 /// Get the version of the validator
 #[wasm_bindgen]
 pub fn get_version() -> String {
@@ -63,10 +282,84 @@ pub fn health_check() -> bool {
 }
 
 // Re-export for native Rust usage
+pub use ai_models::{AiCostEstimate, AiModel, ModelKind, AI_MODEL_CATALOG, PRICE_PER_RUN};
+pub use ai_usage::{analyze_ai_usage, AiRole, AiUsage};
+pub use baseline::{Baseline, BaselineEntry, BaselineFilter, BASELINE_FILE_NAME, BASELINE_VERSION};
+pub use codes::{explain, list_codes, CodeDoc, CodeInfo, DOCS_BASE_URL};
+pub use collection::{validate_collection, NEAR_DUPLICATE_SIMILARITY};
+pub use config::{
+    ConfigError,
+    IntegrationConfig,
+    ModuleConfig,
+    Override,
+    RuleSetting,
+    SdkConfig,
+    ThresholdOverrides,
+    Thresholds,
+    ValidatorConfig,
+    CONFIG_FILE_NAME,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use config::{discover_config, ConfigCache, LoadedConfig};
+pub use custom_rules::{CustomRule, RuleScope, CUSTOM_CATEGORY};
+pub use diagnostic::{Diagnostic, Fix, RelatedLocation, Severity, Span, TextEdit};
+pub use diff::{
+    diff_manifests,
+    diff_workflows,
+    Bump,
+    ChangeSeverity,
+    ChangeType,
+    DetectedChange,
+    VersionCheck,
+    WorkflowDiff,
+};
+pub use error_handling::ErrorHandlingCoverage;
+pub use integration_hosts::{integration_for_url, IntegrationHost, INTEGRATION_HOSTS};
+pub use literal::{parse_at as parse_literal_at, Literal, Property};
+#[cfg(not(target_arch = "wasm32"))]
+pub use sdk_deprecations::discover_sdk_version;
+pub use sdk_deprecations::{sdk_version_from_package_json, SdkDeprecation, LATEST_SDK_VERSION, SDK_DEPRECATIONS};
+pub use readiness::{
+    readiness,
+    score_readiness,
+    AreaScore,
+    Priority,
+    ReadinessArea,
+    ReadinessCheck,
+    ReadinessReport,
+    MIN_DESCRIPTION_LENGTH,
+};
+pub use report::{
+    CheckstyleReporter,
+    FileReport,
+    GitHubReporter,
+    JUnitReporter,
+    JsonLinesReporter,
+    JsonReporter,
+    ReportFormat,
+    Reporter,
+    TextReporter,
+};
+pub use manifest::{
+    extract_manifest,
+    InputManifest,
+    IntegrationManifest,
+    TriggerManifest,
+    WorkflowManifest,
+    MANIFEST_SCHEMA_VERSION,
+};
+pub use budget::{estimate_budget, BudgetEstimate, CpuBudget, SubrequestBudget, SUBREQUEST_LIMIT};
+pub use patterns::{named_patterns, KNOWN_INTEGRATIONS};
+pub use source::matching_close;
 pub use validator::{
     validate_workflow,
+    validate_workflow_with_config,
+    analyzer_names,
+    run_analyzer,
+    is_valid_cron,
     ValidationResult,
-    ValidationError,
-    ValidationWarning,
     WorkflowMetadata,
+    CRON_FIELDS,
 };
+#[cfg(not(target_arch = "wasm32"))]
+pub use validator::validate_file;
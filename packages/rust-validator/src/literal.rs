@@ -0,0 +1,389 @@
+//! Minimal reader for JavaScript object literals.
+//!
+//! Workflow definitions are mostly static data: `defineWorkflow({ name: '...',
+//! integrations: [...], trigger: schedule({ cron: '...' }) })`. This module
+//! reads that data into a [`Literal`] tree so the manifest extractor does not
+//! need one regex per field. Anything that is not plain data (functions,
+//! arithmetic, template interpolation) is kept as source text in
+//! [`Literal::Expr`].
+
+use serde_json::{Map, Number, Value};
+
+use crate::source::{matching_close, skip_comment, skip_string, skip_template};
+
+/// A property of an object literal
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub key: String,
+    pub value: Literal,
+    /// Byte offset of the key in the source
+    pub offset: usize,
+}
+
+/// A statically readable JavaScript value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    Null,
+    Array(Vec<Literal>),
+    Object(Vec<Property>),
+    /// A call such as `schedule({ ... })`; `callee` keeps dotted paths
+    Call { callee: String, args: Vec<Literal> },
+    /// Any other expression, as source text
+    Expr(String),
+}
+
+impl Literal {
+    /// Looks up a property of an object literal.
+    pub fn get(&self, key: &str) -> Option<&Literal> {
+        self.property(key).map(|p| &p.value)
+    }
+
+    /// Looks up a property (with its offset) of an object literal.
+    pub fn property(&self, key: &str) -> Option<&Property> {
+        match self {
+            Literal::Object(props) => props.iter().find(|p| p.key == key),
+            _ => None,
+        }
+    }
+
+    /// Properties of an object literal (empty for anything else).
+    pub fn properties(&self) -> &[Property] {
+        match self {
+            Literal::Object(props) => props,
+            _ => &[],
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Literal::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Literal::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Converts to JSON. Calls and expressions have no JSON form and become
+    /// `null`.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Literal::Str(s) => Value::String(s.clone()),
+            Literal::Num(n) => Number::from_f64(*n).map(Value::Number).unwrap_or(Value::Null),
+            Literal::Bool(b) => Value::Bool(*b),
+            Literal::Array(items) => Value::Array(items.iter().map(Literal::to_json).collect()),
+            Literal::Object(props) => {
+                let mut map = Map::new();
+                for p in props {
+                    map.insert(p.key.clone(), p.value.to_json());
+                }
+                Value::Object(map)
+            }
+            Literal::Null | Literal::Call { .. } | Literal::Expr(_) => Value::Null,
+        }
+    }
+}
+
+/// Skips whitespace and comments starting at `pos`.
+fn skip_trivia(content: &str, mut pos: usize) -> usize {
+    let bytes = content.as_bytes();
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b if b.is_ascii_whitespace() => pos += 1,
+            b'/' => match skip_comment(bytes, pos) {
+                Some(end) => pos = end + 1,
+                None => break,
+            },
+            _ => break,
+        }
+    }
+    pos
+}
+
+/// Skips an arbitrary expression and returns the offset of the delimiter that
+/// ends it (a top-level `,`, `;` or closing bracket, or end of input).
+fn skip_expression(content: &str, mut pos: usize) -> usize {
+    let bytes = content.as_bytes();
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b',' | b';' | b')' | b']' | b'}' => return pos,
+            b'(' | b'[' | b'{' => match matching_close(content, pos) {
+                Some(end) => pos = end,
+                None => return bytes.len(),
+            },
+            b'\'' | b'"' => pos = skip_string(bytes, pos),
+            b'`' => pos = skip_template(content, pos),
+            b'/' => {
+                if let Some(end) = skip_comment(bytes, pos) {
+                    pos = end;
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+    bytes.len()
+}
+
+fn is_ident_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+/// Reads an identifier (or dotted path when `dotted`) at `pos`.
+fn read_ident(content: &str, pos: usize, dotted: bool) -> usize {
+    let bytes = content.as_bytes();
+    let mut end = pos;
+    while end < bytes.len() && (is_ident_char(bytes[end]) || (dotted && bytes[end] == b'.')) {
+        end += 1;
+    }
+    end
+}
+
+/// The text between a string's quotes. Unterminated strings end at a newline
+/// or the end of the file, which may fall inside a multi-byte character's
+/// bytes, so the closing quote is only removed if it is there.
+fn quoted_text(raw: &str) -> &str {
+    let rest = &raw[1..];
+    let quote = raw.as_bytes()[0] as char;
+    rest.strip_suffix(quote).or_else(|| rest.strip_suffix('\n')).unwrap_or(rest)
+}
+
+/// Decodes a quoted string literal (including its quotes).
+fn unquote(raw: &str) -> String {
+    let inner = quoted_text(raw);
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Parses comma separated values up to the bracket closing at `close`.
+fn parse_list(content: &str, mut pos: usize, close: usize) -> Vec<Literal> {
+    let mut items = Vec::new();
+    loop {
+        pos = skip_trivia(content, pos);
+        if pos >= close {
+            break;
+        }
+        let (item, end) = parse_at(content, pos);
+        items.push(item);
+        pos = skip_trivia(content, end);
+        if content.as_bytes().get(pos) == Some(&b',') {
+            pos += 1;
+        } else {
+            break;
+        }
+    }
+    items
+}
+
+/// Parses the properties of an object literal whose `{` is at `open`.
+fn parse_object(content: &str, open: usize, close: usize) -> Vec<Property> {
+    let bytes = content.as_bytes();
+    let mut props = Vec::new();
+    let mut pos = open + 1;
+
+    loop {
+        pos = skip_trivia(content, pos);
+        if pos >= close {
+            break;
+        }
+
+        let offset = pos;
+        let key_end;
+        let key = match bytes[pos] {
+            b'\'' | b'"' => {
+                key_end = skip_string(bytes, pos) + 1;
+                unquote(&content[pos..key_end])
+            }
+            b'.' => {
+                // Spread: ...defaults
+                key_end = skip_expression(content, pos);
+                pos = key_end;
+                if bytes.get(pos) == Some(&b',') {
+                    pos += 1;
+                }
+                continue;
+            }
+            b'[' => {
+                // Computed key: skip the whole property
+                pos = skip_expression(content, pos);
+                if bytes.get(pos) == Some(&b',') {
+                    pos += 1;
+                }
+                continue;
+            }
+            _ => {
+                key_end = read_ident(content, pos, false);
+                if key_end == pos {
+                    break;
+                }
+                content[pos..key_end].to_string()
+            }
+        };
+
+        let mut next = skip_trivia(content, key_end);
+        let (key, value, end) = match bytes.get(next) {
+            Some(b':') => {
+                let (value, end) = parse_at(content, next + 1);
+                (key, value, end)
+            }
+            Some(b',') | Some(b'}') => (key.clone(), Literal::Expr(key), next),
+            _ => {
+                // Method shorthand, possibly prefixed: async execute(...) { ... }
+                let mut name = key;
+                if matches!(name.as_str(), "async" | "get" | "set") && bytes.get(next).is_some_and(|&b| is_ident_char(b)) {
+                    let name_end = read_ident(content, next, false);
+                    name = content[next..name_end].to_string();
+                    next = skip_trivia(content, name_end);
+                }
+                let end = skip_expression(content, next);
+                (name, Literal::Expr(content[offset..end].trim().to_string()), end)
+            }
+        };
+
+        props.push(Property { key, value, offset });
+        pos = skip_trivia(content, end);
+        if bytes.get(pos) == Some(&b',') {
+            pos += 1;
+        } else if pos < close {
+            // Unexpected token: resynchronise on the next top-level comma
+            pos = skip_expression(content, pos);
+            if bytes.get(pos) == Some(&b',') {
+                pos += 1;
+            }
+        }
+    }
+
+    props
+}
+
+/// Parses the value starting at `pos` and returns it with the offset just past
+/// it. Trailing `as const` / `as Type` assertions are ignored.
+pub fn parse_at(content: &str, pos: usize) -> (Literal, usize) {
+    let pos = skip_trivia(content, pos);
+    let bytes = content.as_bytes();
+    let Some(&first) = bytes.get(pos) else {
+        return (Literal::Null, pos);
+    };
+
+    let (value, end) = match first {
+        b'\'' | b'"' => {
+            let end = skip_string(bytes, pos) + 1;
+            (Literal::Str(unquote(&content[pos..end])), end)
+        }
+        b'`' => {
+            let end = skip_template(content, pos) + 1;
+            let raw = &content[pos..end];
+            if raw.contains("${") {
+                (Literal::Expr(raw.to_string()), end)
+            } else {
+                (Literal::Str(quoted_text(raw).to_string()), end)
+            }
+        }
+        b'[' => match matching_close(content, pos) {
+            Some(close) => (Literal::Array(parse_list(content, pos + 1, close)), close + 1),
+            None => (Literal::Expr(content[pos..].to_string()), bytes.len()),
+        },
+        b'{' => match matching_close(content, pos) {
+            Some(close) => (Literal::Object(parse_object(content, pos, close)), close + 1),
+            None => (Literal::Expr(content[pos..].to_string()), bytes.len()),
+        },
+        b'0'..=b'9' | b'-' | b'.' => {
+            let mut end = pos + 1;
+            while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'.' || bytes[end] == b'_') {
+                end += 1;
+            }
+            match content[pos..end].replace('_', "").parse::<f64>() {
+                Ok(n) => (Literal::Num(n), end),
+                Err(_) => (Literal::Expr(content[pos..end].to_string()), end),
+            }
+        }
+        c if is_ident_char(c) => {
+            let ident_end = read_ident(content, pos, true);
+            let ident = &content[pos..ident_end];
+            let after = skip_trivia(content, ident_end);
+            match (ident, bytes.get(after)) {
+                ("true", _) => (Literal::Bool(true), ident_end),
+                ("false", _) => (Literal::Bool(false), ident_end),
+                ("null" | "undefined", _) => (Literal::Null, ident_end),
+                (_, Some(b'(')) if !matches!(ident, "async" | "function") => match matching_close(content, after) {
+                    Some(close) => (
+                        Literal::Call { callee: ident.to_string(), args: parse_list(content, after + 1, close) },
+                        close + 1,
+                    ),
+                    None => (Literal::Expr(content[pos..].to_string()), bytes.len()),
+                },
+                _ => (Literal::Expr(ident.to_string()), ident_end),
+            }
+        }
+        _ => {
+            let end = skip_expression(content, pos);
+            return (Literal::Expr(content[pos..end].trim().to_string()), end);
+        }
+    };
+
+    // Anything other than a delimiter means the value was part of a larger
+    // expression (arrow function, ternary, arithmetic...)
+    let after = skip_trivia(content, end);
+    match bytes.get(after) {
+        None | Some(b',' | b';' | b')' | b']' | b'}') => (value, end),
+        _ if content[after..].starts_with("as ") => (value, skip_expression(content, after)),
+        _ => {
+            let end = skip_expression(content, pos);
+            (Literal::Expr(content[pos..end].trim().to_string()), end)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workflow_shape() {
+        let src = r#"{
+            name: 'Test', // comment
+            'quoted-key': "x",
+            version: `1.0.0`,
+            price: 10.5,
+            integrations: [{ service: 'slack', scopes: ['chat:write'] }, 'notion'],
+            trigger: schedule({ cron: '0 8 * * *' }),
+            visibility: 'public' as const,
+            async execute({ inputs }) { return { ok: inputs.a > 1 }; },
+            onError: async ({ error }) => { console.log(error); },
+        }"#;
+        let (value, _) = parse_at(src, 0);
+
+        assert_eq!(value.get("name").and_then(Literal::as_str), Some("Test"));
+        assert_eq!(value.get("quoted-key").and_then(Literal::as_str), Some("x"));
+        assert_eq!(value.get("version").and_then(Literal::as_str), Some("1.0.0"));
+        assert_eq!(value.get("price"), Some(&Literal::Num(10.5)));
+        assert_eq!(value.get("visibility").and_then(Literal::as_str), Some("public"));
+        assert!(matches!(value.get("trigger"), Some(Literal::Call { callee, .. }) if callee == "schedule"));
+        assert!(matches!(value.get("execute"), Some(Literal::Expr(_))));
+        assert!(matches!(value.get("onError"), Some(Literal::Expr(_))));
+
+        let integrations = value.get("integrations").unwrap().to_json();
+        assert_eq!(integrations[0]["scopes"][0], "chat:write");
+        assert_eq!(integrations[1], "notion");
+    }
+}
@@ -0,0 +1,162 @@
+//! Sensitive data in log statements.
+//!
+//! Console output from every run is shipped to the log pipeline, where it is
+//! kept and searchable long after the run. This module reads the arguments of
+//! each `console.*` call, including `${...}` in template literals, and flags
+//! credentials and personal data by the names they are read through: a token
+//! or secret, the `env` object, an integration client, a whole trigger
+//! payload, or an email or phone field.
+
+use crate::diagnostic::Diagnostic;
+use crate::patterns::*;
+use crate::scan::Scan;
+use crate::source::{dotted, identifiers, matching_close};
+
+/// Name endings (lowercase, without `_`) that hold a credential
+const SECRET_SUFFIXES: [&str; 9] = [
+    "token",
+    "apikey",
+    "secret",
+    "password",
+    "passwd",
+    "authorization",
+    "credential",
+    "credentials",
+    "privatekey",
+];
+
+/// Token names that are cursors rather than credentials
+const CURSOR_SUFFIXES: [&str; 4] = ["pagetoken", "synctoken", "nexttoken", "continuationtoken"];
+
+/// Name endings (lowercase, without `_`) that hold contact details
+const PERSONAL_SUFFIXES: [&str; 7] = ["email", "emailaddress", "phone", "phonenumber", "phonecell", "mobile", "mobilenumber"];
+
+/// Methods that return their receiver's value in another form
+const PRESERVING_METHODS: [&str; 5] = ["toLowerCase", "toUpperCase", "trim", "toString", "normalize"];
+
+/// Properties of `trigger` that hold the whole incoming payload
+const PAYLOAD_PROPERTIES: [&str; 3] = ["data", "payload", "body"];
+
+enum Leak {
+    /// A credential, with a description of what was logged
+    Secret(String),
+    /// Personal data or a whole payload
+    Personal(String),
+}
+
+/// Classifies the value read through `path` (`participant.email`, `env`).
+fn classify(path: &str) -> Option<Leak> {
+    let segments: Vec<&str> = path.split('.').map(|s| s.trim_end_matches('?')).collect();
+    let last = segments.last()?.to_lowercase().replace('_', "");
+    match segments.as_slice() {
+        ["env"] | ["process", "env"] => return Some(Leak::Secret(format!("the env object ({})", path))),
+        ["integrations"] | ["integrations", _] => {
+            return Some(Leak::Secret(format!("an integration client with its credentials ({})", path)))
+        }
+        ["trigger"] | ["payload"] => return Some(Leak::Personal(format!("the whole trigger payload ({})", path))),
+        ["trigger", property] if PAYLOAD_PROPERTIES.contains(property) => {
+            return Some(Leak::Personal(format!("the whole trigger payload ({})", path)))
+        }
+        _ => {}
+    }
+    let secret = (SECRET_SUFFIXES.iter().any(|s| last.ends_with(s)) || last == "auth" || last == "oauth")
+        && !CURSOR_SUFFIXES.iter().any(|s| last.ends_with(s));
+    if secret {
+        return Some(Leak::Secret(format!("a credential ({})", path)));
+    }
+    if PERSONAL_SUFFIXES.iter().any(|s| last.ends_with(s)) {
+        return Some(Leak::Personal(format!("personal data ({})", path)));
+    }
+    None
+}
+
+pub(crate) fn validate_logging(scan: &Scan, diagnostics: &mut Vec<Diagnostic>) {
+    // Log messages are strings; only the values interpolated or passed count
+    let (content, code) = (scan.content(), scan.code());
+    for m in CONSOLE_CALL.find_iter(code) {
+        let open = m.end() - 1;
+        let Some(close) = matching_close(code, open) else {
+            continue;
+        };
+        let call = m.as_str().trim_end_matches(|c: char| c == '(' || c.is_whitespace());
+        let args = &code[open + 1..close];
+        let mut seen: Vec<&str> = Vec::new();
+        for (offset, _) in identifiers(args) {
+            let mut path = dotted(args, offset);
+            let after = args[offset + path.len()..].trim_start();
+            let before = args[..offset].trim_end();
+            if after.starts_with('(') {
+                // `email.toLowerCase()` still logs the email
+                match path.rsplit_once('.') {
+                    Some((receiver, method)) if PRESERVING_METHODS.contains(&method) => {
+                        path = receiver.trim_end_matches('?');
+                    }
+                    _ => continue,
+                }
+            }
+            // Element reads log something else; `!token` logs a boolean; `{ phone: x }` names a key
+            let key = after.starts_with(':') && (before.ends_with('{') || before.ends_with(','));
+            if key || after.starts_with('[') || before.ends_with('!') || before.ends_with("typeof") || seen.contains(&path) {
+                continue;
+            }
+            seen.push(path);
+            let span = open + 1 + offset..open + 1 + offset + path.len();
+            let diagnostic = match classify(path) {
+                Some(Leak::Secret(what)) => Diagnostic::error("SECRET_LOGGED", &format!("{}() logs {}", call, what))
+                    .with_suggestion("Remove it from the log; log a flag such as hasToken or the last 4 characters instead"),
+                Some(Leak::Personal(what)) => Diagnostic::warning("PII_LOGGED", &format!("{}() logs {}", call, what))
+                    .with_suggestion("Log a record id or selected non-personal fields instead"),
+                None => continue,
+            };
+            diagnostics.push(diagnostic.with_span(content, span));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(content: &str) -> Vec<(String, String)> {
+        let mut diagnostics = Vec::new();
+        validate_logging(&Scan::new(content), &mut diagnostics);
+        diagnostics.into_iter().map(|d| (d.code, d.message)).collect()
+    }
+
+    #[test]
+    fn test_flags_credentials_and_personal_data() {
+        let content = r#"
+console.log('Token:', tokens.slack.access_token);
+console.log(env, JSON.stringify(trigger.data));
+console.error(`Failed to notify ${contact.email} via ${integrations.slack}`, error);
+console.info({ phone: patient.phone_cell, apiKey: env.STRIPE_API_KEY });
+console.log(`Invited ${user.email.toLowerCase()}`);
+"#;
+        let found = found(content);
+        let expected = [
+            ("SECRET_LOGGED", "console.log() logs a credential (tokens.slack.access_token)"),
+            ("SECRET_LOGGED", "console.log() logs the env object (env)"),
+            ("PII_LOGGED", "console.log() logs the whole trigger payload (trigger.data)"),
+            ("PII_LOGGED", "console.error() logs personal data (contact.email)"),
+            ("SECRET_LOGGED", "console.error() logs an integration client with its credentials (integrations.slack)"),
+            ("PII_LOGGED", "console.info() logs personal data (patient.phone_cell)"),
+            ("SECRET_LOGGED", "console.info() logs a credential (env.STRIPE_API_KEY)"),
+            ("PII_LOGGED", "console.log() logs personal data (user.email)"),
+        ];
+        let found: Vec<(&str, &str)> = found.iter().map(|(c, m)| (c.as_str(), m.as_str())).collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_ignores_safe_values() {
+        let content = r#"
+// console.log(env) in a comment
+console.log('Sending email to the token holder');
+console.log(`Synced ${emails.length} emails, next page ${nextPageToken}`);
+console.log('Has token:', !!accessToken, typeof env.API_KEY);
+console.log(trigger.data.id, trigger.type, integrations.slack.chat.postMessage(msg));
+console.log(contact.email.split('@')[1]);
+"#;
+        assert!(found(content).is_empty());
+    }
+}
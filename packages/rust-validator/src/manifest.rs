@@ -0,0 +1,340 @@
+//! Workflow manifest extraction.
+//!
+//! Publishing needs a machine-readable description of a workflow. The manifest
+//! collects everything the validator can determine statically from the
+//! `defineWorkflow({ ... })` call and the `export const metadata = { ... }`
+//! block into one normalized structure. The JSON shape is versioned by
+//! [`MANIFEST_SCHEMA_VERSION`] and described by `schema/manifest.schema.json`;
+//! fields are always present (`null` when unknown) so consumers can rely on
+//! them.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::literal::{parse_at, Literal};
+use crate::patterns::*;
+
+/// Version of the manifest JSON schema. Bump the major version for any
+/// change that removes or retypes a field.
+pub const MANIFEST_SCHEMA_VERSION: &str = "1.0.0";
+
+/// Workflow types the schema allows; any other value is reported as `null`
+const WORKFLOW_TYPES: [&str; 3] = ["integration", "ai-enhanced", "ai-native"];
+
+/// Trigger types the schema allows; triggers of any other type are left out
+const TRIGGER_TYPES: [&str; 4] = ["webhook", "schedule", "manual", "poll"];
+
+/// Normalized workflow manifest
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowManifest {
+    pub schema_version: String,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    #[serde(rename = "type")]
+    pub workflow_type: Option<String>,
+    /// Primary trigger first, followed by any `webhooks: [...]`
+    pub triggers: Vec<TriggerManifest>,
+    /// Declared `inputs` (or legacy `config` / `configFields`) in order
+    pub inputs: Vec<InputManifest>,
+    pub integrations: Vec<IntegrationManifest>,
+    pub pricing: Option<Value>,
+    /// The `metadata` object (from `defineWorkflow` or `export const metadata`)
+    pub metadata: Option<Value>,
+    pub pathway: Option<Value>,
+    pub visibility: Option<String>,
+    pub deprecated: bool,
+    pub superseded_by: Option<String>,
+    pub has_ai: bool,
+    pub has_on_error: bool,
+}
+
+/// A workflow trigger
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerManifest {
+    /// `webhook`, `schedule`, `manual` or `poll`
+    #[serde(rename = "type")]
+    pub trigger_type: String,
+    pub service: Option<String>,
+    pub events: Vec<String>,
+    pub cron: Option<String>,
+    pub timezone: Option<String>,
+    /// The full trigger options object
+    pub config: Value,
+}
+
+/// A user-configurable input
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputManifest {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub input_type: Option<String>,
+    pub label: Option<String>,
+    pub required: bool,
+    pub default: Option<Value>,
+    /// `inputs`, `config` or `configFields`
+    pub source: String,
+    /// The full input definition, or `null` when it is not an object
+    pub schema: Value,
+}
+
+/// A required integration
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationManifest {
+    pub service: String,
+    pub scopes: Vec<String>,
+    pub optional: bool,
+}
+
+impl WorkflowManifest {
+    /// Serializes the manifest as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Reads the object literal passed to `defineWorkflow(...)`, falling back to
+/// `export default { ... }`.
+///
+/// `defineWorkflow(` can also appear in comments and docs, so the first object
+/// literal that looks like a workflow (has a trigger or execute) wins.
+pub fn workflow_definition(content: &str) -> Option<Literal> {
+    let candidates: Vec<Literal> = DEFINE_WORKFLOW
+        .find_iter(content)
+        .chain(EXPORT_DEFAULT.find_iter(content))
+        .filter_map(|m| match parse_at(content, m.end()) {
+            (value @ Literal::Object(_), _) => Some(value),
+            _ => None,
+        })
+        .collect();
+
+    let looks_like_workflow = |v: &Literal| v.get("trigger").is_some() || v.get("execute").is_some();
+    match candidates.iter().position(looks_like_workflow) {
+        Some(i) => candidates.into_iter().nth(i),
+        None => candidates.into_iter().next(),
+    }
+}
+
+/// Reads the `export const metadata = { ... }` block, if present.
+pub fn exported_metadata(content: &str) -> Option<Literal> {
+    let m = EXPORTED_METADATA.find(content)?;
+    match parse_at(content, m.end()) {
+        (value @ Literal::Object(_), _) => Some(value),
+        _ => None,
+    }
+}
+
+fn string_list(value: Option<&Literal>) -> Vec<String> {
+    match value {
+        Some(Literal::Array(items)) => items.iter().filter_map(|i| i.as_str().map(str::to_string)).collect(),
+        Some(Literal::Str(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// Normalizes a trigger expression (`webhook({...})`, `schedule('...')`,
+/// `cron({...})`, `{ type: '...' }`).
+fn normalize_trigger(value: &Literal) -> Option<TriggerManifest> {
+    let (trigger_type, options) = match value {
+        Literal::Call { callee, args } => {
+            let kind = match callee.rsplit('.').next().unwrap_or_default() {
+                "cron" | "schedule" => "schedule",
+                kind @ ("webhook" | "manual" | "poll") => kind,
+                _ => return None,
+            };
+            (kind.to_string(), args.first().cloned())
+        }
+        Literal::Object(_) => {
+            let kind = match value.get("type")?.as_str()? {
+                "cron" => "schedule",
+                kind => TRIGGER_TYPES.iter().find(|t| **t == kind)?,
+            };
+            (kind.to_string(), Some(value.clone()))
+        }
+        _ => return None,
+    };
+
+    let mut trigger = TriggerManifest {
+        trigger_type,
+        config: Value::Null,
+        ..Default::default()
+    };
+
+    match options {
+        Some(Literal::Str(cron)) => {
+            trigger.cron = Some(cron.clone());
+            trigger.config = serde_json::json!({ "cron": cron });
+        }
+        Some(options @ Literal::Object(_)) => {
+            trigger.service = options.get("service").and_then(Literal::as_str).map(str::to_string);
+            trigger.events = string_list(options.get("events"));
+            trigger.events.extend(string_list(options.get("event")));
+            trigger.cron = options
+                .get("cron")
+                .or_else(|| options.get("schedule"))
+                .and_then(Literal::as_str)
+                .map(str::to_string);
+            trigger.timezone = options.get("timezone").and_then(Literal::as_str).map(str::to_string);
+            trigger.config = options.to_json();
+        }
+        _ => {}
+    }
+
+    Some(trigger)
+}
+
+fn normalize_input(key: &str, def: &Literal, source: &str) -> InputManifest {
+    InputManifest {
+        key: key.to_string(),
+        input_type: def.get("type").and_then(Literal::as_str).map(str::to_string),
+        label: def.get("label").and_then(Literal::as_str).map(str::to_string),
+        required: def.get("required").and_then(Literal::as_bool).unwrap_or(false),
+        default: def.get("default").map(Literal::to_json),
+        source: source.to_string(),
+        schema: match def {
+            Literal::Object(_) => def.to_json(),
+            _ => Value::Null,
+        },
+    }
+}
+
+fn normalize_integration(value: &Literal) -> Option<IntegrationManifest> {
+    match value {
+        Literal::Str(service) => Some(IntegrationManifest {
+            service: service.to_lowercase(),
+            ..Default::default()
+        }),
+        Literal::Object(_) => Some(IntegrationManifest {
+            service: value.get("service")?.as_str()?.to_lowercase(),
+            scopes: string_list(value.get("scopes")),
+            optional: value.get("optional").and_then(Literal::as_bool).unwrap_or(false),
+        }),
+        _ => None,
+    }
+}
+
+/// Extracts the normalized manifest from workflow source.
+pub fn extract_manifest(content: &str) -> WorkflowManifest {
+    let mut manifest = WorkflowManifest {
+        schema_version: MANIFEST_SCHEMA_VERSION.to_string(),
+        has_ai: AI_USAGE.is_match(content),
+        ..Default::default()
+    };
+
+    let definition = workflow_definition(content).unwrap_or(Literal::Object(Vec::new()));
+    let exported = exported_metadata(content);
+    let inline_metadata = definition.get("metadata").filter(|m| matches!(m, Literal::Object(_)));
+    let meta = |key: &str| {
+        inline_metadata
+            .and_then(|m| m.get(key))
+            .or_else(|| exported.as_ref().and_then(|m| m.get(key)))
+            .and_then(Literal::as_str)
+            .map(str::to_string)
+    };
+    let text = |key: &str| definition.get(key).and_then(Literal::as_str).map(str::to_string);
+
+    manifest.id = meta("id");
+    manifest.name = text("name").or_else(|| meta("name"));
+    manifest.description = text("description").or_else(|| meta("description"));
+    manifest.version = text("version").or_else(|| meta("version"));
+    manifest.workflow_type = text("type").filter(|t| WORKFLOW_TYPES.contains(&t.as_str()));
+    manifest.visibility = meta("visibility").or_else(|| text("visibility"));
+    manifest.deprecated = definition.get("deprecated").and_then(Literal::as_bool).unwrap_or(false);
+    manifest.superseded_by = text("supersededBy");
+    manifest.has_on_error = definition.get("onError").is_some();
+
+    manifest.triggers.extend(definition.get("trigger").and_then(normalize_trigger));
+    if let Some(Literal::Array(webhooks)) = definition.get("webhooks") {
+        manifest.triggers.extend(webhooks.iter().filter_map(normalize_trigger));
+    }
+
+    for source in ["inputs", "config"] {
+        for prop in definition.get(source).map(Literal::properties).unwrap_or_default() {
+            manifest.inputs.push(normalize_input(&prop.key, &prop.value, source));
+        }
+    }
+    if let Some(Literal::Array(fields)) = definition.get("configFields") {
+        for field in fields {
+            if let Some(key) = field.get("key").and_then(Literal::as_str) {
+                manifest.inputs.push(normalize_input(key, field, "configFields"));
+            }
+        }
+    }
+
+    if let Some(Literal::Array(items)) = definition.get("integrations") {
+        manifest.integrations = items.iter().filter_map(normalize_integration).collect();
+    }
+
+    manifest.pricing = definition.get("pricing").map(Literal::to_json);
+    manifest.pathway = definition.get("pathway").map(Literal::to_json);
+    manifest.metadata = inline_metadata.or(exported.as_ref()).map(Literal::to_json);
+
+    manifest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_manifest() {
+        let content = r#"
+import { defineWorkflow, webhook, cron } from '@workwayco/sdk';
+
+export default defineWorkflow({
+    name: 'PR Notifier',
+    version: '1.2.0',
+    type: 'integration',
+    integrations: ['github', { service: 'slack', scopes: ['chat:write'], optional: true }],
+    inputs: {
+        channel: { type: 'string', label: 'Channel', required: true },
+        mention: { type: 'boolean', label: 'Mention', default: false },
+    },
+    trigger: webhook({ service: 'github', events: ['pull_request.opened'] }),
+    webhooks: [cron({ schedule: '0 9 * * 1', timezone: 'UTC' })],
+    pricing: { model: 'usage', pricePerExecution: 0.01 },
+    async execute({ inputs }) { return { success: true }; },
+});
+
+export const metadata = { id: 'pr-notifier', visibility: 'public' as const };
+"#;
+        let manifest = extract_manifest(content);
+
+        assert_eq!(manifest.schema_version, MANIFEST_SCHEMA_VERSION);
+        assert_eq!(manifest.id.as_deref(), Some("pr-notifier"));
+        assert_eq!(manifest.visibility.as_deref(), Some("public"));
+        assert_eq!(manifest.triggers.len(), 2);
+        assert_eq!(manifest.triggers[0].events, vec!["pull_request.opened"]);
+        assert_eq!(manifest.triggers[1].trigger_type, "schedule");
+        assert_eq!(manifest.triggers[1].cron.as_deref(), Some("0 9 * * 1"));
+        assert_eq!(manifest.inputs[0].key, "channel");
+        assert!(manifest.inputs[0].required);
+        assert_eq!(manifest.inputs[1].default, Some(Value::Bool(false)));
+        assert_eq!(manifest.integrations[1].scopes, vec!["chat:write"]);
+        assert!(manifest.integrations[1].optional);
+        assert_eq!(manifest.pricing.as_ref().unwrap()["model"], "usage");
+    }
+
+    #[test]
+    fn test_values_outside_the_schema_are_null() {
+        let content = r#"
+export default defineWorkflow({
+    type: 'experimental',
+    trigger: { type: 'email' },
+    webhooks: [{ type: 'cron', cron: '0 * * * *' }],
+    inputs: { channel: 'general' },
+    async execute() { return { success: true }; },
+});
+"#;
+        let manifest = extract_manifest(content);
+        assert_eq!(manifest.workflow_type, None);
+        assert_eq!(manifest.triggers.len(), 1);
+        assert_eq!(manifest.triggers[0].trigger_type, "schedule");
+        assert_eq!(manifest.inputs[0].schema, Value::Null);
+    }
+}
@@ -26,6 +26,11 @@ pub static WORKERS_AI_IMPORT: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"@workway/sdk/workers-ai").unwrap()
 });
 
+/// Extracts the named imports from the SDK: import { a, b } from '@workway/sdk'
+pub static SDK_NAMED_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"import\s*\{([^}]*)\}\s*from\s*['"]@workway(?:co)?/sdk['"]"#).unwrap()
+});
+
 // ============================================================================
 // WORKFLOW DEFINITION PATTERNS
 // ============================================================================
@@ -40,6 +45,11 @@ pub static EXPORT_DEFAULT: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"export\s+default").unwrap()
 });
 
+/// Matches the exported metadata block: export const metadata = { ... }
+pub static EXPORTED_METADATA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"export\s+const\s+metadata\s*(?::\s*[\w.<>]+\s*)?=").unwrap()
+});
+
 /// Extracts workflow name from name: 'xxx' pattern
 pub static WORKFLOW_NAME: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"name:\s*['"`]([^'"`]+)['"`]"#).unwrap()
@@ -112,6 +122,22 @@ pub static WEBHOOK_CONFIG: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"webhook\s*\(\s*\{([^}]+)\}").unwrap()
 });
 
+/// Matches a call to the deprecated cron() trigger alias (up to the paren)
+pub static CRON_ALIAS_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bcron\s*\(").unwrap()
+});
+
+/// Matches a call to the deprecated `storage.put()` (`put` is group 1)
+pub static STORAGE_PUT_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[^\w$.])(?:(?:ctx|context)\s*\.\s*)?storage\s*\??\.\s*(put)\s*\(").unwrap()
+});
+
+/// Matches a read of a top-level `ActionResult` pagination field, which
+/// moved under `metadata` (the name read from is group 1, the field group 2)
+pub static PAGINATION_READ: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[^\w$.])([A-Za-z_$][\w$]*)\s*(?:\?\.|\.)\s*(total|hasMore|cursor)\b").unwrap()
+});
+
 /// Extracts schedule expression
 pub static SCHEDULE_EXPR: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"schedule\s*\(\s*['"`]([^'"`]+)['"`]"#).unwrap()
@@ -150,6 +176,66 @@ pub static EXTERNAL_AI: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)claude|gpt-4|openai|anthropic|gemini").unwrap()
 });
 
+/// Extracts AIModels.X constant references (ASCII word boundary: this is a
+/// scan probe, and a Unicode `\b` would keep the whole set off the DFA)
+pub static AI_MODEL_CONSTANT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?-u:\b)AIModels\.([A-Z0-9_]+)").unwrap()
+});
+
+/// Extracts raw Workers AI model ids: '@cf/meta/llama-3-8b-instruct'
+pub static AI_MODEL_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"['"`](@(?:cf|hf)/[\w.\-]+/[\w.\-]+)['"`]"#).unwrap()
+});
+
+/// Extracts max_tokens from an AI call
+pub static MAX_TOKENS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"max_tokens\s*:\s*(\d+)").unwrap()
+});
+
+/// Matches a Workers AI call: ai.generateText(, env.AI.run(, integrations.ai.x(
+pub static AI_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:AI|ai)\.([\w$]+)\s*\(").unwrap()
+});
+
+/// Matches a named helper function up to its parameter list's `(`
+/// (group 1 for `function name(`, group 2 for `const name = async (`)
+pub static FUNCTION_DECL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bfunction\s*\*?\s*([\w$]+)\s*(?:<[^>]*>)?\s*\(|\b(?:const|let|var)\s+([\w$]+)\s*=\s*(?:async\s*)?(?:function\s*)?\(").unwrap()
+});
+
+/// Matches the binding a call's result is assigned to, at the end of the text
+/// before the call: `const result = await `, `let { data } = `, `summary = `
+pub static RESULT_BINDING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\b(?:const|let|var)\s+)?(\{[^{}]*\}|[\w$]+)\s*(?::\s*[^=;]+)?=\s*(?:await\s+)?(?:\(\s*)?$").unwrap()
+});
+
+/// Matches a check of a call's outcome on its result (the name is group 1):
+/// `result.success`, `response?.ok`
+pub static RESULT_CHECK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b([A-Za-z_$][\w$]*)\s*\??\.\s*(?:success|ok|error|status)\b").unwrap()
+});
+
+/// Matches the start of a call (the callee's name is group 1)
+pub static CALL_START: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b([A-Za-z_$][\w$]*)\s*\(").unwrap()
+});
+
+/// Matches a declaration or assignment with its right-hand side up to the end
+/// of the line
+pub static ASSIGNMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)(?:\b(?:const|let|var)\s+|^\s*)(\{[^{}]*\}|[\w$]+)\s*(?::\s*[^=;\n]+)?=\s*([^=>\n][^;\n]*)").unwrap()
+});
+
+/// Matches the start of a condition: if (, while (, switch (
+pub static CONDITION_START: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:if|while|switch)\s*\(").unwrap()
+});
+
+/// Matches a comparison or predicate call that turns a value into a decision
+pub static COMPARISON: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[=!]==?|[<>]=?|\.(?:includes|startsWith|endsWith|test|has|some|every)\s*\(").unwrap()
+});
+
 /// Matches env access in execute function
 pub static ENV_ACCESS: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"env\s*[,})]|context\.env|\{ env \}").unwrap()
@@ -164,6 +250,11 @@ pub static CONSOLE_STATEMENTS: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"console\.(log|error|warn)").unwrap()
 });
 
+/// Matches any console logging call (up to the opening paren)
+pub static CONSOLE_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bconsole\.(?:log|error|warn|info|debug)\s*\(").unwrap()
+});
+
 /// Matches await in for loop
 pub static AWAIT_IN_FOR_LOOP: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?s)for\s*\([^)]+\)\s*\{[^}]*await[^}]*\}").unwrap()
@@ -179,6 +270,90 @@ pub static EMPTY_CATCH: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?s)catch\s*\([^)]*\)\s*\{\s*\}").unwrap()
 });
 
+// ============================================================================
+// SUBREQUEST BUDGET PATTERNS
+// ============================================================================
+
+/// Matches outbound call sites: fetch(), integration clients and http helpers
+pub static OUTBOUND_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bfetch\s*\(|\bintegrations\.[A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)+\s*\(|\bhttp\.(?:get|post|put|patch|delete|request)\s*\(").unwrap()
+});
+
+/// Matches fetch() and http helper calls (up to the opening paren)
+pub static RAW_HTTP_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bfetch\s*\(|\bhttp\.(?:get|post|put|patch|delete|request)\s*\(").unwrap()
+});
+
+/// Matches for/while loop headers (up to the opening paren)
+pub static LOOP_STATEMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:for(?:\s+await)?|while)\s*\(").unwrap()
+});
+
+/// Matches do { ... } while loops (up to the opening brace)
+pub static DO_LOOP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bdo\s*\{").unwrap()
+});
+
+/// Matches array iteration callbacks (up to the opening paren)
+pub static ITERATOR_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\.(?:forEach|map|flatMap|filter|reduce|some|every|find)\s*\(").unwrap()
+});
+
+/// Matches Promise.all-style fan-out (up to the opening paren)
+pub static PROMISE_ALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bPromise\.(?:all|allSettled|race|any)\s*\(").unwrap()
+});
+
+/// Extracts literal bounds from a counter loop header: let i = 0; i < 10
+pub static FOR_COUNTER_BOUND: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:let|var)\s+[\w$]+\s*=\s*(\d+)\s*;\s*[\w$]+\s*(<=?)\s*(\d+)\s*;").unwrap()
+});
+
+/// Extracts the literal array from a for...of header: const x of ['a', 'b']
+pub static FOR_OF_LITERAL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)\bof\s+\[([^\[\]]*)\]\s*$").unwrap()
+});
+
+/// Extracts an explicit cap on a list: .slice(0, 10)
+pub static SLICE_BOUND: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\.slice\(\s*0\s*,\s*(\d+)\s*\)\s*$").unwrap()
+});
+
+/// Matches CPU-heavy operations that add up when repeated inside loops
+pub static CPU_HEAVY_OP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"JSON\.(?:parse|stringify)\s*\(|new\s+RegExp\s*\(|crypto\.subtle\.|\.sort\s*\(|\.generateText\s*\(|\bAI\.run\s*\(").unwrap()
+});
+
+// ============================================================================
+// ERROR HANDLING PATTERNS
+// ============================================================================
+
+/// Matches the start of a try block (up to the opening brace)
+pub static TRY_BLOCK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\btry\s*\{").unwrap()
+});
+
+/// Matches the start of a catch block, with or without a binding (up to the
+/// opening brace)
+pub static CATCH_BLOCK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bcatch\s*(?:\([^)]*\))?\s*\{").unwrap()
+});
+
+/// Matches an onError handler, inline or by reference
+pub static HAS_ON_ERROR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bonError\s*[:(]").unwrap()
+});
+
+/// Matches the SDK retry helpers (up to the opening paren)
+pub static RETRY_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:withRetry|fetchWithRetry)\s*\(").unwrap()
+});
+
+/// Matches throwing a plain Error or a non-error value
+pub static GENERIC_THROW: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bthrow\s+(?:new\s+(?:Error|TypeError|RangeError)\s*\(|['"`{])"#).unwrap()
+});
+
 // ============================================================================
 // SECRET DETECTION PATTERNS
 // ============================================================================
@@ -203,18 +378,49 @@ pub static SECRET_TOKEN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"(?i)token\s*[:=]\s*['"`][^'"`]{20,}['"`]"#).unwrap()
 });
 
+// ============================================================================
+// TAINT SINK PATTERNS
+// ============================================================================
+
+/// Matches eval() and the Function constructor (up to the opening paren)
+pub static DYNAMIC_CODE_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\beval\s*\(|\bnew\s+Function\s*\(").unwrap()
+});
+
+/// Matches workflow storage calls that take a key (up to the opening paren)
+pub static STORAGE_KEY_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bstorage\.(?:get|put|set|delete)\s*\(").unwrap()
+});
+
+/// Matches a URL prefix that already fixes the host: an absolute URL up to
+/// the first path slash, or a relative path
+pub static FIXED_HOST_URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:https?://[^/\s]+/|/)").unwrap()
+});
+
+/// Matches a read of a URL's host, or a `startsWith` check on it, after the
+/// name holding the URL
+pub static HOST_CHECK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\s*\??\.\s*(?:host|hostname|origin|startsWith)\b").unwrap()
+});
+
+/// Matches `new URL(name).hostname` (the name is group 1)
+pub static PARSED_HOST: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"new\s+URL\s*\(\s*([A-Za-z_$][\w$]*)\b[^)]*\)\s*\.\s*(?:host|hostname|origin)\b").unwrap()
+});
+
 // ============================================================================
 // CRON VALIDATION PATTERNS
 // ============================================================================
 
-/// Cron step wildcard pattern
+/// Cron step wildcard pattern (ASCII digits; `\d` would accept any script's)
 pub static CRON_STEP_WILDCARD: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^\*/\d+$").unwrap()
+    Regex::new(r"^\*/[0-9]+$").unwrap()
 });
 
 /// Cron range/list pattern
 pub static CRON_RANGE_LIST: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^\d+(-\d+)?(,\d+(-\d+)?)*$").unwrap()
+    Regex::new(r"^[0-9]+(-[0-9]+)?(,[0-9]+(-[0-9]+)?)*$").unwrap()
 });
 
 /// Cron minute field
@@ -246,13 +452,11 @@ pub static CRON_WEEKDAY: Lazy<Regex> = Lazy::new(|| {
 // BLOCKED MODULE PATTERNS
 // ============================================================================
 
-/// Creates a pattern to match import/require of a specific module
-pub fn blocked_module_pattern(module: &str) -> Regex {
-    Regex::new(&format!(
-        r#"(import\s+.*from\s+['"`]{0}['"`])|(require\s*\(\s*['"`]{0}['"`]\s*\))"#,
-        regex::escape(module)
-    )).unwrap()
-}
+/// Matches an import or require of a module, capturing the specifier
+/// (group 1 for `import ... from`, group 2 for `require(...)`)
+pub static MODULE_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"import\s+.*?from\s+['"`]([^'"`]+)['"`]|require\s*\(\s*['"`]([^'"`]+)['"`]\s*\)"#).unwrap()
+});
 
 /// List of blocked Node.js modules
 pub static BLOCKED_NODE_MODULES: &[&str] = &[
@@ -276,3 +480,105 @@ pub static KNOWN_INTEGRATIONS: &[&str] = &[
     "gitlab", "google-workspace", "google-calendar", "google-drive",
     "google-sheets",
 ];
+
+// ============================================================================
+// PATTERN CATALOG
+// ============================================================================
+
+/// Every pattern in this module by name, so benchmarks can time each one on
+/// its own and catch a pattern that regresses
+pub fn named_patterns() -> Vec<(&'static str, &'static Regex)> {
+    vec![
+        ("SDK_IMPORT", &*SDK_IMPORT),
+        ("AI_USAGE", &*AI_USAGE),
+        ("WORKERS_AI_IMPORT", &*WORKERS_AI_IMPORT),
+        ("SDK_NAMED_IMPORT", &*SDK_NAMED_IMPORT),
+        ("DEFINE_WORKFLOW", &*DEFINE_WORKFLOW),
+        ("EXPORT_DEFAULT", &*EXPORT_DEFAULT),
+        ("EXPORTED_METADATA", &*EXPORTED_METADATA),
+        ("WORKFLOW_NAME", &*WORKFLOW_NAME),
+        ("WORKFLOW_TYPE", &*WORKFLOW_TYPE),
+        ("HAS_EXECUTE", &*HAS_EXECUTE),
+        ("HAS_RUN", &*HAS_RUN),
+        ("EXECUTE_BODY", &*EXECUTE_BODY),
+        ("INTEGRATIONS_BLOCK", &*INTEGRATIONS_BLOCK),
+        ("SERVICE_NAMES", &*SERVICE_NAMES),
+        ("SHORTHAND_INTEGRATIONS", &*SHORTHAND_INTEGRATIONS),
+        ("HAS_TRIGGER", &*HAS_TRIGGER),
+        ("TRIGGER_TYPE", &*TRIGGER_TYPE),
+        ("TRIGGER_OBJECT_TYPE", &*TRIGGER_OBJECT_TYPE),
+        ("WEBHOOK_CONFIG", &*WEBHOOK_CONFIG),
+        ("CRON_ALIAS_CALL", &*CRON_ALIAS_CALL),
+        ("STORAGE_PUT_CALL", &*STORAGE_PUT_CALL),
+        ("PAGINATION_READ", &*PAGINATION_READ),
+        ("SCHEDULE_EXPR", &*SCHEDULE_EXPR),
+        ("HAS_PRICING", &*HAS_PRICING),
+        ("PRICING_MODEL", &*PRICING_MODEL),
+        ("PRICING_PRICE", &*PRICING_PRICE),
+        ("HAS_EXECUTIONS", &*HAS_EXECUTIONS),
+        ("EXTERNAL_AI", &*EXTERNAL_AI),
+        ("AI_MODEL_CONSTANT", &*AI_MODEL_CONSTANT),
+        ("AI_MODEL_ID", &*AI_MODEL_ID),
+        ("MAX_TOKENS", &*MAX_TOKENS),
+        ("AI_CALL", &*AI_CALL),
+        ("FUNCTION_DECL", &*FUNCTION_DECL),
+        ("RESULT_BINDING", &*RESULT_BINDING),
+        ("RESULT_CHECK", &*RESULT_CHECK),
+        ("CALL_START", &*CALL_START),
+        ("ASSIGNMENT", &*ASSIGNMENT),
+        ("CONDITION_START", &*CONDITION_START),
+        ("COMPARISON", &*COMPARISON),
+        ("ENV_ACCESS", &*ENV_ACCESS),
+        ("CONSOLE_STATEMENTS", &*CONSOLE_STATEMENTS),
+        ("CONSOLE_CALL", &*CONSOLE_CALL),
+        ("AWAIT_IN_FOR_LOOP", &*AWAIT_IN_FOR_LOOP),
+        ("AWAIT_IN_WHILE_LOOP", &*AWAIT_IN_WHILE_LOOP),
+        ("EMPTY_CATCH", &*EMPTY_CATCH),
+        ("OUTBOUND_CALL", &*OUTBOUND_CALL),
+        ("RAW_HTTP_CALL", &*RAW_HTTP_CALL),
+        ("LOOP_STATEMENT", &*LOOP_STATEMENT),
+        ("DO_LOOP", &*DO_LOOP),
+        ("ITERATOR_CALL", &*ITERATOR_CALL),
+        ("PROMISE_ALL", &*PROMISE_ALL),
+        ("FOR_COUNTER_BOUND", &*FOR_COUNTER_BOUND),
+        ("FOR_OF_LITERAL", &*FOR_OF_LITERAL),
+        ("SLICE_BOUND", &*SLICE_BOUND),
+        ("CPU_HEAVY_OP", &*CPU_HEAVY_OP),
+        ("TRY_BLOCK", &*TRY_BLOCK),
+        ("CATCH_BLOCK", &*CATCH_BLOCK),
+        ("HAS_ON_ERROR", &*HAS_ON_ERROR),
+        ("RETRY_CALL", &*RETRY_CALL),
+        ("GENERIC_THROW", &*GENERIC_THROW),
+        ("SECRET_API_KEY", &*SECRET_API_KEY),
+        ("SECRET_SECRET", &*SECRET_SECRET),
+        ("SECRET_PASSWORD", &*SECRET_PASSWORD),
+        ("SECRET_TOKEN", &*SECRET_TOKEN),
+        ("DYNAMIC_CODE_CALL", &*DYNAMIC_CODE_CALL),
+        ("STORAGE_KEY_CALL", &*STORAGE_KEY_CALL),
+        ("FIXED_HOST_URL", &*FIXED_HOST_URL),
+        ("HOST_CHECK", &*HOST_CHECK),
+        ("PARSED_HOST", &*PARSED_HOST),
+        ("CRON_STEP_WILDCARD", &*CRON_STEP_WILDCARD),
+        ("CRON_RANGE_LIST", &*CRON_RANGE_LIST),
+        ("CRON_MINUTE", &*CRON_MINUTE),
+        ("CRON_HOUR", &*CRON_HOUR),
+        ("CRON_DAY", &*CRON_DAY),
+        ("CRON_MONTH", &*CRON_MONTH),
+        ("CRON_WEEKDAY", &*CRON_WEEKDAY),
+        ("MODULE_IMPORT", &*MODULE_IMPORT),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_lists_every_pattern() {
+        let declared = Regex::new(r"pub static ([A-Z_0-9]+): Lazy<Regex>").unwrap();
+        let source = include_str!("patterns.rs");
+        let names: Vec<&str> = declared.captures_iter(source).map(|c| c.get(1).unwrap().as_str()).collect();
+        let catalog: Vec<&str> = named_patterns().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, catalog);
+    }
+}
@@ -0,0 +1,372 @@
+//! Output formats for validation results.
+//!
+//! Every format is a [`Reporter`] that renders the results of a run (one
+//! [`FileReport`] per validated file) to a string, so CI systems can consume
+//! validator output without glue scripts:
+//!
+//! - `text` - human-readable, one line per diagnostic
+//! - `json` - an array of results with the file path
+//! - `jsonl` - one JSON object per diagnostic, for log ingestion
+//! - `junit` - JUnit XML, one test case per file, for test dashboards
+//! - `github` - GitHub Actions workflow commands, shown inline on PRs
+//! - `checkstyle` - Checkstyle XML, understood by most code review tools
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::validator::ValidationResult;
+
+/// The result for one validated file
+#[derive(Debug, Clone, Copy)]
+pub struct FileReport<'a> {
+    pub path: &'a str,
+    pub result: &'a ValidationResult,
+}
+
+/// Renders a run's results in one output format
+pub trait Reporter {
+    fn render(&self, files: &[FileReport]) -> String;
+}
+
+/// Available output formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    JsonLines,
+    JUnit,
+    GitHub,
+    Checkstyle,
+}
+
+impl ReportFormat {
+    pub const ALL: &'static [ReportFormat] = &[
+        ReportFormat::Text,
+        ReportFormat::Json,
+        ReportFormat::JsonLines,
+        ReportFormat::JUnit,
+        ReportFormat::GitHub,
+        ReportFormat::Checkstyle,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "text",
+            ReportFormat::Json => "json",
+            ReportFormat::JsonLines => "jsonl",
+            ReportFormat::JUnit => "junit",
+            ReportFormat::GitHub => "github",
+            ReportFormat::Checkstyle => "checkstyle",
+        }
+    }
+
+    /// Whether the output is meant for people rather than tools
+    pub fn is_human_readable(&self) -> bool {
+        *self == ReportFormat::Text
+    }
+
+    pub fn reporter(&self) -> Box<dyn Reporter> {
+        match self {
+            ReportFormat::Text => Box::new(TextReporter),
+            ReportFormat::Json => Box::new(JsonReporter),
+            ReportFormat::JsonLines => Box::new(JsonLinesReporter),
+            ReportFormat::JUnit => Box::new(JUnitReporter),
+            ReportFormat::GitHub => Box::new(GitHubReporter),
+            ReportFormat::Checkstyle => Box::new(CheckstyleReporter),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ReportFormat::ALL
+            .iter()
+            .find(|f| f.name().eq_ignore_ascii_case(name))
+            .copied()
+            .ok_or_else(|| {
+                let names: Vec<_> = ReportFormat::ALL.iter().map(ReportFormat::name).collect();
+                format!("unknown format '{}' (expected one of: {})", name, names.join(", "))
+            })
+    }
+}
+
+/// Escapes text for XML attributes and content.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `path:line` or `path`
+fn location(path: &str, diagnostic: &Diagnostic) -> String {
+    match diagnostic.line {
+        Some(line) => format!("{}:{}", path, line),
+        None => path.to_string(),
+    }
+}
+
+/// Human-readable output
+pub struct TextReporter;
+
+impl Reporter for TextReporter {
+    fn render(&self, files: &[FileReport]) -> String {
+        let mut out = String::new();
+        for file in files {
+            for d in &file.result.diagnostics {
+                let _ = writeln!(out, "{}: {}[{}] {}", location(file.path, d), d.severity, d.code, d.message);
+                if let Some(suggestion) = &d.suggestion {
+                    let _ = writeln!(out, "    suggestion: {}", suggestion);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[derive(Serialize)]
+struct FileJson<'a> {
+    file: &'a str,
+    #[serde(flatten)]
+    result: &'a ValidationResult,
+}
+
+/// JSON array of per-file results
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn render(&self, files: &[FileReport]) -> String {
+        let entries: Vec<FileJson> = files
+            .iter()
+            .map(|f| FileJson { file: f.path, result: f.result })
+            .collect();
+        serde_json::to_string_pretty(&entries).unwrap_or_default() + "\n"
+    }
+}
+
+#[derive(Serialize)]
+struct DiagnosticJson<'a> {
+    file: &'a str,
+    #[serde(flatten)]
+    diagnostic: &'a Diagnostic,
+}
+
+/// One JSON object per diagnostic
+pub struct JsonLinesReporter;
+
+impl Reporter for JsonLinesReporter {
+    fn render(&self, files: &[FileReport]) -> String {
+        let mut out = String::new();
+        for file in files {
+            for diagnostic in &file.result.diagnostics {
+                let line = serde_json::to_string(&DiagnosticJson { file: file.path, diagnostic }).unwrap_or_default();
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// JUnit XML: a test case per file that fails when the file has errors
+pub struct JUnitReporter;
+
+impl Reporter for JUnitReporter {
+    fn render(&self, files: &[FileReport]) -> String {
+        let failures = files.iter().filter(|f| !f.result.valid).count();
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            out,
+            "<testsuites name=\"workway-validator\" tests=\"{0}\" failures=\"{1}\">\n  <testsuite name=\"workway-validator\" tests=\"{0}\" failures=\"{1}\" errors=\"0\">",
+            files.len(),
+            failures
+        );
+
+        for file in files {
+            let path = xml_escape(file.path);
+            let _ = write!(out, "    <testcase classname=\"{0}\" name=\"{0}\"", path);
+            if file.result.diagnostics.is_empty() {
+                out.push_str("/>\n");
+                continue;
+            }
+            out.push_str(">\n");
+
+            let errors: Vec<&Diagnostic> = file.result.errors().collect();
+            if !errors.is_empty() {
+                let body: Vec<String> = errors
+                    .iter()
+                    .map(|d| format!("{} [{}] {}", location(file.path, d), d.code, d.message))
+                    .collect();
+                let _ = writeln!(
+                    out,
+                    "      <failure type=\"{}\" message=\"{} error(s)\">{}</failure>",
+                    xml_escape(&errors[0].code),
+                    errors.len(),
+                    xml_escape(&body.join("\n"))
+                );
+            }
+
+            let others: Vec<String> = file
+                .result
+                .diagnostics
+                .iter()
+                .filter(|d| !d.is_error())
+                .map(|d| format!("{} {}[{}] {}", location(file.path, d), d.severity, d.code, d.message))
+                .collect();
+            if !others.is_empty() {
+                let _ = writeln!(out, "      <system-out>{}</system-out>", xml_escape(&others.join("\n")));
+            }
+            out.push_str("    </testcase>\n");
+        }
+
+        out.push_str("  </testsuite>\n</testsuites>\n");
+        out
+    }
+}
+
+/// Escapes the data part of a GitHub workflow command.
+fn github_escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a GitHub workflow command property value.
+fn github_escape_property(text: &str) -> String {
+    github_escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// GitHub Actions `::error file=...,line=...::message` annotations
+pub struct GitHubReporter;
+
+impl Reporter for GitHubReporter {
+    fn render(&self, files: &[FileReport]) -> String {
+        let mut out = String::new();
+        for file in files {
+            for d in &file.result.diagnostics {
+                let command = match d.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info | Severity::Hint => "notice",
+                };
+                let mut properties = format!("file={}", github_escape_property(file.path));
+                if let Some(line) = d.line {
+                    let _ = write!(properties, ",line={}", line);
+                }
+                let _ = write!(properties, ",title={}", github_escape_property(&d.code));
+
+                let mut message = d.message.clone();
+                if let Some(suggestion) = &d.suggestion {
+                    let _ = write!(message, "\n{}", suggestion);
+                }
+                let _ = writeln!(out, "::{} {}::{}", command, properties, github_escape_data(&message));
+            }
+        }
+        out
+    }
+}
+
+/// Checkstyle XML
+pub struct CheckstyleReporter;
+
+impl Reporter for CheckstyleReporter {
+    fn render(&self, files: &[FileReport]) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+        for file in files {
+            let _ = writeln!(out, "  <file name=\"{}\">", xml_escape(file.path));
+            for d in &file.result.diagnostics {
+                let severity = match d.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info | Severity::Hint => "info",
+                };
+                let _ = writeln!(
+                    out,
+                    "    <error line=\"{}\" severity=\"{}\" message=\"{}\" source=\"workway.{}\"/>",
+                    d.line.unwrap_or(0),
+                    severity,
+                    xml_escape(&d.message),
+                    xml_escape(&d.code)
+                );
+            }
+            out.push_str("  </file>\n");
+        }
+        out.push_str("</checkstyle>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ValidationResult {
+        ValidationResult::new(
+            vec![
+                Diagnostic::error("INVALID_CRON", "Invalid cron expression: 0 25 * * *").with_line(7),
+                Diagnostic::warning("EMPTY_CATCH", "Empty catch block <detected>")
+                    .with_suggestion("Handle or re-throw errors properly"),
+            ],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_github_annotations_are_escaped() {
+        let result = sample();
+        let files = [FileReport { path: "src/a,b.ts", result: &result }];
+        let output = ReportFormat::GitHub.reporter().render(&files);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines[0], "::error file=src/a%2Cb.ts,line=7,title=INVALID_CRON::Invalid cron expression: 0 25 * * *");
+        assert_eq!(
+            lines[1],
+            "::warning file=src/a%2Cb.ts,title=EMPTY_CATCH::Empty catch block <detected>%0AHandle or re-throw errors properly"
+        );
+    }
+
+    #[test]
+    fn test_xml_and_jsonl_formats() {
+        let result = sample();
+        let clean = ValidationResult::new(Vec::new(), None);
+        let files = [
+            FileReport { path: "src/a.ts", result: &result },
+            FileReport { path: "src/b.ts", result: &clean },
+        ];
+
+        let junit = ReportFormat::JUnit.reporter().render(&files);
+        assert!(junit.contains("tests=\"2\" failures=\"1\""));
+        assert!(junit.contains("<failure type=\"INVALID_CRON\" message=\"1 error(s)\">"));
+        assert!(junit.contains("Empty catch block &lt;detected&gt;"));
+        assert!(junit.contains("<testcase classname=\"src/b.ts\" name=\"src/b.ts\"/>"));
+
+        let checkstyle = ReportFormat::Checkstyle.reporter().render(&files);
+        assert!(checkstyle.contains("<error line=\"7\" severity=\"error\" message=\"Invalid cron expression: 0 25 * * *\" source=\"workway.INVALID_CRON\"/>"));
+
+        let jsonl = ReportFormat::JsonLines.reporter().render(&files);
+        let first: serde_json::Value = serde_json::from_str(jsonl.lines().next().unwrap()).unwrap();
+        assert_eq!(first["file"], "src/a.ts");
+        assert_eq!(first["type"], "error");
+        assert_eq!(jsonl.lines().count(), 2);
+
+        assert_eq!("JUnit".parse::<ReportFormat>(), Ok(ReportFormat::JUnit));
+        assert!("xml".parse::<ReportFormat>().is_err());
+    }
+}
@@ -0,0 +1,296 @@
+//! Single-pass detection scan.
+//!
+//! Most rules begin by asking whether a pattern occurs anywhere in the file,
+//! and a typical workflow contains only some of the patterns. Rather than
+//! running every pattern over the whole file, the detection patterns are
+//! compiled into one `RegexSet` that reports, in a single pass, which of them
+//! occur. Rules read presence from the `Scan` and only run their own pattern
+//! (to get a span or captures) when the scan saw it.
+//!
+//! Patterns that begin with a literal and span blocks (`EXECUTE_BODY`,
+//! `AWAIT_IN_FOR_LOOP`) are not probes: on their own the engine skips ahead
+//! to the literal and they cost a fraction of a millisecond over the whole
+//! corpus, while adding them to the set makes every scan several times
+//! slower.
+//!
+//! Rules that match identifiers rather than text (inputs, taint, logging,
+//! ...) need the file with strings and comments blanked out; the scan makes
+//! that copy once, on first use, and shares it. The parsed workflow
+//! definition and the location of `execute` are shared the same way.
+//!
+//! Module imports are found by one pattern that captures every specifier,
+//! which is then looked up in the blocked lists, instead of compiling and
+//! running one pattern per blocked module.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use once_cell::unsync::OnceCell;
+use regex::{Regex, RegexSet};
+
+use crate::literal::Literal;
+use crate::manifest::workflow_definition;
+use crate::patterns::*;
+use crate::source::{code_only, execute_parts};
+
+/// A detection pattern checked by the scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Probe {
+    SdkImport,
+    AiUsage,
+    WorkersAiImport,
+    ModuleImport,
+    DefineWorkflow,
+    ExportDefault,
+    WorkflowName,
+    WorkflowType,
+    HasExecute,
+    HasRun,
+    IntegrationsBlock,
+    HasTrigger,
+    TriggerType,
+    TriggerObjectType,
+    WebhookConfig,
+    ScheduleExpr,
+    HasPricing,
+    PricingModel,
+    PricingPrice,
+    HasExecutions,
+    ExternalAi,
+    EnvAccess,
+    ConsoleStatement,
+    SecretApiKey,
+    SecretSecret,
+    SecretPassword,
+    SecretToken,
+    EmptyCatch,
+    AiModelConstant,
+    AiModelId,
+}
+
+impl Probe {
+    /// Every probe, in `RegexSet` order
+    pub(crate) const ALL: [Probe; 30] = [
+        Probe::SdkImport,
+        Probe::AiUsage,
+        Probe::WorkersAiImport,
+        Probe::ModuleImport,
+        Probe::DefineWorkflow,
+        Probe::ExportDefault,
+        Probe::WorkflowName,
+        Probe::WorkflowType,
+        Probe::HasExecute,
+        Probe::HasRun,
+        Probe::IntegrationsBlock,
+        Probe::HasTrigger,
+        Probe::TriggerType,
+        Probe::TriggerObjectType,
+        Probe::WebhookConfig,
+        Probe::ScheduleExpr,
+        Probe::HasPricing,
+        Probe::PricingModel,
+        Probe::PricingPrice,
+        Probe::HasExecutions,
+        Probe::ExternalAi,
+        Probe::EnvAccess,
+        Probe::ConsoleStatement,
+        Probe::SecretApiKey,
+        Probe::SecretSecret,
+        Probe::SecretPassword,
+        Probe::SecretToken,
+        Probe::EmptyCatch,
+        Probe::AiModelConstant,
+        Probe::AiModelId,
+    ];
+
+    /// The pattern a rule runs once the scan has seen it
+    pub(crate) fn pattern(self) -> &'static Regex {
+        match self {
+            Probe::SdkImport => &SDK_IMPORT,
+            Probe::AiUsage => &AI_USAGE,
+            Probe::WorkersAiImport => &WORKERS_AI_IMPORT,
+            Probe::ModuleImport => &MODULE_IMPORT,
+            Probe::DefineWorkflow => &DEFINE_WORKFLOW,
+            Probe::ExportDefault => &EXPORT_DEFAULT,
+            Probe::WorkflowName => &WORKFLOW_NAME,
+            Probe::WorkflowType => &WORKFLOW_TYPE,
+            Probe::HasExecute => &HAS_EXECUTE,
+            Probe::HasRun => &HAS_RUN,
+            Probe::IntegrationsBlock => &INTEGRATIONS_BLOCK,
+            Probe::HasTrigger => &HAS_TRIGGER,
+            Probe::TriggerType => &TRIGGER_TYPE,
+            Probe::TriggerObjectType => &TRIGGER_OBJECT_TYPE,
+            Probe::WebhookConfig => &WEBHOOK_CONFIG,
+            Probe::ScheduleExpr => &SCHEDULE_EXPR,
+            Probe::HasPricing => &HAS_PRICING,
+            Probe::PricingModel => &PRICING_MODEL,
+            Probe::PricingPrice => &PRICING_PRICE,
+            Probe::HasExecutions => &HAS_EXECUTIONS,
+            Probe::ExternalAi => &EXTERNAL_AI,
+            Probe::EnvAccess => &ENV_ACCESS,
+            Probe::ConsoleStatement => &CONSOLE_STATEMENTS,
+            Probe::SecretApiKey => &SECRET_API_KEY,
+            Probe::SecretSecret => &SECRET_SECRET,
+            Probe::SecretPassword => &SECRET_PASSWORD,
+            Probe::SecretToken => &SECRET_TOKEN,
+            Probe::EmptyCatch => &EMPTY_CATCH,
+            Probe::AiModelConstant => &AI_MODEL_CONSTANT,
+            Probe::AiModelId => &AI_MODEL_ID,
+        }
+    }
+}
+
+/// All probes compiled together, built from the same sources as the
+/// individual patterns so the two never disagree. Probe patterns must not use
+/// a Unicode `\b`: the lazy DFA cannot run one over non-ASCII text, and the
+/// set as a whole falls back to a far slower engine.
+static PROBE_SET: Lazy<RegexSet> = Lazy::new(|| {
+    RegexSet::new(Probe::ALL.iter().map(|p| p.pattern().as_str())).unwrap()
+});
+
+/// Which probes occur in a file
+pub(crate) struct Scan<'a> {
+    content: &'a str,
+    matched: [bool; Probe::ALL.len()],
+    code: OnceCell<String>,
+    definition: OnceCell<Option<Literal>>,
+    execute: OnceCell<Option<(Range<usize>, Range<usize>)>>,
+}
+
+impl<'a> Scan<'a> {
+    pub(crate) fn new(content: &'a str) -> Self {
+        let mut matched = [false; Probe::ALL.len()];
+        for index in PROBE_SET.matches(content).iter() {
+            matched[index] = true;
+        }
+        Self {
+            content,
+            matched,
+            code: OnceCell::new(),
+            definition: OnceCell::new(),
+            execute: OnceCell::new(),
+        }
+    }
+
+    /// The scanned file
+    pub(crate) fn content(&self) -> &'a str {
+        self.content
+    }
+
+    /// The file with string contents and comments blanked (see
+    /// [`code_only`]), computed on first use
+    pub(crate) fn code(&self) -> &str {
+        self.code.get_or_init(|| code_only(self.content))
+    }
+
+    /// The object literal defining the workflow (see
+    /// [`workflow_definition`]), parsed on first use
+    pub(crate) fn definition(&self) -> Option<&Literal> {
+        self.definition.get_or_init(|| workflow_definition(self.content)).as_ref()
+    }
+
+    /// Parameter list and body of `execute` (see [`execute_parts`]), found
+    /// in [`Scan::code`] on first use
+    fn execute(&self) -> Option<&(Range<usize>, Range<usize>)> {
+        self.execute
+            .get_or_init(|| if self.has(Probe::HasExecute) { execute_parts(self.code()) } else { None })
+            .as_ref()
+    }
+
+    /// Range of the `execute` parameter list (between the parentheses)
+    pub(crate) fn execute_params(&self) -> Option<Range<usize>> {
+        self.execute().map(|(params, _)| params.clone())
+    }
+
+    /// Range of the `execute` body
+    pub(crate) fn execute_body(&self) -> Option<Range<usize>> {
+        self.execute().map(|(_, body)| body.clone())
+    }
+
+    /// Whether the probe's pattern occurs anywhere in the file
+    pub(crate) fn has(&self, probe: Probe) -> bool {
+        self.matched[probe as usize]
+    }
+
+    /// First match of the probe's pattern
+    pub(crate) fn find(&self, probe: Probe) -> Option<regex::Match<'a>> {
+        if !self.has(probe) {
+            return None;
+        }
+        probe.pattern().find(self.content)
+    }
+
+    /// First match of the probe's pattern, with captures
+    pub(crate) fn captures(&self, probe: Probe) -> Option<regex::Captures<'a>> {
+        if !self.has(probe) {
+            return None;
+        }
+        probe.pattern().captures(self.content)
+    }
+
+    /// Number of matches of the probe's pattern
+    pub(crate) fn count(&self, probe: Probe) -> usize {
+        if !self.has(probe) {
+            return 0;
+        }
+        probe.pattern().find_iter(self.content).count()
+    }
+
+    /// Each imported or required module with the range of its first import.
+    pub(crate) fn imports(&self) -> HashMap<&'a str, Range<usize>> {
+        let mut imports = HashMap::new();
+        if !self.has(Probe::ModuleImport) {
+            return imports;
+        }
+        for caps in MODULE_IMPORT.captures_iter(self.content) {
+            let whole = caps.get(0).unwrap();
+            if let Some(specifier) = caps.get(1).or_else(|| caps.get(2)) {
+                imports.entry(specifier.as_str()).or_insert(whole.range());
+            }
+        }
+        imports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_agrees_with_patterns() {
+        let content = r#"
+import { defineWorkflow } from '@workway/sdk';
+import fs from 'fs'; import { x } from "./local";
+const cp = require( 'child_process' );
+export default defineWorkflow({
+    name: 'Scan',
+    trigger: schedule('0 9 * * *'),
+    async execute() {
+        console.log('a'); console.warn('b');
+        try { await x(); } catch (e) {}
+    }
+});
+"#;
+        let scan = Scan::new(content);
+        for probe in Probe::ALL {
+            assert_eq!(scan.has(probe), probe.pattern().is_match(content), "{:?}", probe);
+        }
+        assert_eq!(scan.count(Probe::ConsoleStatement), 2);
+        assert!(scan.find(Probe::HasPricing).is_none());
+
+        let imports = scan.imports();
+        let mut specifiers: Vec<_> = imports.keys().copied().collect();
+        specifiers.sort();
+        assert_eq!(specifiers, vec!["./local", "@workway/sdk", "child_process", "fs"]);
+        assert_eq!(&content[imports["child_process"].clone()], "require( 'child_process' )");
+    }
+
+    #[test]
+    fn test_probes_avoid_unicode_word_boundaries() {
+        for probe in Probe::ALL {
+            let source = probe.pattern().as_str().replace(r"(?-u:\b)", "");
+            assert!(!source.contains(r"\b"), "{:?} uses a Unicode \\b", probe);
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! Deprecated SDK APIs.
+//!
+//! Mirrors the `@deprecated` tags in `packages/sdk`. Each entry records the
+//! SDK release that deprecated the API and, when the tag names one, the release
+//! that removes it, so a workflow is judged against the SDK it actually depends on:
+//! nothing is reported before the deprecation, a warning after it and an error
+//! once the API is gone. The version comes from `[sdk] version` in the project
+//! configuration, else the nearest `package.json`, else [`LATEST_SDK_VERSION`].
+//! Add an entry here when an API is deprecated in the SDK. The tags do not say
+//! which release added them; the ones present when the catalog was written are
+//! recorded as deprecated in that release.
+
+use std::ops::Range;
+
+use crate::diagnostic::{Diagnostic, TextEdit};
+use crate::literal::Literal;
+use crate::patterns::*;
+use crate::scan::Scan;
+use crate::source::result_bindings;
+
+/// The SDK release the catalog is up to date with
+pub const LATEST_SDK_VERSION: &str = "1.1.0";
+
+/// Names the SDK is published under
+const SDK_PACKAGES: [&str; 2] = ["@workway/sdk", "@workwayco/sdk"];
+
+/// A deprecated SDK API
+#[derive(Debug, Clone)]
+pub struct SdkDeprecation {
+    /// The deprecated form as written in a workflow
+    pub api: &'static str,
+    /// What to use instead
+    pub replacement: &'static str,
+    /// Release that deprecated the API
+    pub deprecated_in: &'static str,
+    /// Release that removes it, once scheduled
+    pub removed_in: Option<&'static str>,
+    /// How to migrate
+    pub migration: &'static str,
+}
+
+const fn deprecation(
+    api: &'static str,
+    replacement: &'static str,
+    deprecated_in: &'static str,
+    removed_in: Option<&'static str>,
+    migration: &'static str,
+) -> SdkDeprecation {
+    SdkDeprecation { api, replacement, deprecated_in, removed_in, migration }
+}
+
+/// Deprecated APIs, oldest first
+pub static SDK_DEPRECATIONS: &[SdkDeprecation] = &[
+    // workflow-sdk.ts `WorkflowDefinition.config`
+    deprecation(
+        "config",
+        "inputs",
+        "1.1.0",
+        None,
+        "Rename config to inputs; execute still receives the values as both inputs and config",
+    ),
+    // triggers.ts `cron`
+    deprecation("cron()", "schedule()", "1.1.0", None, "Call schedule() with the same options"),
+    // action-result.ts `ActionResult.total`, `hasMore` and `cursor`
+    deprecation(
+        "ActionResult.total",
+        "ActionResult.metadata.total",
+        "1.1.0",
+        Some("2.0.0"),
+        "Read pagination from the result's metadata",
+    ),
+    deprecation(
+        "ActionResult.hasMore",
+        "ActionResult.metadata.hasMore",
+        "1.1.0",
+        Some("2.0.0"),
+        "Read pagination from the result's metadata",
+    ),
+    deprecation(
+        "ActionResult.cursor",
+        "ActionResult.metadata.cursor",
+        "1.1.0",
+        Some("2.0.0"),
+        "Read pagination from the result's metadata",
+    ),
+    // workflow-sdk.ts `WorkflowStorage.put`
+    deprecation("storage.put()", "storage.set()", "1.1.0", None, "Call storage.set() with the same arguments"),
+];
+
+fn find(api: &str) -> &'static SdkDeprecation {
+    SDK_DEPRECATIONS.iter().find(|d| d.api == api).expect("API is in the catalog")
+}
+
+fn parse_version(version: &str) -> (u64, u64, u64) {
+    range_floor(version).unwrap_or_default()
+}
+
+/// The lowest version a dependency range admits (`^1.2.0` is 1.2.0, `~1.1`
+/// is 1.1.0). `None` for tags, `*` and `workspace:` links.
+pub(crate) fn range_floor(range: &str) -> Option<(u64, u64, u64)> {
+    let first = range.split("||").next()?.trim();
+    let first = first.trim_start_matches(['^', '~', '>', '=', 'v', ' ']);
+    let core = first.split_whitespace().next()?.split(['-', '+']).next()?;
+    let mut parts = core.split('.');
+    let mut part = || match parts.next() {
+        None | Some("x" | "X" | "*") => Some(0),
+        Some(p) => p.parse::<u64>().ok(),
+    };
+    let version = (part()?, part()?, part()?);
+    parts.next().is_none().then_some(version)
+}
+
+/// The SDK range a `package.json` depends on, from `dependencies`,
+/// `devDependencies` or `peerDependencies`, if it is a version range.
+pub fn sdk_version_from_package_json(source: &str) -> Option<String> {
+    let package: serde_json::Value = serde_json::from_str(source).ok()?;
+    ["dependencies", "devDependencies", "peerDependencies"]
+        .iter()
+        .filter_map(|section| package.get(section))
+        .flat_map(|deps| SDK_PACKAGES.iter().filter_map(move |name| deps.get(name)?.as_str()))
+        .find(|range| range_floor(range).is_some())
+        .map(str::to_string)
+}
+
+/// Reads the SDK range from the nearest `package.json` that depends on the
+/// SDK, starting at `start` (a file or directory).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn discover_sdk_version(start: &std::path::Path) -> Option<String> {
+    let start = std::fs::canonicalize(start).unwrap_or_else(|_| start.to_path_buf());
+    let first_dir = if start.is_dir() { Some(start.as_path()) } else { start.parent() };
+    for dir in first_dir.into_iter().flat_map(std::path::Path::ancestors) {
+        let Ok(source) = std::fs::read_to_string(dir.join("package.json")) else {
+            continue;
+        };
+        // A workspace link or tag still decides: it is not an older release
+        if SDK_PACKAGES.iter().any(|name| source.contains(&format!("\"{}\"", name))) {
+            return sdk_version_from_package_json(&source);
+        }
+    }
+    None
+}
+
+/// The diagnostic for a use of `api` under SDK `target`, if it is deprecated
+/// there.
+fn report(api: &str, target: (u64, u64, u64)) -> Option<Diagnostic> {
+    let entry = find(api);
+    if target < parse_version(entry.deprecated_in) {
+        return None;
+    }
+    let diagnostic = match entry.removed_in {
+        Some(removed) if target >= parse_version(removed) => Diagnostic::error(
+            "REMOVED_SDK_API",
+            &format!("{} was removed in SDK {}; use {}", entry.api, removed, entry.replacement),
+        ),
+        Some(removed) => Diagnostic::warning(
+            "DEPRECATED_SDK_API",
+            &format!(
+                "{} is deprecated since SDK {} and removed in {}; use {}",
+                entry.api, entry.deprecated_in, removed, entry.replacement
+            ),
+        ),
+        None => Diagnostic::warning(
+            "DEPRECATED_SDK_API",
+            &format!("{} is deprecated since SDK {}; use {}", entry.api, entry.deprecated_in, entry.replacement),
+        ),
+    };
+    Some(diagnostic.with_suggestion(entry.migration))
+}
+
+/// Names imported from the SDK, with the range between the braces.
+fn sdk_imports(content: &str) -> Option<(Vec<&str>, Range<usize>)> {
+    let braces = SDK_NAMED_IMPORT.captures(content)?.get(1)?;
+    let names = braces.as_str().split(',').map(str::trim).filter(|n| !n.is_empty()).collect();
+    Some((names, braces.range()))
+}
+
+/// Range of a key written at `offset`, skipping an `async` prefix.
+fn key_span(content: &str, offset: usize, key: &str) -> Range<usize> {
+    let start = offset + content[offset..].find(key).unwrap_or(0);
+    start..start + key.len()
+}
+
+/// Reads of the deprecated top-level pagination fields on the results of
+/// integration calls (`page.hasMore`), with the field's range.
+fn pagination_reads(code: &str) -> Vec<(&'static str, Range<usize>)> {
+    let results: Vec<(usize, Vec<String>)> = OUTBOUND_CALL
+        .find_iter(code)
+        .filter(|m| m.as_str().starts_with("integrations."))
+        .map(|call| (call.end(), result_bindings(code, call.start())))
+        .collect();
+    PAGINATION_READ
+        .captures_iter(code)
+        .filter(|caps| {
+            let name = caps.get(1).unwrap();
+            results.iter().any(|(end, bindings)| name.start() >= *end && bindings.iter().any(|b| b == name.as_str()))
+        })
+        .map(|caps| {
+            let field = caps.get(2).unwrap();
+            let api = match field.as_str() {
+                "total" => "ActionResult.total",
+                "hasMore" => "ActionResult.hasMore",
+                _ => "ActionResult.cursor",
+            };
+            (api, field.range())
+        })
+        .collect()
+}
+
+pub(crate) fn validate_sdk_deprecations(scan: &Scan, diagnostics: &mut Vec<Diagnostic>, sdk_version: Option<&str>) {
+    let target = sdk_version
+        .and_then(range_floor)
+        .unwrap_or_else(|| parse_version(LATEST_SDK_VERSION));
+    let (content, code) = (scan.content(), scan.code());
+    // The module name is a string, which code_only blanks
+    let imports = sdk_imports(content);
+
+    if let Some(definition) = scan.definition() {
+        if let Some(config) = definition.property("config").filter(|p| matches!(p.value, Literal::Object(_))) {
+            if let Some(mut diagnostic) = report("config", target) {
+                let span = key_span(content, config.offset, "config");
+                diagnostic = diagnostic.with_span(content, span.clone());
+                if definition.get("inputs").is_none() {
+                    diagnostic = diagnostic.with_fix("Rename config to inputs", vec![TextEdit::replace(span, "inputs")]);
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    for (api, span) in pagination_reads(code) {
+        let Some(diagnostic) = report(api, target) else {
+            continue;
+        };
+        diagnostics.push(diagnostic.with_span(content, span.clone()).with_fix(
+            "Read it from metadata",
+            vec![TextEdit::insert(span.start, "metadata?.")],
+        ));
+    }
+
+    for m in STORAGE_PUT_CALL.captures_iter(code) {
+        let Some(diagnostic) = report("storage.put()", target) else {
+            break;
+        };
+        let span = m.get(1).unwrap().range();
+        diagnostics.push(
+            diagnostic
+                .with_span(content, span.clone())
+                .with_fix("Use storage.set()", vec![TextEdit::replace(span, "set")]),
+        );
+    }
+
+    // cron() is only the SDK alias when it is imported from the SDK
+    let Some(imports) = imports.filter(|(names, _)| names.contains(&"cron")) else {
+        return;
+    };
+    let calls: Vec<Range<usize>> = CRON_ALIAS_CALL
+        .find_iter(code)
+        .filter(|m| !code[..m.start()].ends_with(['.', '#']) && !code[..m.start()].trim_end().ends_with("function"))
+        .map(|m| m.start()..m.start() + 4)
+        .collect();
+    for span in &calls {
+        let Some(diagnostic) = report("cron()", target) else {
+            return;
+        };
+        let mut edits = vec![TextEdit::replace(span.clone(), "schedule")];
+        // With one call the import can follow; with several, only once it has
+        let has_schedule = imports.0.contains(&"schedule");
+        if calls.len() == 1 {
+            let braces = &content[imports.1.clone()];
+            let cron = braces
+                .match_indices("cron")
+                .find(|(i, _)| !braces[i + 4..].starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+                .map(|(i, _)| imports.1.start + i);
+            match (cron, has_schedule) {
+                (Some(start), false) => edits.push(TextEdit::replace(start..start + 4, "schedule")),
+                (Some(start), true) => {
+                    let mut end = start + 4;
+                    end += content[end..].len() - content[end..].trim_start().len();
+                    if content[end..].starts_with(',') {
+                        end += 1;
+                        end += content[end..].len() - content[end..].trim_start().len();
+                    }
+                    edits.push(TextEdit::replace(start..end, ""));
+                }
+                (None, _) => {}
+            }
+        }
+        let diagnostic = diagnostic.with_span(content, span.clone());
+        diagnostics.push(if calls.len() == 1 || has_schedule {
+            diagnostic.with_fix("Use schedule()", edits)
+        } else {
+            diagnostic
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(content: &str, diagnostic: &Diagnostic) -> String {
+        let mut edits = diagnostic.fixes[0].edits.clone();
+        edits.sort_by_key(|e| std::cmp::Reverse(e.span.start));
+        let mut fixed = content.to_string();
+        for edit in edits {
+            fixed.replace_range(edit.span.start..edit.span.end, &edit.replacement);
+        }
+        fixed
+    }
+
+    #[test]
+    fn test_deprecations_follow_the_target_version() {
+        let content = r#"
+import { defineWorkflow, cron } from '@workway/sdk';
+export default defineWorkflow({
+    name: 'Digest',
+    config: { channel: { type: 'text', label: 'Channel' } },
+    trigger: cron({ schedule: '0 9 * * 1' }),
+    async execute({ config, integrations }) {
+        const page = await integrations.notion.databases.query({ id: config.db });
+        if (page.hasMore) console.log(page.metadata?.cursor);
+        return { success: true };
+    },
+});
+"#;
+        let codes = |version: Option<&str>| {
+            let mut diagnostics = Vec::new();
+            validate_sdk_deprecations(&Scan::new(content), &mut diagnostics, version);
+            diagnostics.into_iter().map(|d| (d.code, d.message)).collect::<Vec<_>>()
+        };
+        assert!(codes(Some("1.0.3")).is_empty());
+        let current = codes(None);
+        assert_eq!(current.len(), 3);
+        assert_eq!(
+            current[1],
+            (
+                "DEPRECATED_SDK_API".to_string(),
+                "ActionResult.hasMore is deprecated since SDK 1.1.0 and removed in 2.0.0; use ActionResult.metadata.hasMore"
+                    .to_string()
+            )
+        );
+        let removed = codes(Some("2.0.0"));
+        assert_eq!(removed[1].0, "REMOVED_SDK_API");
+        assert_eq!(removed[1].1, "ActionResult.hasMore was removed in SDK 2.0.0; use ActionResult.metadata.hasMore");
+
+        assert_eq!(range_floor("~1.1"), Some((1, 1, 0)));
+        assert_eq!(range_floor(">= 1.0.0 < 2"), Some((1, 0, 0)));
+        assert_eq!(range_floor("workspace:*"), None);
+        let package = r#"{ "name": "w", "dependencies": { "@workwayco/sdk": "^1.0.3" } }"#;
+        assert_eq!(sdk_version_from_package_json(package).as_deref(), Some("^1.0.3"));
+    }
+
+    #[test]
+    fn test_mechanical_migrations() {
+        let content = r#"import { defineWorkflow, cron } from '@workway/sdk';
+export default defineWorkflow({
+    config: { channel: { type: 'text', label: 'Channel' } },
+    webhooks: [cron({ schedule: '0 9 * * 1' })],
+    async execute({ storage, integrations }) {
+        const issues = await integrations.linear.issues.list({ first: 50 });
+        await storage.put('cursor', issues?.cursor);
+        await this.state.storage.put('ignored', 1);
+        return { success: true };
+    },
+});
+"#;
+        let mut diagnostics = Vec::new();
+        validate_sdk_deprecations(&Scan::new(content), &mut diagnostics, Some("1.1.0"));
+        let fixed: Vec<String> = diagnostics.iter().map(|d| apply(content, d)).collect();
+
+        assert_eq!(diagnostics.len(), 4);
+        assert!(fixed[0].contains("  inputs: { channel:"));
+        assert!(fixed[1].contains("await storage.put('cursor', issues?.metadata?.cursor);"));
+        assert!(fixed[2].contains("await storage.set('cursor', issues?.cursor);"));
+        assert!(fixed[3].contains("import { defineWorkflow, schedule } from"));
+        assert!(fixed[3].contains("webhooks: [schedule({ schedule: '0 9 * * 1' })]"));
+    }
+}
@@ -0,0 +1,393 @@
+//! Lightweight source scanning helpers.
+//!
+//! The validator stays regex based, but some rules need to know where a block
+//! starts and ends (the `execute` body, a loop body, a `Promise.all(...)` call).
+//! These helpers do just enough lexing - strings, template literals and
+//! comments - to match brackets without being fooled by their contents.
+
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::patterns::RESULT_BINDING;
+
+/// Matches the start of a workflow method (`execute(`, `async execute(`,
+/// `execute: async (`, `execute: async function (`). The method name is
+/// substituted in by the `*_body` helpers below.
+fn method_pattern(name: &str) -> Regex {
+    Regex::new(&format!(
+        r"\b{}\s*(?::\s*(?:async\s*)?(?:function\s*)?)?\(",
+        regex::escape(name)
+    ))
+    .unwrap()
+}
+
+static EXECUTE_METHOD: Lazy<Regex> = Lazy::new(|| method_pattern("execute"));
+
+/// Returns the closing bracket for an opening bracket byte.
+fn closer(open: u8) -> Option<u8> {
+    match open {
+        b'(' => Some(b')'),
+        b'[' => Some(b']'),
+        b'{' => Some(b'}'),
+        _ => None,
+    }
+}
+
+/// Skips a quoted string starting at `start` and returns the offset of the
+/// closing quote (or the end of the line for unterminated strings).
+pub(crate) fn skip_string(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'\n' => return i,
+            b if b == quote => return i,
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len().saturating_sub(1)
+}
+
+/// Skips a template literal starting at `start`, including nested `${...}`
+/// expressions, and returns the offset of the closing backtick.
+pub(crate) fn skip_template(content: &str, start: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'`' => return i,
+            b'$' if bytes.get(i + 1) == Some(&b'{') => match matching_close(content, i + 1) {
+                Some(end) => i = end,
+                None => return bytes.len().saturating_sub(1),
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len().saturating_sub(1)
+}
+
+/// Skips a `//` or `/* */` comment starting at `start` and returns the offset
+/// of its last byte. Returns `None` if `start` does not begin a comment.
+pub(crate) fn skip_comment(bytes: &[u8], start: usize) -> Option<usize> {
+    match bytes.get(start + 1) {
+        Some(b'/') => Some(
+            bytes[start..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|p| start + p)
+                .unwrap_or(bytes.len() - 1),
+        ),
+        Some(b'*') => Some(
+            bytes[start + 2..]
+                .windows(2)
+                .position(|w| w == b"*/")
+                .map(|p| start + 2 + p + 1)
+                .unwrap_or(bytes.len() - 1),
+        ),
+        _ => None,
+    }
+}
+
+/// Returns `content` with string contents and comments replaced by spaces,
+/// keeping `${...}` expressions, line breaks and byte offsets, so patterns
+/// over identifiers only see code.
+pub(crate) fn code_only(content: &str) -> String {
+    let mut out = content.as_bytes().to_vec();
+    blank_literals(content, 0..content.len(), &mut out);
+    // Only whole runs between ASCII delimiters are blanked
+    String::from_utf8(out).unwrap_or_else(|_| content.to_string())
+}
+
+fn blank_literals(content: &str, range: Range<usize>, out: &mut [u8]) {
+    let bytes = content.as_bytes();
+    let blank = |out: &mut [u8], from: usize, to: usize| {
+        let to = to.min(range.end);
+        if from >= to {
+            return;
+        }
+        for b in &mut out[from..to] {
+            if *b != b'\n' {
+                *b = b' ';
+            }
+        }
+    };
+    let mut i = range.start;
+    while i < range.end {
+        match bytes[i] {
+            b'\'' | b'"' => {
+                let end = skip_string(bytes, i);
+                blank(out, i + 1, end);
+                i = end;
+            }
+            b'`' => {
+                let mut j = i + 1;
+                while j < range.end && bytes[j] != b'`' {
+                    if bytes[j] == b'\\' {
+                        blank(out, j, j + 2);
+                        j += 2;
+                        continue;
+                    }
+                    if bytes[j] == b'$' && bytes.get(j + 1) == Some(&b'{') {
+                        let close = matching_close(content, j + 1).unwrap_or(range.end);
+                        blank_literals(content, j + 2..close.min(range.end), out);
+                        j = close + 1;
+                        continue;
+                    }
+                    blank(out, j, j + 1);
+                    j += 1;
+                }
+                i = j;
+            }
+            b'/' => {
+                if let Some(end) = skip_comment(bytes, i) {
+                    blank(out, i, end + 1);
+                    i = end;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// Finds the bracket that closes the one at `open`.
+///
+/// `open` must point at `(`, `[` or `{`. Brackets inside strings, template
+/// literals and comments are ignored. Returns `None` if the bracket is never
+/// closed.
+pub fn matching_close(content: &str, open: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut stack = vec![closer(*bytes.get(open)?)?];
+    let mut i = open + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => i = skip_string(bytes, i),
+            b'`' => i = skip_template(content, i),
+            b'/' => {
+                if let Some(end) = skip_comment(bytes, i) {
+                    i = end;
+                }
+            }
+            b @ (b'(' | b'[' | b'{') => stack.push(closer(b)?),
+            b @ (b')' | b']' | b'}') if stack.last() == Some(&b) => {
+                stack.pop();
+                if stack.is_empty() {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Byte ranges of the comma-separated parts of `text` at bracket depth 0,
+/// skipping commas in strings, templates and comments. A trailing comma
+/// adds no part.
+pub(crate) fn split_top_level(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut parts = Vec::new();
+    let (mut start, mut depth, mut i) = (0, 0i32, 0);
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => i = skip_string(bytes, i),
+            b'`' => i = skip_template(text, i),
+            b'/' => {
+                if let Some(end) = skip_comment(bytes, i) {
+                    i = end;
+                }
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b',' if depth == 0 => {
+                parts.push(start..i);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    if !text[start..].trim().is_empty() {
+        parts.push(start..text.len());
+    }
+    parts
+}
+
+/// Returns the byte ranges of a method's parameter list and body (both
+/// between their brackets) given a regex matching up to and including the
+/// parameter list's `(`.
+fn method_parts(content: &str, pattern: &Regex) -> Option<(Range<usize>, Range<usize>)> {
+    for m in pattern.find_iter(content) {
+        let params_end = match matching_close(content, m.end() - 1) {
+            Some(end) => end,
+            None => continue,
+        };
+
+        let rest = &content[params_end + 1..];
+        let trimmed = rest.trim_start();
+        let trimmed = trimmed
+            .strip_prefix("=>")
+            .map(str::trim_start)
+            .unwrap_or(trimmed);
+        if !trimmed.starts_with('{') {
+            continue;
+        }
+
+        let open = content.len() - trimmed.len();
+        if let Some(close) = matching_close(content, open) {
+            return Some((m.end()..params_end, open + 1..close));
+        }
+    }
+    None
+}
+
+/// Returns the byte ranges of the `execute` parameter list and body, if they
+/// can be found.
+pub(crate) fn execute_parts(content: &str) -> Option<(Range<usize>, Range<usize>)> {
+    method_parts(content, &EXECUTE_METHOD)
+}
+
+/// Returns the byte range of the `execute` function body, if it can be found.
+pub fn execute_body(content: &str) -> Option<Range<usize>> {
+    execute_parts(content).map(|(_, body)| body)
+}
+
+/// Names bound by a binding pattern: `result` or `{ data, label: kind }`.
+pub(crate) fn binding_names(pattern: &str) -> Vec<String> {
+    match pattern.strip_prefix('{') {
+        Some(inner) => inner
+            .trim_end_matches('}')
+            .split(',')
+            .filter_map(|part| {
+                let part = part.split('=').next()?.trim().trim_start_matches("...");
+                let name = part.rsplit(':').next()?.trim();
+                (!name.is_empty()).then(|| name.to_string())
+            })
+            .collect(),
+        None => vec![pattern.to_string()],
+    }
+}
+
+/// Identifiers in `text` that are not property names (`x` in `x.y`, not
+/// `y`), with their offsets.
+pub(crate) fn identifiers(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            if bytes[i].is_ascii_alphabetic() || bytes[i] == b'_' || bytes[i] == b'$' {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'$') {
+                    i += 1;
+                }
+                let is_property = start > 0 && bytes[start - 1] == b'.' && !text[..start].ends_with("...");
+                if !is_property {
+                    return Some((start, &text[start..i]));
+                }
+            } else {
+                i += 1;
+            }
+        }
+        None
+    })
+}
+
+/// The property path starting at `start`: `trigger.data?.url`.
+pub(crate) fn dotted(text: &str, start: usize) -> &str {
+    let bytes = text.as_bytes();
+    let ident_end = |mut i: usize| {
+        while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'_' | b'$')) {
+            i += 1;
+        }
+        i
+    };
+    let mut end = ident_end(start);
+    loop {
+        let rest = &text[end..];
+        let skip = if rest.starts_with("?.") {
+            2
+        } else if rest.starts_with('.') && !rest.starts_with("..") {
+            1
+        } else {
+            break;
+        };
+        let next = ident_end(end + skip);
+        if next == end + skip {
+            break;
+        }
+        end = next;
+    }
+    &text[start..end]
+}
+
+/// Start of the expression a call at `offset` belongs to, skipping back over
+/// its receiver (`integrations.ai.generateText` starts at `integrations`).
+fn receiver_start(content: &str, offset: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut start = offset;
+    while start > 0 && (bytes[start - 1].is_ascii_alphanumeric() || matches!(bytes[start - 1], b'_' | b'$' | b'.')) {
+        start -= 1;
+    }
+    start
+}
+
+/// Names a call's result is bound to, read from the text before it on the
+/// same line.
+pub(crate) fn result_bindings(content: &str, call_start: usize) -> Vec<String> {
+    let start = receiver_start(content, call_start);
+    let line_start = content[..start].rfind(['\n', ';']).map_or(0, |i| i + 1);
+    RESULT_BINDING
+        .captures(&content[line_start..start])
+        .map(|caps| binding_names(&caps[1]))
+        .unwrap_or_default()
+}
+
+/// Returns the 1-based line number of a byte offset.
+pub fn line_at(content: &str, offset: usize) -> u32 {
+    let end = offset.min(content.len());
+    content.as_bytes()[..end]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count() as u32
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_close_skips_strings_and_templates() {
+        let src = r#"{ a: '}', b: `${ {x: 1} }}`, /* } */ c: "}" }"#;
+        assert_eq!(matching_close(src, 0), Some(src.len() - 1));
+    }
+
+    #[test]
+    fn test_execute_body_forms() {
+        let method = "async execute({ inputs }) { return 1; }";
+        let arrow = "execute: async ({ inputs }) => { return 2; }";
+        assert_eq!(&method[execute_body(method).unwrap()], " return 1; ");
+        assert_eq!(&arrow[execute_body(arrow).unwrap()], " return 2; ");
+    }
+
+    #[test]
+    fn test_code_only_keeps_template_expressions() {
+        let src = "const a = 'x y'; // note\nf(`say ${name + 'z'}!`);";
+        assert_eq!(code_only(src), "const a = '   ';        \nf(`    ${name + ' '} `);");
+    }
+
+    #[test]
+    fn test_code_only_unterminated_quote_at_end() {
+        assert_eq!(code_only("'"), "'");
+        assert_eq!(code_only("x\""), "x\"");
+    }
+}
@@ -0,0 +1,430 @@
+//! Taint tracking from untrusted values to dangerous sinks.
+//!
+//! Webhook payloads are whatever the sender chose to post, and inputs are
+//! whatever the installing user typed. This module follows values from
+//! `trigger`, `inputs` and `payload` through assignments and helper arguments,
+//! and reports when one reaches:
+//!
+//! - the part of a `fetch` / `http.*` URL that picks the host (SSRF),
+//! - `eval` or `new Function` (dynamic code execution),
+//! - a whole `storage` key, with no fixed prefix.
+//!
+//! The analysis is name-based and ignores scopes: once any assignment to a
+//! name mentions a tainted value, the name is tainted everywhere. Reading a
+//! URL's `host`, `hostname` or `origin`, or calling `startsWith` on it, counts
+//! as validation for the URL sink.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use regex::Match;
+
+use crate::diagnostic::Diagnostic;
+use crate::patterns::*;
+use crate::scan::Scan;
+use crate::source::{binding_names, dotted, identifiers, matching_close, split_top_level};
+
+/// Names whose values come from outside the workflow author's control
+const SOURCES: [&str; 3] = ["trigger", "inputs", "payload"];
+
+/// Assignments followed back from a sink before giving up on a trace
+const MAX_TRACE_STEPS: usize = 8;
+
+/// How an expression starts, which decides whether a tainted value later in
+/// it can still pick a URL's host or a whole storage key
+#[derive(Debug, Clone, PartialEq)]
+enum Lead {
+    /// A string or template literal starting with this text
+    Literal(String),
+    /// A template starting with an untainted `${base}`
+    Base,
+    /// Anything else
+    Open,
+}
+
+impl Lead {
+    fn fixes_host(&self) -> bool {
+        match self {
+            Lead::Literal(prefix) => FIXED_HOST_URL.is_match(prefix),
+            Lead::Base => true,
+            Lead::Open => false,
+        }
+    }
+
+    fn fixes_key(&self) -> bool {
+        match self {
+            Lead::Literal(prefix) => !prefix.is_empty(),
+            Lead::Base => true,
+            Lead::Open => false,
+        }
+    }
+}
+
+/// How a name became tainted
+struct Origin {
+    /// The tainted expression it was assigned from (`trigger.data.url`)
+    from: String,
+    /// Span of the assignment or call argument
+    span: Range<usize>,
+    lead: Lead,
+    /// Tainted as a helper parameter rather than by assignment
+    argument: bool,
+}
+
+/// Tainted names; sources have no origin
+type Taint = HashMap<String, Option<Origin>>;
+
+/// The first tainted name mentioned in `text`, as an offset and property path.
+fn first_tainted<'a>(text: &'a str, taint: &Taint) -> Option<(usize, &'a str)> {
+    identifiers(text)
+        .find(|(_, id)| taint.contains_key(*id))
+        .map(|(offset, _)| (offset, dotted(text, offset)))
+}
+
+/// How the expression at `range` starts. Literal text is read from `content`,
+/// expressions from `code`.
+fn lead(content: &str, code: &str, range: Range<usize>, taint: &Taint) -> Lead {
+    let text = &content[range.clone()];
+    let trimmed = text.trim_start();
+    let start = range.start + text.len() - trimmed.len();
+    match trimmed.chars().next() {
+        Some(quote @ ('\'' | '"')) => Lead::Literal(trimmed[1..].split(quote).next().unwrap_or_default().to_string()),
+        Some('`') => {
+            let body = &trimmed[1..];
+            let end = body.find('`').unwrap_or(body.len());
+            let prefix_end = body.find("${").unwrap_or(end).min(end);
+            if prefix_end > 0 || prefix_end == end {
+                return Lead::Literal(body[..prefix_end].to_string());
+            }
+            let open = start + 2;
+            let close = matching_close(code, open).unwrap_or(range.end);
+            match first_tainted(&code[open + 1..close], taint) {
+                Some(_) => Lead::Open,
+                None => Lead::Base,
+            }
+        }
+        _ => Lead::Open,
+    }
+}
+
+/// Helper functions with the names bound by each parameter.
+fn helper_params(code: &str) -> Vec<(String, usize, Vec<Vec<String>>)> {
+    FUNCTION_DECL
+        .captures_iter(code)
+        .filter_map(|caps| {
+            let name = caps.get(1).or_else(|| caps.get(2))?;
+            let open = caps.get(0)?.end() - 1;
+            let close = matching_close(code, open)?;
+            let params = &code[open + 1..close];
+            let names = split_top_level(params)
+                .into_iter()
+                .map(|part| {
+                    let param = params[part].trim().trim_start_matches("...");
+                    match param.starts_with('{') {
+                        true => binding_names(&param[..matching_close(param, 0).map_or(param.len(), |c| c + 1)]),
+                        false => {
+                            let end = param.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).unwrap_or(param.len());
+                            vec![param[..end].to_string()]
+                        }
+                    }
+                })
+                .collect();
+            Some((name.as_str().to_string(), name.start(), names))
+        })
+        .collect()
+}
+
+/// Taints names through assignments and helper calls until nothing new is
+/// reached.
+fn propagate(content: &str, code: &str) -> Taint {
+    let mut taint: Taint = SOURCES.iter().map(|s| (s.to_string(), None)).collect();
+    let assignments: Vec<(Vec<String>, Range<usize>, Range<usize>)> = ASSIGNMENT
+        .captures_iter(code)
+        .map(|caps| (binding_names(&caps[1]), caps.get(1).unwrap().range(), caps.get(2).unwrap().range()))
+        .collect();
+    let helpers = helper_params(code);
+    // Opening parens of the calls to each helper
+    let mut calls: Vec<Vec<usize>> = vec![Vec::new(); helpers.len()];
+    for call in CALL_START.find_iter(code) {
+        let name = call.as_str().trim_end_matches('(').trim_end();
+        let preceded = code[..call.start()].trim_end();
+        if preceded.ends_with('.') || preceded.ends_with("function") {
+            continue;
+        }
+        for (opens, (helper, declared_at, _)) in calls.iter_mut().zip(&helpers) {
+            if helper == name && call.start() != *declared_at {
+                opens.push(call.end() - 1);
+            }
+        }
+    }
+
+    loop {
+        let before = taint.len();
+        for (names, binding, rhs) in &assignments {
+            if names.iter().all(|name| taint.contains_key(name)) {
+                continue;
+            }
+            let Some((_, from)) = first_tainted(&code[rhs.clone()], &taint) else {
+                continue;
+            };
+            let origin_lead = lead(content, code, rhs.clone(), &taint);
+            let from = from.to_string();
+            for name in names {
+                taint.entry(name.clone()).or_insert_with(|| {
+                    Some(Origin { from: from.clone(), span: binding.start..rhs.end, lead: origin_lead.clone(), argument: false })
+                });
+            }
+        }
+
+        for ((_, _, params), opens) in helpers.iter().zip(&calls) {
+            for &open in opens {
+                let Some(close) = matching_close(code, open) else {
+                    continue;
+                };
+                let args = open + 1..close;
+                for (part, names) in split_top_level(&code[args.clone()]).into_iter().zip(params) {
+                    let arg = args.start + part.start..args.start + part.end;
+                    let Some((_, from)) = first_tainted(&code[arg.clone()], &taint) else {
+                        continue;
+                    };
+                    let from = from.to_string();
+                    let origin_lead = lead(content, code, arg.clone(), &taint);
+                    for name in names {
+                        taint.entry(name.clone()).or_insert_with(|| {
+                            Some(Origin { from: from.clone(), span: arg.clone(), lead: origin_lead.clone(), argument: true })
+                        });
+                    }
+                }
+            }
+        }
+
+        if taint.len() == before {
+            break;
+        }
+    }
+    taint
+}
+
+/// Names whose host is validated: `new URL(url).hostname`, `url.startsWith(...)`.
+fn validated(code: &str, taint: &Taint) -> HashSet<String> {
+    let checked = HOST_CHECK.find_iter(code).filter_map(|m| {
+        let before = &code[..m.start()];
+        let start = before.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_' || c == '$').len();
+        (start < before.len()).then(|| &before[start..])
+    });
+    let parsed = PARSED_HOST.captures_iter(code).map(|caps| caps.get(1).unwrap().as_str());
+    checked
+        .chain(parsed)
+        .filter(|name| !SOURCES.contains(name) && taint.contains_key(*name))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Follows `mention` back to a source. Returns the steps (nearest first), or
+/// `None` when a step on the way makes the value safe for the sink.
+fn trace<'a>(
+    taint: &'a Taint,
+    mention: &str,
+    safe: impl Fn(&str, &Origin) -> bool,
+) -> Option<Vec<(&'a str, &'a Origin)>> {
+    let mut steps = Vec::new();
+    let mut name = identifiers(mention).next()?.1.to_string();
+    for _ in 0..MAX_TRACE_STEPS {
+        let (key, origin) = taint.get_key_value(&name)?;
+        let Some(origin) = origin else {
+            return Some(steps);
+        };
+        if safe(key, origin) || steps.iter().any(|(seen, _)| *seen == key.as_str()) {
+            return None;
+        }
+        steps.push((key.as_str(), origin));
+        name = identifiers(&origin.from).next()?.1.to_string();
+    }
+    None
+}
+
+/// The argument list of the call whose `(` ends `m`, split at top level.
+fn call_args(code: &str, m: &Match) -> Vec<Range<usize>> {
+    let open = m.end() - 1;
+    let close = matching_close(code, open).unwrap_or(code.len());
+    split_top_level(&code[open + 1..close])
+        .into_iter()
+        .map(|part| open + 1 + part.start..open + 1 + part.end)
+        .collect()
+}
+
+/// Builds the diagnostic for a tainted value at `mention` reaching `sink`.
+fn tainted_sink(
+    content: &str,
+    diagnostic: Diagnostic,
+    mention: Range<usize>,
+    sink: &str,
+    steps: &[(&str, &Origin)],
+) -> Diagnostic {
+    let source = steps.last().map_or(&content[mention.clone()], |(_, origin)| origin.from.as_str());
+    let mut chain = vec![source];
+    chain.extend(steps.iter().rev().map(|(name, _)| *name));
+    chain.push(sink);
+
+    let mut diagnostic = diagnostic.with_span(content, mention);
+    diagnostic.message = format!("{} ({})", diagnostic.message, chain.join(" → "));
+    for (name, origin) in steps {
+        let verb = if origin.argument { "receives" } else { "is assigned from" };
+        diagnostic = diagnostic.with_related(content, origin.span.clone(), &format!("{} {} {}", name, verb, origin.from));
+    }
+    diagnostic
+}
+
+pub(crate) fn validate_taint(scan: &Scan, diagnostics: &mut Vec<Diagnostic>) {
+    // Strings and comments are blanked with offsets kept, so spans still
+    // point into `content`
+    let (content, code) = (scan.content(), scan.code());
+    let taint = propagate(content, code);
+    let validated = validated(code, &taint);
+    let sink_name = |m: &Match| m.as_str().trim_end_matches(|c: char| c == '(' || c.is_whitespace()).to_string();
+
+    for m in RAW_HTTP_CALL.find_iter(code) {
+        let Some(url) = call_args(code, &m).into_iter().next() else {
+            continue;
+        };
+        if lead(content, code, url.clone(), &taint).fixes_host() {
+            continue;
+        }
+        let Some((offset, path)) = first_tainted(&code[url.clone()], &taint) else {
+            continue;
+        };
+        let safe = |name: &str, origin: &Origin| validated.contains(name) || origin.lead.fixes_host();
+        let Some(steps) = trace(&taint, path, safe) else {
+            continue;
+        };
+        let mention = url.start + offset..url.start + offset + path.len();
+        let sink = format!("{}() URL", sink_name(&m));
+        diagnostics.push(
+            tainted_sink(
+                content,
+                Diagnostic::warning("SSRF_RISK", &format!("Untrusted input can choose the host of a {} request", sink_name(&m))),
+                mention,
+                &sink,
+                &steps,
+            )
+            .with_suggestion("Start the URL with a fixed https://host/ prefix, or check new URL(url).hostname against an allowlist first"),
+        );
+    }
+
+    for m in DYNAMIC_CODE_CALL.find_iter(code) {
+        let tainted = call_args(code, &m).into_iter().find_map(|arg| {
+            let (offset, path) = first_tainted(&code[arg.clone()], &taint)?;
+            let steps = trace(&taint, path, |_, _| false)?;
+            Some((arg.start + offset..arg.start + offset + path.len(), steps))
+        });
+        let Some((mention, steps)) = tainted else {
+            continue;
+        };
+        let sink = format!("{}()", sink_name(&m));
+        diagnostics.push(
+            tainted_sink(
+                content,
+                Diagnostic::error("DYNAMIC_CODE_EXECUTION", &format!("{} runs code built from untrusted input", sink)),
+                mention,
+                &sink,
+                &steps,
+            )
+            .with_suggestion("Parse data with JSON.parse, or map input values to fixed functions; never execute input as code"),
+        );
+    }
+
+    for m in STORAGE_KEY_CALL.find_iter(code) {
+        let Some(key) = call_args(code, &m).into_iter().next() else {
+            continue;
+        };
+        if lead(content, code, key.clone(), &taint).fixes_key() {
+            continue;
+        }
+        let Some((offset, path)) = first_tainted(&code[key.clone()], &taint) else {
+            continue;
+        };
+        let Some(steps) = trace(&taint, path, |_, origin| origin.lead.fixes_key()) else {
+            continue;
+        };
+        let mention = key.start + offset..key.start + offset + path.len();
+        let sink = format!("{}() key", sink_name(&m));
+        diagnostics.push(
+            tainted_sink(
+                content,
+                Diagnostic::warning("TAINTED_STORAGE_KEY", "Storage key is taken entirely from untrusted input"),
+                mention,
+                &sink,
+                &steps,
+            )
+            .with_suggestion("Prefix the key with a fixed namespace, e.g. `processed:${id}`, so input cannot read or overwrite other state"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(content: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        validate_taint(&Scan::new(content), &mut diagnostics);
+        diagnostics
+    }
+
+    #[test]
+    fn test_traces_sources_to_sinks() {
+        let content = r#"
+async function notify(target, body) {
+    return fetch(target, { method: 'POST', body });
+}
+export default defineWorkflow({
+    async execute({ trigger, inputs, storage }) {
+        const event = trigger.data;
+        const callback = event.callbackUrl;
+        await notify(callback, 'done');
+        await fetch(inputs.reportUrl);
+        const rule = new Function('row', inputs.filterCode);
+        await storage.put(event.id, { seen: true });
+        return { success: true };
+    },
+});
+"#;
+        let diagnostics = check(content);
+        let found: Vec<(&str, &str)> = diagnostics.iter().map(|d| (d.code.as_str(), d.message.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("SSRF_RISK", "Untrusted input can choose the host of a fetch request (trigger.data → event → callback → target → fetch() URL)"),
+                ("SSRF_RISK", "Untrusted input can choose the host of a fetch request (inputs.reportUrl → fetch() URL)"),
+                ("DYNAMIC_CODE_EXECUTION", "new Function() runs code built from untrusted input (inputs.filterCode → new Function())"),
+                ("TAINTED_STORAGE_KEY", "Storage key is taken entirely from untrusted input (trigger.data → event → storage.put() key)"),
+            ]
+        );
+        let first = &diagnostics[0];
+        assert_eq!(first.line, Some(3));
+        assert_eq!(first.related.len(), 3);
+        assert_eq!(first.related[0].message, "target receives callback");
+    }
+
+    #[test]
+    fn test_fixed_prefixes_and_validation_are_safe() {
+        let content = r#"
+async execute({ trigger, inputs, env, storage }) {
+    const id = trigger.data.id;
+    await fetch(`https://api.example.com/items/${id}`);
+    await fetch(`${env.API_BASE}/items/${inputs.itemId}`);
+    const base = 'https://hooks.example.com/' + inputs.path;
+    await http.post(base, {});
+    const target = inputs.webhookUrl;
+    if (!ALLOWED_HOSTS.includes(new URL(target).hostname)) throw new Error('blocked');
+    await fetch(target);
+    const key = `processed:${id}`;
+    await storage.set(key, true);
+    const parsed = JSON.parse(trigger.body);
+    // eval(trigger.body) in a comment is not a call
+    return { success: true };
+}
+"#;
+        assert!(check(content).is_empty());
+    }
+}
@@ -4,33 +4,24 @@
 //! but uses pre-compiled Rust regex patterns for significantly better performance.
 
 use serde::{Deserialize, Serialize};
+use crate::diagnostic::{Diagnostic, Severity, TextEdit};
+use crate::ai_models::{validate_ai_models, AiCostEstimate};
+use crate::ai_usage::{validate_workflow_type, AiUsage};
+use crate::budget::{validate_budget, BudgetEstimate};
+use crate::config::ValidatorConfig;
+use crate::custom_rules::validate_custom_rules;
+use crate::error_handling::{validate_error_handling, ErrorHandlingCoverage};
+use crate::inputs::validate_inputs;
+use crate::integration_hosts::validate_raw_api_calls;
+use crate::logging::validate_logging;
+use crate::sdk_deprecations::validate_sdk_deprecations;
+use crate::taint::validate_taint;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::config::{discover_config, ConfigError};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sdk_deprecations::discover_sdk_version;
 use crate::patterns::*;
-
-/// Validation error
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ValidationError {
-    #[serde(rename = "type")]
-    pub error_type: String,
-    pub code: String,
-    pub message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub line: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub suggestion: Option<String>,
-}
-
-/// Validation warning
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ValidationWarning {
-    #[serde(rename = "type")]
-    pub warning_type: String,
-    pub code: String,
-    pub message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub line: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub suggestion: Option<String>,
-}
+use crate::scan::{Probe, Scan};
 
 /// Workflow metadata extracted during validation
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -49,6 +40,14 @@ pub struct WorkflowMetadata {
     pub has_ai: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pricing: Option<PricingMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetEstimate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai: Option<AiCostEstimate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_usage: Option<AiUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_handling: Option<ErrorHandlingCoverage>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -60,46 +59,78 @@ pub struct PricingMetadata {
 }
 
 /// Validation result
+///
+/// Serialized with separate `errors` and `warnings` arrays (plus `info` for
+/// info and hint diagnostics) to match the TypeScript `ValidationResult`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "ValidationResultRepr", from = "ValidationResultRepr")]
 pub struct ValidationResult {
     pub valid: bool,
-    pub errors: Vec<ValidationError>,
-    pub warnings: Vec<ValidationWarning>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Vec<Diagnostic>,
     pub metadata: Option<WorkflowMetadata>,
 }
 
-impl ValidationError {
-    pub fn new(code: &str, message: &str) -> Self {
+impl ValidationResult {
+    /// Builds a result from diagnostics; valid when none are errors.
+    pub fn new(diagnostics: Vec<Diagnostic>, metadata: Option<WorkflowMetadata>) -> Self {
         Self {
-            error_type: "error".to_string(),
-            code: code.to_string(),
-            message: message.to_string(),
-            line: None,
-            suggestion: None,
+            valid: !diagnostics.iter().any(Diagnostic::is_error),
+            diagnostics,
+            metadata,
         }
     }
 
-    pub fn with_suggestion(mut self, suggestion: &str) -> Self {
-        self.suggestion = Some(suggestion.to_string());
-        self
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Warning)
+    }
+
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(move |d| d.severity == severity)
     }
 }
 
-impl ValidationWarning {
-    pub fn new(code: &str, message: &str) -> Self {
-        Self {
-            warning_type: "warning".to_string(),
-            code: code.to_string(),
-            message: message.to_string(),
-            line: None,
-            suggestion: None,
+/// Wire format of [`ValidationResult`]
+#[derive(Serialize, Deserialize)]
+struct ValidationResultRepr {
+    valid: bool,
+    errors: Vec<Diagnostic>,
+    warnings: Vec<Diagnostic>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    info: Vec<Diagnostic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<WorkflowMetadata>,
+}
+
+impl From<ValidationResult> for ValidationResultRepr {
+    fn from(result: ValidationResult) -> Self {
+        let mut repr = Self {
+            valid: result.valid,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            info: Vec::new(),
+            metadata: result.metadata,
+        };
+        for d in result.diagnostics {
+            match d.severity {
+                Severity::Error => repr.errors.push(d),
+                Severity::Warning => repr.warnings.push(d),
+                Severity::Info | Severity::Hint => repr.info.push(d),
+            }
         }
+        repr
     }
+}
 
-    pub fn with_suggestion(mut self, suggestion: &str) -> Self {
-        self.suggestion = Some(suggestion.to_string());
-        self
+impl From<ValidationResultRepr> for ValidationResult {
+    fn from(repr: ValidationResultRepr) -> Self {
+        let mut diagnostics = repr.errors;
+        diagnostics.extend(repr.warnings);
+        diagnostics.extend(repr.info);
+        Self { valid: repr.valid, diagnostics, metadata: repr.metadata }
     }
 }
 
@@ -135,168 +166,249 @@ fn get_npm_package_suggestion(package: &str) -> &'static str {
     }
 }
 
+/// An analyzer run by [`validate_workflow_with_config`]
+type Analyzer = fn(&Scan, &ValidatorConfig, &mut Vec<Diagnostic>, &mut WorkflowMetadata);
+
+/// Every analyzer, by name, in the order they run. Later analyzers may read
+/// the metadata earlier ones filled in.
+static ANALYZERS: &[(&str, Analyzer)] = &[
+    ("imports", |scan, config, d, m| validate_imports(scan, config, d, m)),
+    ("definition", |scan, _, d, m| validate_workflow_definition(scan, d, m)),
+    ("execute", |scan, _, d, _| validate_execute_function(scan, d)),
+    ("integrations", |scan, config, d, m| validate_integrations(scan, config, d, m)),
+    // Reads the integrations declared above
+    ("integration_hosts", |scan, _, d, m| validate_raw_api_calls(scan, d, m)),
+    ("trigger", |scan, _, d, m| validate_trigger(scan, d, m)),
+    ("pricing", |scan, _, d, m| validate_pricing(scan, d, m)),
+    ("ai_usage", |scan, _, d, m| validate_ai_usage(scan, d, m)),
+    ("ai_models", |scan, _, d, m| validate_ai_models(scan, d, m)),
+    ("workflow_type", |scan, _, d, m| validate_workflow_type(scan, d, m)),
+    ("common_mistakes", |scan, config, d, _| validate_common_mistakes(scan, config, d)),
+    // SDK APIs deprecated in the targeted release
+    ("sdk_deprecations", |scan, config, d, _| validate_sdk_deprecations(scan, d, config.sdk.version.as_deref())),
+    // Input reads against the declared inputs
+    ("inputs", |scan, _, d, _| validate_inputs(scan, d)),
+    // Untrusted values followed into URLs, dynamic code and storage keys
+    ("taint", |scan, _, d, _| validate_taint(scan, d)),
+    // What console calls log
+    ("logging", |scan, _, d, _| validate_logging(scan, d)),
+    // Error handling around outbound calls
+    ("error_handling", |scan, _, d, m| validate_error_handling(scan, d, m)),
+    // Subrequest and CPU budget
+    ("budget", |scan, _, d, m| validate_budget(scan, d, m)),
+    // Project-specific pattern rules
+    ("custom_rules", |scan, config, d, _| validate_custom_rules(scan, &config.custom_rules, d)),
+];
+
 /// Validate a workflow file content
 pub fn validate_workflow(content: &str) -> ValidationResult {
-    let mut errors = Vec::new();
-    let mut warnings = Vec::new();
-    let mut metadata = WorkflowMetadata::default();
-
-    // Validate imports
-    validate_imports(content, &mut errors, &mut warnings, &mut metadata);
-
-    // Validate workflow definition
-    validate_workflow_definition(content, &mut errors, &mut warnings, &mut metadata);
+    validate_workflow_with_config(content, &ValidatorConfig::default(), None)
+}
 
-    // Validate execute function
-    validate_execute_function(content, &mut errors, &mut warnings);
+/// Validate workflow content under a project configuration.
+///
+/// `path` selects which `[[overrides]]` apply; it should be relative to the
+/// directory holding the configuration file.
+pub fn validate_workflow_with_config(
+    content: &str,
+    config: &ValidatorConfig,
+    path: Option<&str>,
+) -> ValidationResult {
+    let config = config.for_path(path);
+    let scan = Scan::new(content);
+    let mut diagnostics = Vec::new();
+    let mut metadata = WorkflowMetadata::default();
 
-    // Validate integrations
-    validate_integrations(content, &mut errors, &mut warnings, &mut metadata);
+    for (_, analyzer) in ANALYZERS {
+        analyzer(&scan, &config, &mut diagnostics, &mut metadata);
+    }
 
-    // Validate trigger
-    validate_trigger(content, &mut errors, &mut warnings, &mut metadata);
+    // Apply rule switches and severity overrides
+    config.apply_rules(&mut diagnostics);
 
-    // Validate pricing
-    validate_pricing(content, &mut errors, &mut warnings, &mut metadata);
+    ValidationResult::new(diagnostics, Some(metadata))
+}
 
-    // Validate AI usage
-    validate_ai_usage(content, &mut errors, &mut warnings, &mut metadata);
+/// Names of the analyzers validation runs, in order
+pub fn analyzer_names() -> impl Iterator<Item = &'static str> {
+    ANALYZERS.iter().map(|(name, _)| *name)
+}
 
-    // Validate common mistakes
-    validate_common_mistakes(content, &mut errors, &mut warnings);
+/// Run one analyzer on its own over `content`, under the default
+/// configuration, and return what it reports (`None` for an unknown name).
+///
+/// For benchmarks: each run includes the probe scan, and an analyzer that
+/// reads metadata sees none from the analyzers before it.
+pub fn run_analyzer(name: &str, content: &str) -> Option<Vec<Diagnostic>> {
+    let (_, analyzer) = ANALYZERS.iter().find(|(n, _)| *n == name)?;
+    let mut diagnostics = Vec::new();
+    analyzer(&Scan::new(content), &ValidatorConfig::default(), &mut diagnostics, &mut WorkflowMetadata::default());
+    Some(diagnostics)
+}
 
-    ValidationResult {
-        valid: errors.is_empty(),
-        errors,
-        warnings,
-        metadata: Some(metadata),
-    }
+/// Validate a file on disk, using the nearest `.workway-validator.toml` and,
+/// unless it sets `[sdk] version`, the SDK range in the nearest `package.json`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn validate_file(path: &std::path::Path) -> Result<ValidationResult, ConfigError> {
+    let content = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })?;
+    let loaded = discover_config(path)?;
+    let relative = loaded.as_ref().and_then(|l| l.relative_path(path));
+    let mut config = loaded.map(|l| l.config).unwrap_or_default();
+    if config.sdk.version.is_none() {
+        config.sdk.version = discover_sdk_version(path);
+    }
+    Ok(validate_workflow_with_config(&content, &config, relative.as_deref()))
 }
 
 fn validate_imports(
-    content: &str,
-    errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    config: &ValidatorConfig,
+    diagnostics: &mut Vec<Diagnostic>,
     metadata: &mut WorkflowMetadata,
 ) {
+    let content = scan.content();
+
     // Check for SDK import
-    if !SDK_IMPORT.is_match(content) {
-        errors.push(
-            ValidationError::new("MISSING_SDK_IMPORT", "Workflow must import from @workway/sdk")
+    if !scan.has(Probe::SdkImport) {
+        diagnostics.push(
+            Diagnostic::error("MISSING_SDK_IMPORT", "Workflow must import from @workway/sdk")
                 .with_suggestion("Add: import { defineWorkflow } from '@workway/sdk'")
+                .with_fix("Import defineWorkflow from @workway/sdk", vec![
+                    TextEdit::insert(0, "import { defineWorkflow } from '@workway/sdk';\n"),
+                ])
         );
     }
 
     // Check for Workers AI import if using AI
-    let has_ai_usage = AI_USAGE.is_match(content);
-    let has_workers_ai_import = WORKERS_AI_IMPORT.is_match(content);
+    let has_ai_usage = scan.has(Probe::AiUsage);
+    let has_workers_ai_import = scan.has(Probe::WorkersAiImport);
 
     if has_ai_usage && !has_workers_ai_import {
-        warnings.push(
-            ValidationWarning::new("MISSING_AI_IMPORT", "AI usage detected but no workers-ai import found")
+        diagnostics.push(
+            Diagnostic::warning("MISSING_AI_IMPORT", "AI usage detected but no workers-ai import found")
                 .with_suggestion("Add: import { createAIClient, AIModels } from '@workway/sdk/workers-ai'")
+                .with_fix("Import createAIClient and AIModels", vec![
+                    TextEdit::insert(0, "import { createAIClient, AIModels } from '@workway/sdk/workers-ai';\n"),
+                ])
         );
     }
 
     metadata.has_ai = Some(has_ai_usage);
 
+    let imports = scan.imports();
+    if imports.is_empty() {
+        return;
+    }
+
     // Check for blocked Node.js modules
-    for module in BLOCKED_NODE_MODULES {
-        let pattern = blocked_module_pattern(module);
-        if pattern.is_match(content) {
-            errors.push(
-                ValidationError::new(
+    for module in BLOCKED_NODE_MODULES.iter().filter(|m| !config.is_allowed_module(m)) {
+        if let Some(range) = imports.get(module) {
+            diagnostics.push(
+                Diagnostic::error(
                     "BLOCKED_NODE_MODULE",
                     &format!("Node.js module '{}' is not available in Cloudflare Workers", module)
-                ).with_suggestion(get_node_module_suggestion(module))
+                ).with_span(content, range.clone()).with_suggestion(get_node_module_suggestion(module))
             );
         }
     }
 
     // Check for blocked npm packages
-    for package in BLOCKED_NPM_PACKAGES {
-        let pattern = blocked_module_pattern(package);
-        if pattern.is_match(content) {
-            warnings.push(
-                ValidationWarning::new(
+    for package in BLOCKED_NPM_PACKAGES.iter().filter(|p| !config.is_allowed_module(p)) {
+        if let Some(range) = imports.get(package) {
+            diagnostics.push(
+                Diagnostic::warning(
                     "INCOMPATIBLE_NPM_PACKAGE",
                     &format!("npm package '{}' is incompatible with Cloudflare Workers", package)
-                ).with_suggestion(get_npm_package_suggestion(package))
+                ).with_span(content, range.clone()).with_suggestion(get_npm_package_suggestion(package))
+            );
+        }
+    }
+
+    // Check for packages blocked by project configuration
+    for package in &config.modules.blocked {
+        if let Some(range) = imports.get(package.as_str()) {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "INCOMPATIBLE_NPM_PACKAGE",
+                    &format!("npm package '{}' is blocked by project configuration", package)
+                ).with_span(content, range.clone()).with_suggestion("See modules.blocked in .workway-validator.toml")
             );
         }
     }
 }
 
 fn validate_workflow_definition(
-    content: &str,
-    errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
     metadata: &mut WorkflowMetadata,
 ) {
     // Check for defineWorkflow or export default
-    let has_define_workflow = DEFINE_WORKFLOW.is_match(content);
-    let has_export_default = EXPORT_DEFAULT.is_match(content);
+    let has_define_workflow = scan.has(Probe::DefineWorkflow);
+    let has_export_default = scan.has(Probe::ExportDefault);
 
     if !has_define_workflow && !has_export_default {
-        errors.push(
-            ValidationError::new("NO_WORKFLOW_EXPORT", "Workflow must use defineWorkflow() or export default")
+        diagnostics.push(
+            Diagnostic::error("NO_WORKFLOW_EXPORT", "Workflow must use defineWorkflow() or export default")
                 .with_suggestion("Wrap your workflow in defineWorkflow({ ... })")
         );
     }
 
     // Extract workflow name
-    if let Some(caps) = WORKFLOW_NAME.captures(content) {
+    if let Some(caps) = scan.captures(Probe::WorkflowName) {
         metadata.name = caps.get(1).map(|m| m.as_str().to_string());
     } else {
-        warnings.push(
-            ValidationWarning::new("MISSING_NAME", "Workflow should have a name property")
+        diagnostics.push(
+            Diagnostic::warning("MISSING_NAME", "Workflow should have a name property")
                 .with_suggestion("Add: name: 'My Workflow'")
         );
     }
 
     // Extract workflow type
-    if let Some(caps) = WORKFLOW_TYPE.captures(content) {
+    if let Some(caps) = scan.captures(Probe::WorkflowType) {
         metadata.workflow_type = caps.get(1).map(|m| m.as_str().to_string());
     }
 }
 
 fn validate_execute_function(
-    content: &str,
-    errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
 ) {
     // Check for execute or run function
-    let has_execute = HAS_EXECUTE.is_match(content);
-    let has_run = HAS_RUN.is_match(content);
+    let has_execute = scan.has(Probe::HasExecute);
+    let has_run = scan.has(Probe::HasRun);
 
     if !has_execute && !has_run {
-        errors.push(
-            ValidationError::new("MISSING_EXECUTE", "Workflow must have an execute or run function")
+        diagnostics.push(
+            Diagnostic::error("MISSING_EXECUTE", "Workflow must have an execute or run function")
                 .with_suggestion("Add: async execute({ trigger, actions }) { ... }")
         );
     }
 
-    // Check for return statement in execute
-    if let Some(caps) = EXECUTE_BODY.captures(content) {
-        if let Some(body) = caps.get(1) {
-            if !body.as_str().contains("return") {
-                warnings.push(
-                    ValidationWarning::new("NO_RETURN", "Execute function should return a result")
-                        .with_suggestion("Add: return { success: true, data: ... }")
-                );
-            }
+    // Check for return statement in execute. The body pattern matches the
+    // TypeScript validator's (one level of nested braces), so both report
+    // the same files; it needs `execute(`, which HAS_EXECUTE also matches
+    let body = has_execute.then(|| EXECUTE_BODY.captures(scan.content())).flatten();
+    if let Some(body) = body.and_then(|caps| caps.get(1)) {
+        if !body.as_str().contains("return") {
+            diagnostics.push(
+                Diagnostic::warning("NO_RETURN", "Execute function should return a result")
+                    .with_suggestion("Add: return { success: true, data: ... }")
+            );
         }
     }
 }
 
 fn validate_integrations(
-    content: &str,
-    _errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    config: &ValidatorConfig,
+    diagnostics: &mut Vec<Diagnostic>,
     metadata: &mut WorkflowMetadata,
 ) {
     // Extract integrations array
-    if let Some(caps) = INTEGRATIONS_BLOCK.captures(content) {
+    if let Some(caps) = scan.captures(Probe::IntegrationsBlock) {
         if let Some(block) = caps.get(1) {
             let block_str = block.as_str();
             let mut integrations = Vec::new();
@@ -312,7 +424,7 @@ fn validate_integrations(
             for caps in SHORTHAND_INTEGRATIONS.captures_iter(block_str) {
                 if let Some(name) = caps.get(1) {
                     let name_lower = name.as_str().to_lowercase();
-                    if KNOWN_INTEGRATIONS.contains(&name_lower.as_str()) && !integrations.contains(&name_lower) {
+                    if config.is_known_integration(&name_lower) && !integrations.contains(&name_lower) {
                         integrations.push(name_lower);
                     }
                 }
@@ -320,9 +432,9 @@ fn validate_integrations(
 
             // Validate each integration
             for integration in &integrations {
-                if !KNOWN_INTEGRATIONS.contains(&integration.as_str()) {
-                    warnings.push(
-                        ValidationWarning::new(
+                if !config.is_known_integration(integration) {
+                    diagnostics.push(
+                        Diagnostic::warning(
                             "UNKNOWN_INTEGRATION",
                             &format!("Unknown integration: {}", integration)
                         ).with_suggestion(&format!("Valid integrations: {}...", KNOWN_INTEGRATIONS[..5].join(", ")))
@@ -332,8 +444,8 @@ fn validate_integrations(
 
             // Check for scope definitions
             if !integrations.is_empty() && !block_str.contains("scopes") {
-                warnings.push(
-                    ValidationWarning::new("MISSING_SCOPES", "Integrations should specify required scopes")
+                diagnostics.push(
+                    Diagnostic::warning("MISSING_SCOPES", "Integrations should specify required scopes")
                         .with_suggestion("Add: scopes: ['read_data', 'write_data']")
                 );
             }
@@ -346,35 +458,35 @@ fn validate_integrations(
 }
 
 fn validate_trigger(
-    content: &str,
-    errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
     metadata: &mut WorkflowMetadata,
 ) {
     // Check for trigger definition
-    if !HAS_TRIGGER.is_match(content) {
-        errors.push(
-            ValidationError::new("MISSING_TRIGGER", "Workflow must define a trigger")
+    let content = scan.content();
+    if !scan.has(Probe::HasTrigger) {
+        diagnostics.push(
+            Diagnostic::error("MISSING_TRIGGER", "Workflow must define a trigger")
                 .with_suggestion("Add: trigger: webhook({ service: 'stripe', event: 'payment.succeeded' })")
         );
         return;
     }
 
     // Extract trigger type
-    if let Some(caps) = TRIGGER_TYPE.captures(content) {
+    if let Some(caps) = scan.captures(Probe::TriggerType) {
         metadata.trigger = caps.get(1).map(|m| m.as_str().to_string());
-    } else if let Some(caps) = TRIGGER_OBJECT_TYPE.captures(content) {
+    } else if let Some(caps) = scan.captures(Probe::TriggerObjectType) {
         metadata.trigger = caps.get(1).map(|m| m.as_str().to_string());
     }
 
     // Validate webhook trigger
     if content.contains("webhook(") {
-        if let Some(caps) = WEBHOOK_CONFIG.captures(content) {
+        if let Some(caps) = scan.captures(Probe::WebhookConfig) {
             if let Some(config) = caps.get(1) {
                 let config_str = config.as_str();
                 if !config_str.contains("service") && !config_str.contains("event") {
-                    warnings.push(
-                        ValidationWarning::new("INCOMPLETE_WEBHOOK", "Webhook trigger should specify service and event")
+                    diagnostics.push(
+                        Diagnostic::warning("INCOMPLETE_WEBHOOK", "Webhook trigger should specify service and event")
                             .with_suggestion("Add: service: 'stripe', event: 'payment.succeeded'")
                     );
                 }
@@ -384,12 +496,13 @@ fn validate_trigger(
 
     // Validate schedule trigger
     if content.contains("schedule(") {
-        if let Some(caps) = SCHEDULE_EXPR.captures(content) {
+        if let Some(caps) = scan.captures(Probe::ScheduleExpr) {
             if let Some(cron_match) = caps.get(1) {
                 let cron_expr = cron_match.as_str();
                 if !is_valid_cron(cron_expr) {
-                    errors.push(
-                        ValidationError::new("INVALID_CRON", &format!("Invalid cron expression: {}", cron_expr))
+                    diagnostics.push(
+                        Diagnostic::error("INVALID_CRON", &format!("Invalid cron expression: {}", cron_expr))
+                            .with_span(content, cron_match.range())
                             .with_suggestion("Use format: '0 8 * * *' (minute hour day month weekday)")
                     );
                 }
@@ -399,14 +512,13 @@ fn validate_trigger(
 }
 
 fn validate_pricing(
-    content: &str,
-    _errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
     metadata: &mut WorkflowMetadata,
 ) {
-    if !HAS_PRICING.is_match(content) {
-        warnings.push(
-            ValidationWarning::new("MISSING_PRICING", "Workflow should define pricing for marketplace")
+    if !scan.has(Probe::HasPricing) {
+        diagnostics.push(
+            Diagnostic::warning("MISSING_PRICING", "Workflow should define pricing for marketplace")
                 .with_suggestion("Add: pricing: { model: 'subscription', price: 10, executions: 100 }")
         );
         return;
@@ -415,101 +527,111 @@ fn validate_pricing(
     let mut pricing = PricingMetadata::default();
 
     // Extract pricing model
-    if let Some(caps) = PRICING_MODEL.captures(content) {
+    if let Some(caps) = scan.captures(Probe::PricingModel) {
         pricing.model = caps.get(1).map(|m| m.as_str().to_string());
     }
 
     // Extract price
-    if let Some(caps) = PRICING_PRICE.captures(content) {
+    if let Some(caps) = scan.captures(Probe::PricingPrice) {
         if let Some(price_match) = caps.get(1) {
             pricing.price = price_match.as_str().parse().ok();
         }
     }
 
     // Validate subscription pricing has executions
-    if pricing.model.as_deref() == Some("subscription") {
-        if !HAS_EXECUTIONS.is_match(content) {
-            warnings.push(
-                ValidationWarning::new("MISSING_EXECUTIONS", "Subscription pricing should specify executions limit")
-                    .with_suggestion("Add: executions: 100")
-            );
-        }
+    if pricing.model.as_deref() == Some("subscription") && !scan.has(Probe::HasExecutions) {
+        diagnostics.push(
+            Diagnostic::warning("MISSING_EXECUTIONS", "Subscription pricing should specify executions limit")
+                .with_suggestion("Add: executions: 100")
+        );
     }
 
     metadata.pricing = Some(pricing);
 }
 
 fn validate_ai_usage(
-    content: &str,
-    _errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    diagnostics: &mut Vec<Diagnostic>,
     metadata: &mut WorkflowMetadata,
 ) {
     // Check for external AI providers
-    if EXTERNAL_AI.is_match(content) {
-        warnings.push(
-            ValidationWarning::new("EXTERNAL_AI_DETECTED", "External AI providers detected. WORKWAY uses Cloudflare Workers AI only.")
+    if let Some(m) = scan.find(Probe::ExternalAi) {
+        diagnostics.push(
+            Diagnostic::warning("EXTERNAL_AI_DETECTED", "External AI providers detected. WORKWAY uses Cloudflare Workers AI only.")
+                .with_span(scan.content(), m.range())
                 .with_suggestion("Use: createAIClient(env) with AIModels.LLAMA_3_8B or AIModels.MISTRAL_7B")
         );
     }
 
     // Check for proper AI client usage
-    if metadata.has_ai == Some(true) {
-        if !ENV_ACCESS.is_match(content) {
-            warnings.push(
-                ValidationWarning::new("MISSING_ENV_ACCESS", "AI usage requires env parameter in execute function")
-                    .with_suggestion("Update: async execute({ trigger, actions, env }) { ... }")
-            );
-        }
+    if metadata.has_ai == Some(true) && !scan.has(Probe::EnvAccess) {
+        diagnostics.push(
+            Diagnostic::warning("MISSING_ENV_ACCESS", "AI usage requires env parameter in execute function")
+                .with_suggestion("Update: async execute({ trigger, actions, env }) { ... }")
+        );
     }
 }
 
 fn validate_common_mistakes(
-    content: &str,
-    errors: &mut Vec<ValidationError>,
-    warnings: &mut Vec<ValidationWarning>,
+    scan: &Scan,
+    config: &ValidatorConfig,
+    diagnostics: &mut Vec<Diagnostic>,
 ) {
+    let content = scan.content();
+
     // Check for console.log
-    let console_count = CONSOLE_STATEMENTS.find_iter(content).count();
-    if console_count > 3 {
-        warnings.push(
-            ValidationWarning::new("EXCESSIVE_LOGGING", &format!("Found {} console statements", console_count))
+    let console_count = scan.count(Probe::ConsoleStatement);
+    if console_count > config.thresholds.max_console_statements {
+        diagnostics.push(
+            Diagnostic::warning("EXCESSIVE_LOGGING", &format!("Found {} console statements", console_count))
                 .with_suggestion("Consider reducing logging in production builds")
         );
     }
 
     // Check for hardcoded secrets
-    if SECRET_API_KEY.is_match(content)
-        || SECRET_SECRET.is_match(content)
-        || SECRET_PASSWORD.is_match(content)
-        || SECRET_TOKEN.is_match(content)
-    {
-        errors.push(
-            ValidationError::new("HARDCODED_SECRET", "Possible hardcoded secret detected")
+    let secret = scan.find(Probe::SecretApiKey)
+        .or_else(|| scan.find(Probe::SecretSecret))
+        .or_else(|| scan.find(Probe::SecretPassword))
+        .or_else(|| scan.find(Probe::SecretToken));
+    if let Some(m) = secret {
+        diagnostics.push(
+            Diagnostic::error("HARDCODED_SECRET", "Possible hardcoded secret detected")
+                .with_span(content, m.range())
                 .with_suggestion("Use environment variables or secrets manager instead")
         );
     }
 
     // Check for await inside loops
-    if AWAIT_IN_FOR_LOOP.is_match(content) || AWAIT_IN_WHILE_LOOP.is_match(content) {
-        warnings.push(
-            ValidationWarning::new("AWAIT_IN_LOOP", "Await inside loop detected (may affect performance)")
+    if let Some(m) = AWAIT_IN_FOR_LOOP.find(content).or_else(|| AWAIT_IN_WHILE_LOOP.find(content)) {
+        diagnostics.push(
+            Diagnostic::warning("AWAIT_IN_LOOP", "Await inside loop detected (may affect performance)")
+                .with_span(content, m.range())
                 .with_suggestion("Consider using Promise.all() for parallel execution")
         );
     }
 
     // Check for empty catch blocks
-    if EMPTY_CATCH.is_match(content) {
-        warnings.push(
-            ValidationWarning::new("EMPTY_CATCH", "Empty catch block detected")
+    if let Some(m) = scan.find(Probe::EmptyCatch) {
+        diagnostics.push(
+            Diagnostic::warning("EMPTY_CATCH", "Empty catch block detected")
+                .with_span(content, m.range())
                 .with_suggestion("Handle or re-throw errors properly")
         );
     }
 }
 
-/// Validate a cron expression
-fn is_valid_cron(expr: &str) -> bool {
-    let parts: Vec<&str> = expr.trim().split_whitespace().collect();
+/// Cron fields in order: (name, lowest value, highest value)
+pub const CRON_FIELDS: [(&str, u32, u32); 5] = [
+    ("minute", 0, 59),
+    ("hour", 0, 23),
+    ("day of month", 1, 31),
+    ("month", 1, 12),
+    ("day of week", 0, 6),
+];
+
+/// Validate a five-field cron expression (minute hour day month weekday)
+pub fn is_valid_cron(expr: &str) -> bool {
+    let parts: Vec<&str> = expr.split_whitespace().collect();
     if parts.len() != 5 {
         return false;
     }
@@ -522,8 +644,6 @@ fn is_valid_cron(expr: &str) -> bool {
         &*CRON_WEEKDAY,
     ];
 
-    // Maximum values for each cron field
-    let max_values = [59, 23, 31, 12, 6]; // minute, hour, day, month, weekday
 
     for (i, part) in parts.iter().enumerate() {
         if *part == "*" {
@@ -539,7 +659,7 @@ fn is_valid_cron(expr: &str) -> bool {
                 let range_parts: Vec<&str> = segment.split('-').collect();
                 range_parts.iter().all(|num| {
                     if let Ok(n) = num.parse::<u32>() {
-                        n <= max_values[i]
+                        n <= CRON_FIELDS[i].2
                     } else {
                         false
                     }
@@ -579,7 +699,7 @@ export default defineWorkflow({
 "#;
         let result = validate_workflow(content);
         assert!(result.valid);
-        assert!(result.errors.is_empty());
+        assert!(result.errors().next().is_none());
     }
 
     #[test]
@@ -587,7 +707,64 @@ export default defineWorkflow({
         let content = "export default { name: 'test' }";
         let result = validate_workflow(content);
         assert!(!result.valid);
-        assert!(result.errors.iter().any(|e| e.code == "MISSING_SDK_IMPORT"));
+        assert!(result.errors().any(|e| e.code == "MISSING_SDK_IMPORT"));
+    }
+
+    #[test]
+    fn test_config_rules_and_thresholds() {
+        let content = r#"
+import { defineWorkflow } from '@workway/sdk';
+import _ from 'lodash';
+
+export default defineWorkflow({
+    name: 'Noisy',
+    integrations: [{ service: 'acme-crm', scopes: ['read'] }],
+    trigger: webhook({ service: 'stripe', event: 'payment.succeeded' }),
+    async execute() {
+        console.log(1); console.log(2); console.log(3); console.log(4);
+        try { run(); } catch (e) {}
+        return { success: true };
+    }
+});
+"#;
+        let default = validate_workflow(content);
+        assert!(default.warnings().any(|w| w.code == "EXCESSIVE_LOGGING"));
+        assert!(default.warnings().any(|w| w.code == "UNKNOWN_INTEGRATION"));
+
+        let config = ValidatorConfig::parse(
+            r#"
+[rules]
+EMPTY_CATCH = "off"
+MISSING_PRICING = "error"
+
+[thresholds]
+max_console_statements = 5
+
+[modules]
+blocked = ["lodash"]
+
+[integrations]
+known = ["acme-crm"]
+"#,
+        )
+        .unwrap();
+        let result = validate_workflow_with_config(content, &config, None);
+        assert!(!result.valid);
+        assert!(result.errors().any(|e| e.code == "MISSING_PRICING"));
+        assert!(result.diagnostics.iter().all(|d| d.code != "EMPTY_CATCH"
+            && d.code != "EXCESSIVE_LOGGING"
+            && d.code != "UNKNOWN_INTEGRATION"));
+        assert!(result.warnings().any(|w| w.code == "INCOMPATIBLE_NPM_PACKAGE" && w.message.contains("lodash")));
+    }
+
+    #[test]
+    fn test_run_analyzer_reports_only_its_own_checks() {
+        let content = "import fs from 'fs';\nexport default defineWorkflow({});";
+        let imports = run_analyzer("imports", content).unwrap();
+        assert!(imports.iter().any(|d| d.code == "BLOCKED_NODE_MODULE"));
+        assert!(imports.iter().all(|d| d.code != "MISSING_EXECUTE"));
+        assert!(run_analyzer("execute", content).unwrap().iter().any(|d| d.code == "MISSING_EXECUTE"));
+        assert!(run_analyzer("unknown", content).is_none());
     }
 
     #[test]
@@ -597,5 +774,6 @@ export default defineWorkflow({
         assert!(is_valid_cron("0 0 1 * *"));
         assert!(!is_valid_cron("invalid"));
         assert!(!is_valid_cron("0 25 * * *")); // Invalid hour
+        assert!(!is_valid_cron("*/٣ * * * *")); // Non-ASCII digit
     }
 }
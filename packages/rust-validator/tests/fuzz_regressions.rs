@@ -0,0 +1,45 @@
+//! Replays the checked-in fuzz regressions under `cargo test`.
+//!
+//! Each file in `fuzz/regressions/<target>/` once crashed, hung or broke an
+//! invariant in that fuzz target. The checks are the fuzz crate's own
+//! (`fuzz/src/lib.rs`), so this needs neither cargo-fuzz nor nightly.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[path = "../fuzz/src/lib.rs"]
+mod fuzz;
+
+/// Generous enough for an unoptimized build; a regression of a pathological
+/// runtime case takes orders of magnitude longer
+const TIME_LIMIT: Duration = Duration::from_secs(5);
+
+fn replay(target: &str, check: fn(&str)) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/regressions").join(target);
+    let mut cases: Vec<_> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+    cases.sort();
+
+    for case in cases {
+        let content = fuzz::decode(&fs::read(&case).unwrap());
+        let start = Instant::now();
+        check(&content);
+        let elapsed = start.elapsed();
+        assert!(elapsed < TIME_LIMIT, "{} took {:?}", case.display(), elapsed);
+    }
+}
+
+#[test]
+fn test_validate_workflow_regressions() {
+    replay("validate_workflow", fuzz::check_workflow);
+}
+
+#[test]
+fn test_cron_regressions() {
+    replay("cron", fuzz::check_cron);
+}
+
+#[test]
+fn test_literal_regressions() {
+    replay("literal", fuzz::check_literal);
+}
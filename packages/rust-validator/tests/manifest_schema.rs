@@ -0,0 +1,35 @@
+//! Manifests extracted from the shipped workflows conform to
+//! `schema/manifest.schema.json`.
+//!
+//! The schema is what downstream tools code against, so every workflow in
+//! `packages/workflows` must produce a document it accepts.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use workway_validator::extract_manifest;
+
+#[test]
+fn test_workflow_manifests_match_schema() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let schema: Value = serde_json::from_str(&fs::read_to_string(root.join("schema/manifest.schema.json")).unwrap()).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for entry in fs::read_dir(root.join("../workflows/src")).unwrap().flatten() {
+        let Ok(content) = fs::read_to_string(entry.path().join("index.ts")) else {
+            continue;
+        };
+        let manifest = serde_json::to_value(extract_manifest(&content)).unwrap();
+        for error in validator.iter_errors(&manifest) {
+            failures.push(format!("{}: {} at {}", entry.file_name().to_string_lossy(), error, error.instance_path()));
+        }
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no workflows found in packages/workflows/src");
+    assert!(failures.is_empty(), "manifests that fail the schema:\n{}", failures.join("\n"));
+}
+
@@ -0,0 +1,175 @@
+//! Golden-fixture parity with the TypeScript validator.
+//!
+//! Each `fixtures/parity/<name>.ts` workflow has a `<name>.expected.json`
+//! snapshot of what both validators must report for it: validity, error and
+//! warning codes, and metadata. The same snapshots are checked by
+//! `packages/cli/src/lib/workflow-validator.parity.test.ts`, so a rule
+//! changed in one implementation only fails here until the other matches.
+//!
+//! Only the codes listed in `shared-codes.json` are compared; the Rust
+//! validator's extra rules (budget, AI models, ...) are out of scope.
+//!
+//! Snapshots use the TypeScript validator's metadata form, which differs from
+//! the WASM wire format only in spelling and empty values: `hasAi` is written
+//! `hasAI`, and an empty `integrations` list or a `pricing` block without a
+//! model or price is left out. Both harnesses apply the same mapping.
+//!
+//! Regenerate the snapshots after an intended change with
+//! `WORKWAY_UPDATE_FIXTURES=1 cargo test --test parity`, then review the
+//! diff and run the TypeScript side.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Map, Value};
+use workway_validator::{explain, validate_workflow, Severity, ValidationResult};
+
+/// Metadata keys produced by the TypeScript validator, with the key the WASM
+/// output uses for each
+const METADATA_KEYS: [(&str, &str); 6] = [
+    ("name", "name"),
+    ("type", "type"),
+    ("integrations", "integrations"),
+    ("trigger", "trigger"),
+    ("hasAI", "hasAi"),
+    ("pricing", "pricing"),
+];
+
+/// Whether a metadata value carries nothing: `[]` or `{}`
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/parity")
+}
+
+fn shared_codes() -> Vec<String> {
+    let source = fs::read_to_string(fixtures_dir().join("shared-codes.json")).unwrap();
+    serde_json::from_str(&source).unwrap()
+}
+
+fn fixtures() -> Vec<PathBuf> {
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ts"))
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+/// Writes whole numbers as integers, as JavaScript does, so `10` and
+/// `10.0` compare equal
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Number(n) => {
+            let f = n.as_f64().unwrap();
+            if f.fract() == 0.0 && f.abs() < i64::MAX as f64 { json!(f as i64) } else { json!(f) }
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(normalize).collect()),
+        Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, normalize(v))).collect()),
+        other => other,
+    }
+}
+
+/// The part of a result both validators agree on, in snapshot form
+fn snapshot(result: &ValidationResult, shared: &[String]) -> Value {
+    let codes = |severity: Severity| -> Vec<&str> {
+        result
+            .with_severity(severity)
+            .map(|d| d.code.as_str())
+            .filter(|code| shared.iter().any(|s| s == code))
+            .collect()
+    };
+    let errors = codes(Severity::Error);
+    let warnings = codes(Severity::Warning);
+
+    let mut metadata = Map::new();
+    if let Some(Value::Object(full)) = result.metadata.as_ref().map(|m| serde_json::to_value(m).unwrap()) {
+        for (key, wire_key) in METADATA_KEYS {
+            if let Some(value) = full.get(wire_key).filter(|v| !is_empty(v)) {
+                metadata.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+
+    json!({
+        "valid": errors.is_empty(),
+        "errors": errors,
+        "warnings": warnings,
+        "metadata": metadata,
+    })
+}
+
+#[test]
+fn test_fixtures_match_snapshots() {
+    let shared = shared_codes();
+    let update = std::env::var_os("WORKWAY_UPDATE_FIXTURES").is_some();
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures in {}", fixtures_dir().display());
+
+    let mut mismatches = Vec::new();
+    for fixture in &fixtures {
+        let content = fs::read_to_string(fixture).unwrap();
+        let actual = normalize(snapshot(&validate_workflow(&content), &shared));
+        let expected_path = fixture.with_extension("expected.json");
+
+        if update {
+            let mut rendered = serde_json::to_string_pretty(&actual).unwrap();
+            rendered.push('\n');
+            fs::write(&expected_path, rendered).unwrap();
+            continue;
+        }
+
+        let name = fixture.file_name().unwrap().to_string_lossy();
+        let Ok(source) = fs::read_to_string(&expected_path) else {
+            mismatches.push(format!("{}: missing {}", name, expected_path.display()));
+            continue;
+        };
+        let expected: Value = serde_json::from_str(&source).unwrap();
+        if normalize(expected.clone()) != actual {
+            mismatches.push(format!(
+                "{}:\n  expected {}\n  actual   {}",
+                name, expected, actual
+            ));
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "{} of {} parity fixtures differ (WORKWAY_UPDATE_FIXTURES=1 regenerates):\n{}",
+        mismatches.len(),
+        fixtures.len(),
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn test_snapshots_only_use_shared_codes() {
+    let shared = shared_codes();
+    for code in &shared {
+        assert!(explain(code).is_some(), "shared code {} is not documented", code);
+    }
+    for fixture in fixtures() {
+        let Ok(source) = fs::read_to_string(fixture.with_extension("expected.json")) else {
+            continue;
+        };
+        let expected: Value = serde_json::from_str(&source).unwrap();
+        for key in ["errors", "warnings"] {
+            for code in expected[key].as_array().unwrap() {
+                let code = code.as_str().unwrap();
+                assert!(
+                    shared.iter().any(|s| s == code),
+                    "{}: {} is not in shared-codes.json",
+                    fixture.display(),
+                    code
+                );
+            }
+        }
+    }
+}